@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS idx_issue_status;
+
+ALTER TABLE issue
+    DROP COLUMN IF EXISTS regressed_at,
+    DROP COLUMN IF EXISTS ignore_until_event_count,
+    DROP COLUMN IF EXISTS ignore_until,
+    DROP COLUMN IF EXISTS resolved_at,
+    DROP COLUMN IF EXISTS resolved_in_release,
+    DROP COLUMN IF EXISTS status;
@@ -0,0 +1,14 @@
+-- ============================================
+-- ISSUE LIFECYCLE
+-- ============================================
+
+ALTER TABLE issue
+    ADD COLUMN status TEXT NOT NULL DEFAULT 'unresolved'
+        CHECK (status IN ('unresolved', 'resolved', 'ignored')),
+    ADD COLUMN resolved_in_release TEXT,
+    ADD COLUMN resolved_at TIMESTAMP,
+    ADD COLUMN ignore_until TIMESTAMP,
+    ADD COLUMN ignore_until_event_count INTEGER,
+    ADD COLUMN regressed_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_issue_status ON issue(status);
@@ -0,0 +1 @@
+ALTER TABLE issue DROP COLUMN IF EXISTS grouping_strategy;
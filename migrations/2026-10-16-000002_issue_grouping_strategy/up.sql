@@ -0,0 +1,4 @@
+-- Which grouping rule produced the fingerprint of an issue
+-- (custom, in_app_frames, all_frames, exception_message, logentry).
+-- NULL for issues created before strategies were recorded.
+ALTER TABLE issue ADD COLUMN grouping_strategy TEXT;
@@ -0,0 +1,29 @@
+-- Reverting fails if the same hash, event_id or fingerprint exists in several projects.
+
+DROP INDEX IF EXISTS idx_issue_project;
+ALTER TABLE issue DROP CONSTRAINT IF EXISTS issue_project_fingerprint_key;
+ALTER TABLE issue DROP COLUMN IF EXISTS project_id;
+ALTER TABLE issue ADD CONSTRAINT issue_fingerprint_hash_key UNIQUE (fingerprint_hash);
+
+ALTER TABLE report DROP CONSTRAINT IF EXISTS report_project_event_id_key;
+ALTER TABLE report ADD CONSTRAINT report_event_id_key UNIQUE (event_id);
+
+ALTER TABLE report DROP CONSTRAINT IF EXISTS report_archive_fkey;
+ALTER TABLE queue_error DROP CONSTRAINT IF EXISTS queue_error_archive_fkey;
+ALTER TABLE queue_error DROP CONSTRAINT IF EXISTS queue_error_project_archive_key;
+ALTER TABLE queue_error DROP COLUMN IF EXISTS project_id;
+ALTER TABLE queue DROP CONSTRAINT IF EXISTS queue_archive_fkey;
+ALTER TABLE queue DROP CONSTRAINT IF EXISTS queue_project_archive_key;
+ALTER TABLE queue DROP COLUMN IF EXISTS project_id;
+
+ALTER TABLE archive DROP CONSTRAINT IF EXISTS archive_pkey;
+ALTER TABLE archive ADD PRIMARY KEY (hash);
+
+ALTER TABLE queue ADD CONSTRAINT queue_archive_hash_key UNIQUE (archive_hash);
+ALTER TABLE queue ADD CONSTRAINT queue_archive_hash_fkey
+    FOREIGN KEY (archive_hash) REFERENCES archive(hash);
+ALTER TABLE queue_error ADD CONSTRAINT queue_error_archive_hash_key UNIQUE (archive_hash);
+ALTER TABLE queue_error ADD CONSTRAINT queue_error_archive_hash_fkey
+    FOREIGN KEY (archive_hash) REFERENCES archive(hash);
+ALTER TABLE report ADD CONSTRAINT report_archive_hash_fkey
+    FOREIGN KEY (archive_hash) REFERENCES archive(hash);
@@ -0,0 +1,119 @@
+-- ============================================
+-- PROJECT-SCOPED KEYS
+-- archive.hash, report.event_id and issue.fingerprint_hash
+-- are now unique per project instead of globally.
+-- ============================================
+
+-- Archive: primary key (project_id, hash), referenced by (project_id, archive_hash)
+ALTER TABLE queue DROP CONSTRAINT IF EXISTS queue_archive_hash_fkey;
+ALTER TABLE queue_error DROP CONSTRAINT IF EXISTS queue_error_archive_hash_fkey;
+ALTER TABLE report DROP CONSTRAINT IF EXISTS report_archive_hash_fkey;
+
+ALTER TABLE archive DROP CONSTRAINT IF EXISTS archive_pkey;
+ALTER TABLE archive ADD PRIMARY KEY (project_id, hash);
+
+ALTER TABLE queue ADD COLUMN project_id INTEGER;
+UPDATE queue q SET project_id = a.project_id FROM archive a WHERE a.hash = q.archive_hash;
+ALTER TABLE queue ALTER COLUMN project_id SET NOT NULL;
+ALTER TABLE queue DROP CONSTRAINT IF EXISTS queue_archive_hash_key;
+ALTER TABLE queue ADD CONSTRAINT queue_project_archive_key UNIQUE (project_id, archive_hash);
+ALTER TABLE queue ADD CONSTRAINT queue_archive_fkey
+    FOREIGN KEY (project_id, archive_hash) REFERENCES archive(project_id, hash);
+
+ALTER TABLE queue_error ADD COLUMN project_id INTEGER;
+UPDATE queue_error q SET project_id = a.project_id FROM archive a WHERE a.hash = q.archive_hash;
+ALTER TABLE queue_error ALTER COLUMN project_id SET NOT NULL;
+ALTER TABLE queue_error DROP CONSTRAINT IF EXISTS queue_error_archive_hash_key;
+ALTER TABLE queue_error ADD CONSTRAINT queue_error_project_archive_key UNIQUE (project_id, archive_hash);
+ALTER TABLE queue_error ADD CONSTRAINT queue_error_archive_fkey
+    FOREIGN KEY (project_id, archive_hash) REFERENCES archive(project_id, hash);
+
+ALTER TABLE report ADD CONSTRAINT report_archive_fkey
+    FOREIGN KEY (project_id, archive_hash) REFERENCES archive(project_id, hash);
+
+-- Report: event_id unique per project
+ALTER TABLE report DROP CONSTRAINT IF EXISTS report_event_id_key;
+ALTER TABLE report ADD CONSTRAINT report_project_event_id_key UNIQUE (project_id, event_id);
+
+-- Issue: owned by a project, fingerprint unique per project
+ALTER TABLE issue DROP CONSTRAINT IF EXISTS issue_fingerprint_hash_key;
+ALTER TABLE issue ADD COLUMN project_id INTEGER REFERENCES project(id);
+
+-- Each issue keeps the project of its first report...
+UPDATE issue i SET project_id = first_report.project_id
+FROM (
+    SELECT DISTINCT ON (issue_id) issue_id, project_id
+    FROM report
+    WHERE issue_id IS NOT NULL
+    ORDER BY issue_id, id
+) first_report
+WHERE first_report.issue_id = i.id;
+
+-- ...and reports of other projects move to a copy of the issue in their project
+DO $$
+DECLARE
+    pair RECORD;
+    copy_id INTEGER;
+BEGIN
+    FOR pair IN
+        SELECT DISTINCT r.issue_id, r.project_id
+        FROM report r
+        JOIN issue i ON i.id = r.issue_id
+        WHERE r.project_id <> i.project_id
+    LOOP
+        INSERT INTO issue (
+            project_id, fingerprint_hash, exception_type_id, title, first_seen, last_seen,
+            event_count, status, resolved_in_release, resolved_at, ignore_until,
+            ignore_until_event_count, regressed_at, grouping_strategy
+        )
+        SELECT pair.project_id, fingerprint_hash, exception_type_id, title, first_seen, last_seen,
+            event_count, status, resolved_in_release, resolved_at, ignore_until,
+            ignore_until_event_count, regressed_at, grouping_strategy
+        FROM issue WHERE id = pair.issue_id
+        RETURNING id INTO copy_id;
+
+        UPDATE report SET issue_id = copy_id
+        WHERE issue_id = pair.issue_id AND project_id = pair.project_id;
+    END LOOP;
+END $$;
+
+-- Split issues get counters from their own reports
+UPDATE issue i SET
+    event_count = stats.event_count,
+    first_seen = stats.first_seen,
+    last_seen = stats.last_seen
+FROM (
+    SELECT issue_id, COUNT(*) AS event_count, MIN(received_at) AS first_seen, MAX(received_at) AS last_seen
+    FROM report
+    WHERE issue_id IS NOT NULL
+    GROUP BY issue_id
+) stats
+WHERE stats.issue_id = i.id
+  AND i.fingerprint_hash IN (
+      SELECT fingerprint_hash FROM issue GROUP BY fingerprint_hash HAVING COUNT(*) > 1
+  );
+
+-- Issues without any report go to the oldest project. Their fingerprints were
+-- globally unique, so they cannot clash with an issue already there.
+DO $$
+DECLARE
+    orphans INTEGER;
+    default_project INTEGER;
+BEGIN
+    SELECT COUNT(*) INTO orphans FROM issue WHERE project_id IS NULL;
+    IF orphans = 0 THEN
+        RETURN;
+    END IF;
+
+    SELECT MIN(id) INTO default_project FROM project;
+    IF default_project IS NULL THEN
+        RAISE EXCEPTION '% issues have no report and there is no project to attribute them to; insert a project row and run the migrations again', orphans;
+    END IF;
+
+    UPDATE issue SET project_id = default_project WHERE project_id IS NULL;
+    RAISE NOTICE 'Attributed % issues without reports to project %', orphans, default_project;
+END $$;
+
+ALTER TABLE issue ALTER COLUMN project_id SET NOT NULL;
+ALTER TABLE issue ADD CONSTRAINT issue_project_fingerprint_key UNIQUE (project_id, fingerprint_hash);
+CREATE INDEX IF NOT EXISTS idx_issue_project ON issue(project_id);
@@ -0,0 +1,21 @@
+-- Every exception value of an event, in the order the SDK sent them
+-- (innermost cause first, outermost exception last).
+-- report.exception_type_id / exception_message_id / stacktrace_id keep
+-- pointing at the last value, the one used for the issue title.
+CREATE TABLE IF NOT EXISTS report_exception (
+    id SERIAL PRIMARY KEY,
+    report_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    exception_type_id INTEGER,
+    exception_message_id INTEGER,
+    mechanism_type TEXT,
+    handled BOOLEAN,
+    stacktrace_id INTEGER,
+    UNIQUE (report_id, position),
+    FOREIGN KEY (report_id) REFERENCES report(id) ON DELETE CASCADE,
+    FOREIGN KEY (exception_type_id) REFERENCES unwrap_exception_type(id),
+    FOREIGN KEY (exception_message_id) REFERENCES unwrap_exception_message(id),
+    FOREIGN KEY (stacktrace_id) REFERENCES unwrap_stacktrace(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_exception_type ON report_exception(exception_type_id);
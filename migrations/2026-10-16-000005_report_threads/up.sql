@@ -0,0 +1,15 @@
+-- Every thread of an event (threads interface), in the order the SDK sent them.
+-- thread_id is text because SDKs send numbers or strings.
+CREATE TABLE IF NOT EXISTS report_thread (
+    id SERIAL PRIMARY KEY,
+    report_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    thread_id TEXT,
+    name TEXT,
+    crashed BOOLEAN NOT NULL DEFAULT FALSE,
+    current BOOLEAN NOT NULL DEFAULT FALSE,
+    stacktrace_id INTEGER,
+    UNIQUE (report_id, position),
+    FOREIGN KEY (report_id) REFERENCES report(id) ON DELETE CASCADE,
+    FOREIGN KEY (stacktrace_id) REFERENCES unwrap_stacktrace(id)
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS report_tag;
+DROP TABLE IF EXISTS unwrap_tag_value;
+DROP TABLE IF EXISTS unwrap_tag_key;
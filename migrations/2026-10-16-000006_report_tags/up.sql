@@ -0,0 +1,23 @@
+-- Event tags, normalized like the other unwrap_* dimensions.
+-- A report has at most one value per tag key.
+CREATE TABLE IF NOT EXISTS unwrap_tag_key (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS unwrap_tag_value (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS report_tag (
+    report_id INTEGER NOT NULL,
+    tag_key_id INTEGER NOT NULL,
+    tag_value_id INTEGER NOT NULL,
+    PRIMARY KEY (report_id, tag_key_id),
+    FOREIGN KEY (report_id) REFERENCES report(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_key_id) REFERENCES unwrap_tag_key(id),
+    FOREIGN KEY (tag_value_id) REFERENCES unwrap_tag_value(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_tag_key_value ON report_tag(tag_key_id, tag_value_id);
@@ -0,0 +1,22 @@
+-- Breadcrumbs of an event, oldest first. url holds the navigation target
+-- (data.to) or HTTP request URL (data.url) for breadcrumb search; data is
+-- capped by the digest.
+CREATE TABLE IF NOT EXISTS report_breadcrumb (
+    id SERIAL PRIMARY KEY,
+    report_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    timestamp TIMESTAMP,
+    breadcrumb_type TEXT,
+    category TEXT,
+    level TEXT,
+    message TEXT,
+    url TEXT,
+    data JSONB,
+    UNIQUE (report_id, position),
+    FOREIGN KEY (report_id) REFERENCES report(id) ON DELETE CASCADE
+);
+
+-- Breadcrumb search matches url with ILIKE '%...%', which needs trigrams
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE INDEX IF NOT EXISTS idx_report_breadcrumb_url ON report_breadcrumb
+    USING GIN (url gin_trgm_ops) WHERE url IS NOT NULL;
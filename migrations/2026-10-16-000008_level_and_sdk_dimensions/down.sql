@@ -0,0 +1,6 @@
+ALTER TABLE report DROP COLUMN IF EXISTS sdk_version_id;
+ALTER TABLE report DROP COLUMN IF EXISTS sdk_name_id;
+ALTER TABLE report DROP COLUMN IF EXISTS level_id;
+DROP TABLE IF EXISTS unwrap_sdk_version;
+DROP TABLE IF EXISTS unwrap_sdk_name;
+DROP TABLE IF EXISTS unwrap_level;
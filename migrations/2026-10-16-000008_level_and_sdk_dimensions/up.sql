@@ -0,0 +1,19 @@
+-- Event level (fatal, error, warning, info, debug) and the SDK that sent the event
+CREATE TABLE IF NOT EXISTS unwrap_level (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS unwrap_sdk_name (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS unwrap_sdk_version (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+ALTER TABLE report ADD COLUMN level_id INTEGER REFERENCES unwrap_level(id);
+ALTER TABLE report ADD COLUMN sdk_name_id INTEGER REFERENCES unwrap_sdk_name(id);
+ALTER TABLE report ADD COLUMN sdk_version_id INTEGER REFERENCES unwrap_sdk_version(id);
@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS unwrap_session_release (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+INSERT INTO unwrap_session_release (value)
+SELECT DISTINCT CASE WHEN build = '' THEN version ELSE version || '+' || build END
+FROM release
+ON CONFLICT DO NOTHING;
+
+DROP INDEX IF EXISTS idx_session_release;
+ALTER TABLE session DROP CONSTRAINT IF EXISTS session_release_id_fkey;
+
+UPDATE session s SET release_id = usr.id
+FROM release rel, unwrap_session_release usr
+WHERE rel.id = s.release_id
+  AND usr.value = CASE WHEN rel.build = '' THEN rel.version ELSE rel.version || '+' || rel.build END;
+
+ALTER TABLE session ADD CONSTRAINT session_release_id_fkey
+    FOREIGN KEY (release_id) REFERENCES unwrap_session_release(id);
+
+DROP INDEX IF EXISTS idx_report_release;
+ALTER TABLE report DROP COLUMN IF EXISTS release_id;
+DROP TABLE IF EXISTS release;
@@ -0,0 +1,74 @@
+-- ============================================
+-- RELEASE ENTITY
+-- One row per (project, version, build), shared by reports and sessions.
+-- Replaces unwrap_session_release; the package name before '@' is dropped.
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS release (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    version TEXT NOT NULL,
+    -- Empty when the release has no '+build' suffix
+    build TEXT NOT NULL DEFAULT '',
+    first_seen TIMESTAMP NOT NULL,
+    last_seen TIMESTAMP NOT NULL,
+    CONSTRAINT release_project_version_build_key UNIQUE (project_id, version, build)
+);
+
+-- Backfill from reports (split into app version/build by the digest)
+INSERT INTO release (project_id, version, build, first_seen, last_seen)
+SELECT r.project_id, v.value, COALESCE(b.value, ''), MIN(r.received_at), MAX(r.received_at)
+FROM report r
+JOIN unwrap_app_version v ON v.id = r.app_version_id
+LEFT JOIN unwrap_app_build b ON b.id = r.app_build_id
+GROUP BY r.project_id, v.value, COALESCE(b.value, '')
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE report ADD COLUMN release_id INTEGER REFERENCES release(id);
+
+UPDATE report r SET release_id = rel.id
+FROM unwrap_app_version v, release rel
+WHERE v.id = r.app_version_id
+  AND rel.project_id = r.project_id
+  AND rel.version = v.value
+  AND rel.build = COALESCE((SELECT b.value FROM unwrap_app_build b WHERE b.id = r.app_build_id), '');
+
+CREATE INDEX IF NOT EXISTS idx_report_release ON report(release_id);
+
+-- Backfill from sessions, which stored the raw release string
+CREATE TEMPORARY TABLE session_release_split AS
+SELECT s.id AS session_id,
+       s.project_id,
+       split_part(vb.version_build, '+', 1) AS version,
+       CASE WHEN position('+' IN vb.version_build) > 0
+            THEN substring(vb.version_build FROM position('+' IN vb.version_build) + 1)
+            ELSE '' END AS build
+FROM session s
+JOIN unwrap_session_release usr ON usr.id = s.release_id
+CROSS JOIN LATERAL (
+    SELECT CASE WHEN position('@' IN usr.value) > 0
+                THEN substring(usr.value FROM position('@' IN usr.value) + 1)
+                ELSE usr.value END AS version_build
+) vb;
+
+INSERT INTO release (project_id, version, build, first_seen, last_seen)
+SELECT DISTINCT project_id, version, build, NOW(), NOW()
+FROM session_release_split
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE session DROP CONSTRAINT IF EXISTS session_release_id_fkey;
+
+UPDATE session s SET release_id = rel.id
+FROM session_release_split srs, release rel
+WHERE srs.session_id = s.id
+  AND rel.project_id = srs.project_id
+  AND rel.version = srs.version
+  AND rel.build = srs.build;
+
+ALTER TABLE session ADD CONSTRAINT session_release_id_fkey
+    FOREIGN KEY (release_id) REFERENCES release(id);
+
+CREATE INDEX IF NOT EXISTS idx_session_release ON session(release_id);
+
+DROP TABLE session_release_split;
+DROP TABLE IF EXISTS unwrap_session_release;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS bucket_release_health;
+ALTER TABLE session DROP COLUMN IF EXISTS distinct_id;
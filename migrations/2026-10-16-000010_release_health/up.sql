@@ -0,0 +1,33 @@
+-- ============================================
+-- RELEASE HEALTH
+-- Hourly session rollups per project, release and environment,
+-- recomputed by the release health job.
+-- ============================================
+
+-- Distinct id (`did`) of the session's user, for crash-free user rates
+ALTER TABLE session ADD COLUMN distinct_id TEXT;
+
+CREATE TABLE IF NOT EXISTS bucket_release_health (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    bucket_start TIMESTAMP NOT NULL,
+    release_id INTEGER REFERENCES release(id),
+    environment_id INTEGER REFERENCES unwrap_session_environment(id),
+    total_sessions INTEGER NOT NULL,
+    crashed INTEGER NOT NULL,
+    abnormal INTEGER NOT NULL,
+    -- Sessions with errors that neither crashed nor ended abnormally
+    errored INTEGER NOT NULL,
+    -- Distinct users (by distinct_id); sessions without one are not counted
+    total_users INTEGER NOT NULL,
+    crashed_users INTEGER NOT NULL,
+    crash_free_session_rate DOUBLE PRECISION NOT NULL,
+    -- NULL when no session of the bucket has a distinct_id
+    crash_free_user_rate DOUBLE PRECISION,
+    computed_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_bucket_release_health_project_start
+    ON bucket_release_health(project_id, bucket_start);
+CREATE INDEX IF NOT EXISTS idx_bucket_release_health_release
+    ON bucket_release_health(release_id);
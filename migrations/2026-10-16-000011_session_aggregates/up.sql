@@ -0,0 +1,22 @@
+-- ============================================
+-- SESSION AGGREGATES
+-- Pre-aggregated session counts from `sessions` envelope items,
+-- counted alongside individual sessions by the release health job.
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS session_aggregate (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    started_at TIMESTAMP NOT NULL,
+    release_id INTEGER REFERENCES release(id),
+    environment_id INTEGER REFERENCES unwrap_session_environment(id),
+    distinct_id TEXT,
+    exited INTEGER NOT NULL DEFAULT 0,
+    errored INTEGER NOT NULL DEFAULT 0,
+    abnormal INTEGER NOT NULL DEFAULT 0,
+    crashed INTEGER NOT NULL DEFAULT 0,
+    received_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_aggregate_project_started
+    ON session_aggregate(project_id, started_at);
@@ -0,0 +1,6 @@
+ALTER TABLE session ALTER COLUMN started_at TYPE TEXT
+    USING to_char(started_at, 'YYYY-MM-DD"T"HH24:MI:SS.US"Z"');
+ALTER TABLE session ALTER COLUMN timestamp TYPE TEXT
+    USING to_char(timestamp, 'YYYY-MM-DD"T"HH24:MI:SS.US"Z"');
+
+ALTER TABLE session DROP COLUMN IF EXISTS duration;
@@ -0,0 +1,27 @@
+-- ============================================
+-- SESSION TIMESTAMPS
+-- started_at and timestamp become real timestamps (UTC) so updates can be
+-- ordered, and the session duration sent by SDKs is stored.
+-- ============================================
+
+ALTER TABLE session ADD COLUMN IF NOT EXISTS duration DOUBLE PRECISION;
+
+ALTER TABLE session ALTER COLUMN started_at DROP NOT NULL;
+ALTER TABLE session ALTER COLUMN timestamp DROP NOT NULL;
+
+ALTER TABLE session ALTER COLUMN started_at TYPE TIMESTAMP USING (
+    CASE WHEN started_at ~ '^\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}'
+        THEN started_at::timestamptz AT TIME ZONE 'UTC' END
+);
+ALTER TABLE session ALTER COLUMN timestamp TYPE TIMESTAMP USING (
+    CASE WHEN timestamp ~ '^\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}'
+        THEN timestamp::timestamptz AT TIME ZONE 'UTC' END
+);
+
+-- Sessions without a parseable start take their last update time, or the
+-- migration time when neither could be parsed
+UPDATE session SET started_at = COALESCE(timestamp, NOW()) WHERE started_at IS NULL;
+UPDATE session SET timestamp = started_at WHERE timestamp IS NULL;
+
+ALTER TABLE session ALTER COLUMN started_at SET NOT NULL;
+ALTER TABLE session ALTER COLUMN timestamp SET NOT NULL;
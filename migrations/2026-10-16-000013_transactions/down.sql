@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS bucket_transaction_latency;
+DROP TABLE IF EXISTS transaction;
+DROP TABLE IF EXISTS unwrap_transaction_op;
+DROP TABLE IF EXISTS unwrap_transaction_name;
@@ -0,0 +1,60 @@
+-- ============================================
+-- TRANSACTIONS
+-- Performance events (`transaction` envelope items) and hourly
+-- duration percentiles per transaction name.
+-- ============================================
+
+-- Transaction name (route, screen or task) and trace operation (http.server, ui.load, ...)
+CREATE TABLE IF NOT EXISTS unwrap_transaction_name (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS unwrap_transaction_op (
+    id SERIAL PRIMARY KEY,
+    value TEXT UNIQUE NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transaction (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    event_id TEXT NOT NULL,
+    archive_hash TEXT NOT NULL,
+    name_id INTEGER NOT NULL REFERENCES unwrap_transaction_name(id),
+    op_id INTEGER REFERENCES unwrap_transaction_op(id),
+    -- Trace status (ok, internal_error, deadline_exceeded, ...)
+    status TEXT,
+    trace_id TEXT,
+    start_timestamp TIMESTAMP NOT NULL,
+    timestamp TIMESTAMP NOT NULL,
+    duration_ms DOUBLE PRECISION NOT NULL,
+    release_id INTEGER REFERENCES release(id),
+    environment_id INTEGER REFERENCES unwrap_environment(id),
+    received_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE(project_id, event_id),
+    FOREIGN KEY (project_id, archive_hash) REFERENCES archive(project_id, hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_project_start
+    ON transaction(project_id, start_timestamp);
+
+CREATE TABLE IF NOT EXISTS bucket_transaction_latency (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    bucket_start TIMESTAMP NOT NULL,
+    name_id INTEGER NOT NULL REFERENCES unwrap_transaction_name(id),
+    release_id INTEGER REFERENCES release(id),
+    environment_id INTEGER REFERENCES unwrap_environment(id),
+    transaction_count INTEGER NOT NULL,
+    avg_ms DOUBLE PRECISION NOT NULL,
+    p50_ms DOUBLE PRECISION NOT NULL,
+    p95_ms DOUBLE PRECISION NOT NULL,
+    p99_ms DOUBLE PRECISION NOT NULL,
+    max_ms DOUBLE PRECISION NOT NULL,
+    computed_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_bucket_transaction_latency_project_start
+    ON bucket_transaction_latency(project_id, bucket_start);
+CREATE INDEX IF NOT EXISTS idx_bucket_transaction_latency_name
+    ON bucket_transaction_latency(name_id);
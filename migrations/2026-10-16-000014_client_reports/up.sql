@@ -0,0 +1,19 @@
+-- ============================================
+-- CLIENT REPORTS
+-- Events discarded by SDKs before sending (`client_report` envelope
+-- items), counted per project, reason and category per minute.
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS bucket_client_discard (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    bucket_start TIMESTAMP NOT NULL,
+    -- queue_overflow, ratelimit_backoff, network_error, sample_rate, ...
+    reason TEXT NOT NULL,
+    -- error, transaction, session, attachment, ...
+    category TEXT NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 0,
+    UNIQUE(project_id, reason, category, bucket_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bucket_client_discard_start ON bucket_client_discard(bucket_start);
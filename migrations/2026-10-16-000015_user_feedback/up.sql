@@ -0,0 +1,23 @@
+-- ============================================
+-- USER FEEDBACK
+-- Comments users leave after a crash (`user_report` / `feedback`
+-- envelope items and the user-feedback endpoint), linked to the
+-- report and issue of their event once it is digested.
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS user_feedback (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    -- Event the feedback is about; NULL for feedback not tied to an event
+    event_id TEXT,
+    name TEXT,
+    email TEXT,
+    comments TEXT NOT NULL,
+    -- Set once the event is digested; cleared (not deleted) by ruminate
+    report_id INTEGER REFERENCES report(id) ON DELETE SET NULL,
+    issue_id INTEGER REFERENCES issue(id) ON DELETE SET NULL,
+    received_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE(project_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_feedback_issue ON user_feedback(issue_id);
@@ -0,0 +1,31 @@
+-- ============================================
+-- ATTACHMENTS
+-- Files sent as `attachment` envelope items (screenshots, view
+-- hierarchies, log files), extracted by the digest. Content is stored
+-- once per project like `archive`, keyed by the SHA-256 of the raw bytes.
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS attachment_blob (
+    hash TEXT NOT NULL,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    compressed_payload BYTEA NOT NULL,
+    original_size INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, hash)
+);
+
+-- One row per attachment item of an event, in envelope order
+CREATE TABLE IF NOT EXISTS attachment (
+    id SERIAL PRIMARY KEY,
+    report_id INTEGER NOT NULL REFERENCES report(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    filename TEXT NOT NULL,
+    content_type TEXT,
+    -- event.attachment, event.view_hierarchy, ...
+    attachment_type TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    UNIQUE (report_id, position),
+    FOREIGN KEY (project_id, hash) REFERENCES attachment_blob(project_id, hash)
+);
@@ -0,0 +1,20 @@
+-- Executables and libraries loaded in the crashed process, read from the
+-- minidump of a native crash in module list order. debug_id/debug_file are
+-- what symbol files are matched on; image_addr is hex like in Sentry's
+-- debug_meta, since addresses can exceed BIGINT.
+CREATE TABLE IF NOT EXISTS report_module (
+    id SERIAL PRIMARY KEY,
+    report_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    code_file TEXT NOT NULL,
+    code_id TEXT,
+    debug_file TEXT,
+    debug_id TEXT,
+    image_addr TEXT NOT NULL,
+    image_size BIGINT NOT NULL,
+    version TEXT,
+    UNIQUE (report_id, position),
+    FOREIGN KEY (report_id) REFERENCES report(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_module_debug_id ON report_module(debug_id) WHERE debug_id IS NOT NULL;
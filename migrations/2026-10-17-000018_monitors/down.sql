@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS monitor_checkin;
+DROP TABLE IF EXISTS monitor;
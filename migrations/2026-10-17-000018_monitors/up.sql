@@ -0,0 +1,56 @@
+-- ============================================
+-- CRON MONITORS
+-- Scheduled jobs reporting `check_in` envelope items. The schedule
+-- comes from the SDK's monitor_config; a background task records
+-- missed runs and in-progress check-ins that exceeded max_runtime.
+-- Check-ins are not archived, so ruminate keeps both tables.
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS monitor (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    slug TEXT NOT NULL,
+    -- crontab | interval
+    schedule_type TEXT NOT NULL,
+    -- Crontab expression, or the number of interval units
+    schedule TEXT NOT NULL,
+    -- Interval unit: minute, hour, day, week, month, year
+    schedule_unit TEXT,
+    -- Minutes a check-in may be late before the run counts as missed
+    checkin_margin INTEGER NOT NULL,
+    -- Minutes a run may stay in progress before it times out
+    max_runtime INTEGER NOT NULL,
+    -- IANA timezone crontab schedules are evaluated in
+    timezone TEXT NOT NULL,
+    -- active (no check-in yet), ok, error, missed, timeout
+    status TEXT NOT NULL DEFAULT 'active',
+    last_checkin_at TIMESTAMP,
+    next_checkin_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE(project_id, slug)
+);
+
+CREATE INDEX IF NOT EXISTS idx_monitor_next_checkin ON monitor(next_checkin_at);
+
+CREATE TABLE IF NOT EXISTS monitor_checkin (
+    id SERIAL PRIMARY KEY,
+    monitor_id INTEGER NOT NULL REFERENCES monitor(id) ON DELETE CASCADE,
+    -- Id sent by the SDK to close an in-progress check-in; generated for missed runs
+    check_in_id TEXT NOT NULL,
+    -- in_progress, ok, error, missed, timeout
+    status TEXT NOT NULL,
+    duration_ms DOUBLE PRECISION,
+    -- Kept as text: ruminate clears release and unwrap_environment
+    release TEXT,
+    environment TEXT,
+    -- Scheduled time of the run, for missed check-ins
+    expected_at TIMESTAMP,
+    received_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE(monitor_id, check_in_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_monitor_checkin_monitor_received
+    ON monitor_checkin(monitor_id, received_at);
+CREATE INDEX IF NOT EXISTS idx_monitor_checkin_in_progress
+    ON monitor_checkin(received_at) WHERE status = 'in_progress';
@@ -0,0 +1 @@
+ALTER TABLE archive DROP COLUMN IF EXISTS compression;
@@ -0,0 +1,3 @@
+-- Compression of compressed_payload, one of the Content-Encoding names
+-- (gzip, deflate, br, zstd). Ingest writes gzip; existing rows are gzip.
+ALTER TABLE archive ADD COLUMN compression TEXT NOT NULL DEFAULT 'gzip';
@@ -0,0 +1,12 @@
+-- Bodies of envelopes whose client reports were counted, so a retried
+-- envelope that is not archived does not count its discards twice.
+-- Envelopes with an event rely on the archive dedup instead.
+CREATE TABLE IF NOT EXISTS client_report_receipt (
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    -- SHA-256 of the compressed body, like archive.hash
+    hash TEXT NOT NULL,
+    received_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_report_receipt_received ON client_report_receipt(received_at);
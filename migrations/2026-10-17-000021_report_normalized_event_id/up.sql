@@ -0,0 +1,4 @@
+-- Feedback is linked to its report by the event id in the form
+-- normalize_event_id gives: lowercase hex without dashes
+CREATE INDEX IF NOT EXISTS idx_report_normalized_event_id
+    ON report(project_id, (replace(lower(event_id), '-', '')));
@@ -0,0 +1,13 @@
+-- Bodies of envelopes whose `sessions` items were stored, so a retried
+-- envelope does not add its buckets twice. Unlike client reports this
+-- covers envelopes with an event too, as aggregates are stored at ingest
+-- before the archive dedup runs.
+CREATE TABLE IF NOT EXISTS session_aggregate_receipt (
+    project_id INTEGER NOT NULL REFERENCES project(id),
+    -- SHA-256 of the compressed body, like archive.hash
+    hash TEXT NOT NULL,
+    received_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_aggregate_receipt_received ON session_aggregate_receipt(received_at);
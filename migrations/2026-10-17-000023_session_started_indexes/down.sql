@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_session_aggregate_started;
+DROP INDEX IF EXISTS idx_session_started;
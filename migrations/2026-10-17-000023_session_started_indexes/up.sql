@@ -0,0 +1,4 @@
+-- The release health rollup selects the sessions and aggregates of its
+-- lookback window across every project
+CREATE INDEX IF NOT EXISTS idx_session_started ON session(started_at);
+CREATE INDEX IF NOT EXISTS idx_session_aggregate_started ON session_aggregate(started_at);
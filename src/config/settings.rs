@@ -22,6 +22,18 @@ pub struct Settings {
     // Request payload limits
     pub max_compressed_payload_bytes: usize,
     pub max_uncompressed_payload_bytes: usize,
+    /// Per attachment item, not counted against the uncompressed payload limit
+    pub max_attachment_bytes: usize,
+    // REST API (disabled when no token is configured)
+    pub api_token: Option<String>,
+    // Release health rollup job
+    pub release_health_interval_secs: u64,
+    pub release_health_lookback_hours: i64,
+    // Transaction latency rollup job
+    pub transaction_rollup_interval_secs: u64,
+    pub transaction_rollup_lookback_hours: i64,
+    // Cron monitor job (missed and timed out check-ins)
+    pub monitor_check_interval_secs: u64,
 }
 
 impl Settings {
@@ -77,6 +89,36 @@ impl Settings {
             max_uncompressed_payload_bytes: Self::require_env_parse(
                 "MAX_UNCOMPRESSED_PAYLOAD_BYTES",
             ),
+            max_attachment_bytes: Self::optional_env_parse("MAX_ATTACHMENT_BYTES", 1024 * 1024),
+
+            // REST API
+            api_token: Self::optional_env("API_TOKEN"),
+
+            // Release health
+            release_health_interval_secs: Self::optional_env_parse(
+                "RELEASE_HEALTH_INTERVAL_SECS",
+                300,
+            ),
+            release_health_lookback_hours: Self::optional_env_parse(
+                "RELEASE_HEALTH_LOOKBACK_HOURS",
+                48,
+            ),
+
+            // Transaction latency
+            transaction_rollup_interval_secs: Self::optional_env_parse(
+                "TRANSACTION_ROLLUP_INTERVAL_SECS",
+                300,
+            ),
+            transaction_rollup_lookback_hours: Self::optional_env_parse(
+                "TRANSACTION_ROLLUP_LOOKBACK_HOURS",
+                6,
+            ),
+
+            // Cron monitors
+            monitor_check_interval_secs: Self::optional_env_parse(
+                "MONITOR_CHECK_INTERVAL_SECS",
+                30,
+            ),
         }
     }
 
@@ -84,6 +126,12 @@ impl Settings {
         format!("{}:{}", self.server_host, self.server_port)
     }
 
+    /// Largest decompressed payload accepted anywhere: the uncompressed
+    /// payload limit plus room for one attachment
+    pub fn max_decompressed_bytes(&self) -> usize {
+        self.max_uncompressed_payload_bytes + self.max_attachment_bytes
+    }
+
     /// Calculate worker budget (90% of interval to prevent overlap)
     pub fn worker_budget_secs(&self) -> u64 {
         (self.worker_interval_secs as f64 * 0.9) as u64
@@ -94,6 +142,24 @@ impl Settings {
         env::var(key).unwrap_or_else(|_| panic!("Missing required environment variable: {}", key))
     }
 
+    fn optional_env(key: &str) -> Option<String> {
+        env::var(key)
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn optional_env_parse<T>(key: &str, default: T) -> T
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        match Self::optional_env(key) {
+            Some(value) => Self::parse_value(&value, key),
+            None => default,
+        }
+    }
+
     fn require_env_parse<T>(key: &str) -> T
     where
         T: std::str::FromStr,
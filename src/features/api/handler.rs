@@ -0,0 +1,370 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, put},
+};
+use tracing::warn;
+
+use crate::features::ingest::map_domain_error_to_response;
+
+use super::use_case::{
+    EventSearchParams, GetEventAttachmentUseCase, GetEventUseCase, GetIssueTagsUseCase,
+    GetReleaseHealthUseCase, GetTransactionLatencyUseCase, IssueFeedbackParams, IssueListParams,
+    IssueStatusParams, IssueTagsParams, ListEventAttachmentsUseCase, ListEventBreadcrumbsUseCase,
+    ListIssueFeedbackUseCase, ListIssuesUseCase, ListMonitorCheckInsUseCase, ListMonitorsUseCase,
+    MonitorCheckInParams, ReleaseHealthParams, SearchEventsUseCase, TransactionLatencyParams,
+    UpdateIssueStatusUseCase,
+};
+
+#[derive(Clone)]
+pub struct ApiState {
+    pub list_issues_use_case: ListIssuesUseCase,
+    pub get_event_use_case: GetEventUseCase,
+    pub update_issue_status_use_case: UpdateIssueStatusUseCase,
+    pub get_issue_tags_use_case: GetIssueTagsUseCase,
+    pub list_issue_feedback_use_case: ListIssueFeedbackUseCase,
+    pub list_event_breadcrumbs_use_case: ListEventBreadcrumbsUseCase,
+    pub list_event_attachments_use_case: ListEventAttachmentsUseCase,
+    pub get_event_attachment_use_case: GetEventAttachmentUseCase,
+    pub search_events_use_case: SearchEventsUseCase,
+    pub get_release_health_use_case: GetReleaseHealthUseCase,
+    pub get_transaction_latency_use_case: GetTransactionLatencyUseCase,
+    pub list_monitors_use_case: ListMonitorsUseCase,
+    pub list_monitor_check_ins_use_case: ListMonitorCheckInsUseCase,
+    pub api_token: String,
+}
+
+/// Creates the REST API router (bearer-token protected, not rate limited)
+pub fn create_rest_api_router(state: ApiState) -> Router {
+    Router::new()
+        .route("/api/0/projects/{project_id}/issues/", get(list_issues))
+        .route("/api/0/projects/{project_id}/issues", get(list_issues))
+        .route(
+            "/api/0/projects/{project_id}/issues/{issue_id}/",
+            put(update_issue_status),
+        )
+        .route(
+            "/api/0/projects/{project_id}/issues/{issue_id}",
+            put(update_issue_status),
+        )
+        .route(
+            "/api/0/projects/{project_id}/issues/{issue_id}/tags/",
+            get(get_issue_tags),
+        )
+        .route(
+            "/api/0/projects/{project_id}/issues/{issue_id}/tags",
+            get(get_issue_tags),
+        )
+        .route(
+            "/api/0/projects/{project_id}/issues/{issue_id}/user-feedback/",
+            get(list_issue_feedback),
+        )
+        .route(
+            "/api/0/projects/{project_id}/issues/{issue_id}/user-feedback",
+            get(list_issue_feedback),
+        )
+        .route("/api/0/projects/{project_id}/events/", get(search_events))
+        .route("/api/0/projects/{project_id}/events", get(search_events))
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}/",
+            get(get_event),
+        )
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}",
+            get(get_event),
+        )
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}/breadcrumbs/",
+            get(list_event_breadcrumbs),
+        )
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}/breadcrumbs",
+            get(list_event_breadcrumbs),
+        )
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}/attachments/",
+            get(list_event_attachments),
+        )
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}/attachments",
+            get(list_event_attachments),
+        )
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}/attachments/{attachment_id}/",
+            get(get_event_attachment),
+        )
+        .route(
+            "/api/0/projects/{project_id}/events/{event_id}/attachments/{attachment_id}",
+            get(get_event_attachment),
+        )
+        .route(
+            "/api/0/projects/{project_id}/sessions/",
+            get(get_release_health),
+        )
+        .route(
+            "/api/0/projects/{project_id}/sessions",
+            get(get_release_health),
+        )
+        .route(
+            "/api/0/projects/{project_id}/transactions/",
+            get(get_transaction_latency),
+        )
+        .route(
+            "/api/0/projects/{project_id}/transactions",
+            get(get_transaction_latency),
+        )
+        .route("/api/0/projects/{project_id}/monitors/", get(list_monitors))
+        .route("/api/0/projects/{project_id}/monitors", get(list_monitors))
+        .route(
+            "/api/0/projects/{project_id}/monitors/{monitor_slug}/checkins/",
+            get(list_monitor_check_ins),
+        )
+        .route(
+            "/api/0/projects/{project_id}/monitors/{monitor_slug}/checkins",
+            get(list_monitor_check_ins),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_token,
+        ))
+        .with_state(state)
+}
+
+async fn require_api_token(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    let provided = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    match provided {
+        Some(token) if tokens_match(token, &state.api_token) => next.run(request).await,
+        Some(_) => {
+            warn!(path = %request.uri().path(), "Invalid API token");
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({"error": "Invalid API token"})),
+            )
+                .into_response()
+        }
+        None => (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Missing bearer token"})),
+        )
+            .into_response(),
+    }
+}
+
+/// Constant-time comparison so the token cannot be guessed byte by byte
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+async fn list_issues(
+    State(state): State<ApiState>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<IssueListParams>,
+) -> impl IntoResponse {
+    match state.list_issues_use_case.execute(project_id, &params) {
+        Ok(page) => (StatusCode::OK, Json(serde_json::json!(page))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn update_issue_status(
+    State(state): State<ApiState>,
+    Path((project_id, issue_id)): Path<(i32, i32)>,
+    Json(params): Json<IssueStatusParams>,
+) -> impl IntoResponse {
+    match state
+        .update_issue_status_use_case
+        .execute(project_id, issue_id, &params)
+    {
+        Ok(issue) => (StatusCode::OK, Json(serde_json::json!(issue))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn get_issue_tags(
+    State(state): State<ApiState>,
+    Path((project_id, issue_id)): Path<(i32, i32)>,
+    Query(params): Query<IssueTagsParams>,
+) -> impl IntoResponse {
+    match state
+        .get_issue_tags_use_case
+        .execute(project_id, issue_id, &params)
+    {
+        Ok(tags) => (StatusCode::OK, Json(serde_json::json!(tags))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn list_issue_feedback(
+    State(state): State<ApiState>,
+    Path((project_id, issue_id)): Path<(i32, i32)>,
+    Query(params): Query<IssueFeedbackParams>,
+) -> impl IntoResponse {
+    match state
+        .list_issue_feedback_use_case
+        .execute(project_id, issue_id, &params)
+    {
+        Ok(feedback) => (StatusCode::OK, Json(serde_json::json!(feedback))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn get_event(
+    State(state): State<ApiState>,
+    Path((project_id, event_id)): Path<(i32, String)>,
+) -> impl IntoResponse {
+    match state.get_event_use_case.execute(project_id, &event_id) {
+        Ok(event) => (StatusCode::OK, Json(event)),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn list_event_breadcrumbs(
+    State(state): State<ApiState>,
+    Path((project_id, event_id)): Path<(i32, String)>,
+) -> impl IntoResponse {
+    match state
+        .list_event_breadcrumbs_use_case
+        .execute(project_id, &event_id)
+    {
+        Ok(breadcrumbs) => (StatusCode::OK, Json(serde_json::json!(breadcrumbs))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn list_event_attachments(
+    State(state): State<ApiState>,
+    Path((project_id, event_id)): Path<(i32, String)>,
+) -> impl IntoResponse {
+    match state
+        .list_event_attachments_use_case
+        .execute(project_id, &event_id)
+    {
+        Ok(attachments) => (StatusCode::OK, Json(serde_json::json!(attachments))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn get_event_attachment(
+    State(state): State<ApiState>,
+    Path((project_id, event_id, attachment_id)): Path<(i32, String, i32)>,
+) -> Response {
+    match state
+        .get_event_attachment_use_case
+        .execute(project_id, &event_id, attachment_id)
+    {
+        Ok(content) => {
+            let content_type = content
+                .content_type
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+            let disposition = format!(
+                "attachment; filename=\"{}\"",
+                sanitize_filename(&content.filename)
+            );
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, content_type),
+                    (header::CONTENT_DISPOSITION, disposition),
+                ],
+                content.payload,
+            )
+                .into_response()
+        }
+        Err(e) => map_domain_error_to_response(&e).into_response(),
+    }
+}
+
+/// Keeps a client-supplied filename from breaking out of the quoted header value
+fn sanitize_filename(filename: &str) -> String {
+    filename
+        .chars()
+        .map(|c| {
+            if c == ' ' || (c.is_ascii_graphic() && !matches!(c, '"' | '\\')) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+async fn search_events(
+    State(state): State<ApiState>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<EventSearchParams>,
+) -> impl IntoResponse {
+    match state.search_events_use_case.execute(project_id, &params) {
+        Ok(events) => (StatusCode::OK, Json(serde_json::json!(events))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn get_release_health(
+    State(state): State<ApiState>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<ReleaseHealthParams>,
+) -> impl IntoResponse {
+    match state
+        .get_release_health_use_case
+        .execute(project_id, &params)
+    {
+        Ok(buckets) => (StatusCode::OK, Json(serde_json::json!(buckets))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn get_transaction_latency(
+    State(state): State<ApiState>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<TransactionLatencyParams>,
+) -> impl IntoResponse {
+    match state
+        .get_transaction_latency_use_case
+        .execute(project_id, &params)
+    {
+        Ok(buckets) => (StatusCode::OK, Json(serde_json::json!(buckets))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn list_monitors(
+    State(state): State<ApiState>,
+    Path(project_id): Path<i32>,
+) -> impl IntoResponse {
+    match state.list_monitors_use_case.execute(project_id) {
+        Ok(monitors) => (StatusCode::OK, Json(serde_json::json!(monitors))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+async fn list_monitor_check_ins(
+    State(state): State<ApiState>,
+    Path((project_id, monitor_slug)): Path<(i32, String)>,
+    Query(params): Query<MonitorCheckInParams>,
+) -> impl IntoResponse {
+    match state
+        .list_monitor_check_ins_use_case
+        .execute(project_id, &monitor_slug, &params)
+    {
+        Ok(check_ins) => (StatusCode::OK, Json(serde_json::json!(check_ins))),
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
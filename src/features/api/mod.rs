@@ -0,0 +1,17 @@
+mod handler;
+mod use_case;
+
+#[cfg(test)]
+mod tests;
+
+pub use handler::{ApiState, create_rest_api_router};
+pub use use_case::{
+    AttachmentContent, AttachmentEntry, BreadcrumbEntry, CheckInEntry, EventMatch,
+    EventSearchParams, GetEventAttachmentUseCase, GetEventUseCase, GetIssueTagsUseCase,
+    GetReleaseHealthUseCase, GetTransactionLatencyUseCase, IssueFeedbackParams, IssueListParams,
+    IssuePage, IssueStatusParams, IssueSummary, IssueTagsParams, ListEventAttachmentsUseCase,
+    ListEventBreadcrumbsUseCase, ListIssueFeedbackUseCase, ListIssuesUseCase,
+    ListMonitorCheckInsUseCase, ListMonitorsUseCase, MonitorCheckInParams, MonitorScheduleEntry,
+    MonitorSummary, ReleaseHealthParams, SearchEventsUseCase, TransactionLatencyParams,
+    UpdateIssueStatusUseCase, UserFeedbackEntry,
+};
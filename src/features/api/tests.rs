@@ -0,0 +1,1276 @@
+use axum::http::StatusCode;
+use sha2::{Digest, Sha256};
+
+use crate::features::digest::DigestReportUseCase;
+use crate::features::ingest::IngestReportUseCase;
+use crate::features::monitors::{CheckMonitorsUseCase, MonitorCheckSummary};
+use crate::features::performance::RollupTransactionLatencyUseCase;
+use crate::features::release_health::RollupReleaseHealthUseCase;
+use crate::features::test_support::{ingest_router, post_envelope, test_pool};
+use crate::shared::compression::Compressor;
+use crate::shared::domain::{DomainError, MonitorSchedule};
+use crate::shared::parser::{Envelope, SentryCheckIn};
+use crate::shared::persistence::db::models::{
+    NewMonitorCheckinModel, NewMonitorModel, NewSessionModel, NewUserFeedbackModel,
+};
+use crate::shared::persistence::{DbPool, Repositories};
+
+use super::{
+    EventSearchParams, GetEventAttachmentUseCase, GetEventUseCase, GetIssueTagsUseCase,
+    GetReleaseHealthUseCase, GetTransactionLatencyUseCase, IssueFeedbackParams, IssueListParams,
+    IssueStatusParams, IssueTagsParams, ListEventAttachmentsUseCase, ListEventBreadcrumbsUseCase,
+    ListIssueFeedbackUseCase, ListIssuesUseCase, ListMonitorCheckInsUseCase, ListMonitorsUseCase,
+    MonitorCheckInParams, ReleaseHealthParams, SearchEventsUseCase, TransactionLatencyParams,
+    UpdateIssueStatusUseCase,
+};
+
+fn setup_test_db() -> (Repositories, DbPool, i32) {
+    let pool = test_pool();
+    let repos = Repositories::new(pool.clone());
+    let project_id = repos.project.create(None, None).unwrap();
+    (repos, pool, project_id)
+}
+
+fn compress_and_hash(payload: &[u8]) -> (String, Vec<u8>) {
+    let compressor = Compressor::new();
+    let compressed = compressor.compress(payload).unwrap();
+    let mut hasher = Sha256::new();
+    hasher.update(&compressed);
+    let hash = hex::encode(hasher.finalize());
+    (hash, compressed)
+}
+
+fn crash_event(event_id: &str, environment: &str, platform: &str, lineno: i32) -> Vec<u8> {
+    serde_json::json!({
+        "event_id": event_id,
+        "timestamp": "2026-01-22T10:00:00Z",
+        "platform": platform,
+        "release": "my-app@1.2.3+45",
+        "environment": environment,
+        "exception": {
+            "values": [{
+                "type": "ValueError",
+                "value": "bad input",
+                "stacktrace": {
+                    "frames": [{"filename": "main.py", "function": "run", "lineno": lineno, "in_app": true}]
+                }
+            }]
+        }
+    })
+    .to_string()
+    .into_bytes()
+}
+
+fn ingest_and_digest(repos: &Repositories, pool: &DbPool, project_id: i32, payloads: &[Vec<u8>]) {
+    let ingest_use_case = IngestReportUseCase::new(
+        repos.archive.clone(),
+        repos.queue.clone(),
+        repos.project.clone(),
+    );
+    let digest_use_case = DigestReportUseCase::new(repos.clone(), pool.clone(), Compressor::new());
+
+    let mut conn = pool.get().unwrap();
+    for payload in payloads {
+        let (hash, compressed) = compress_and_hash(payload);
+        ingest_use_case
+            .execute(&mut conn, project_id, hash, compressed, None)
+            .unwrap();
+    }
+    digest_use_case.process_batch(100).unwrap();
+}
+
+#[test]
+fn test_list_issues_filters_by_environment_and_platform() {
+    let (repos, pool, project_id) = setup_test_db();
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[
+            crash_event("e1", "production", "python", 10),
+            crash_event("e2", "staging", "python", 20),
+            crash_event("e3", "production", "rust", 30),
+        ],
+    );
+    let use_case = ListIssuesUseCase::new(repos, pool);
+
+    let all = use_case
+        .execute(project_id, &IssueListParams::default())
+        .unwrap();
+    assert_eq!(all.data.len(), 3);
+    assert_eq!(all.data[0].exception_type.as_deref(), Some("ValueError"));
+
+    let production = IssueListParams {
+        environment: Some("production".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        use_case
+            .execute(project_id, &production)
+            .unwrap()
+            .data
+            .len(),
+        2
+    );
+
+    let production_rust = IssueListParams {
+        environment: Some("production".to_string()),
+        platform: Some("rust".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        use_case
+            .execute(project_id, &production_rust)
+            .unwrap()
+            .data
+            .len(),
+        1
+    );
+
+    let unknown = IssueListParams {
+        environment: Some("nowhere".to_string()),
+        ..Default::default()
+    };
+    assert!(
+        use_case
+            .execute(project_id, &unknown)
+            .unwrap()
+            .data
+            .is_empty()
+    );
+
+    let release = IssueListParams {
+        release: Some("my-app@1.2.3+45".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        use_case.execute(project_id, &release).unwrap().data.len(),
+        3
+    );
+
+    let outside_window = IssueListParams {
+        start: Some("2026-02-01T00:00:00Z".to_string()),
+        ..Default::default()
+    };
+    assert!(
+        use_case
+            .execute(project_id, &outside_window)
+            .unwrap()
+            .data
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_list_issues_cursor_pagination_is_stable() {
+    let (repos, pool, project_id) = setup_test_db();
+    let payloads: Vec<Vec<u8>> = (0..5)
+        .map(|i| crash_event(&format!("e{}", i), "production", "python", i))
+        .collect();
+    ingest_and_digest(&repos, &pool, project_id, &payloads);
+    let use_case = ListIssuesUseCase::new(repos, pool);
+
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    loop {
+        let params = IssueListParams {
+            sort: Some("event_count".to_string()),
+            limit: Some(2),
+            cursor: cursor.clone(),
+            ..Default::default()
+        };
+        let page = use_case.execute(project_id, &params).unwrap();
+        seen.extend(page.data.iter().map(|i| i.id));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let mut unique = seen.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(seen.len(), 5);
+    assert_eq!(unique.len(), 5);
+
+    // A cursor only continues the sort it came from
+    let first = IssueListParams {
+        sort: Some("event_count".to_string()),
+        limit: Some(2),
+        ..Default::default()
+    };
+    let other_sort = IssueListParams {
+        sort: Some("last_seen".to_string()),
+        cursor: use_case.execute(project_id, &first).unwrap().next_cursor,
+        ..Default::default()
+    };
+    assert!(matches!(
+        use_case.execute(project_id, &other_sort),
+        Err(DomainError::InvalidRequest(_))
+    ));
+}
+
+#[test]
+fn test_list_issues_rejects_invalid_params() {
+    let (repos, pool, project_id) = setup_test_db();
+    let use_case = ListIssuesUseCase::new(repos, pool);
+
+    let bad_sort = IssueListParams {
+        sort: Some("title".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        use_case.execute(project_id, &bad_sort),
+        Err(DomainError::InvalidRequest(_))
+    ));
+
+    let bad_cursor = IssueListParams {
+        cursor: Some("not-a-cursor".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        use_case.execute(project_id, &bad_cursor),
+        Err(DomainError::InvalidRequest(_))
+    ));
+
+    assert!(matches!(
+        use_case.execute(project_id + 1, &IssueListParams::default()),
+        Err(DomainError::ProjectNotFound(_))
+    ));
+}
+
+#[test]
+fn test_get_event_from_store_and_envelope() {
+    let (repos, pool, project_id) = setup_test_db();
+    let store_event = crash_event("store-event", "production", "python", 10);
+    let envelope_event = crash_event("envelope-event", "staging", "rust", 20);
+    let batched_event = crash_event("batched-event", "development", "go", 30);
+    let mut envelope = br#"{"event_id":"envelope-event"}"#.to_vec();
+    envelope.extend_from_slice(b"\n{\"type\":\"event\"}\n");
+    envelope.extend_from_slice(&envelope_event);
+    // The digest skips a malformed item, and so does the event lookup
+    envelope.extend_from_slice(b"\n{\"type\":\"event\"}\n{\"event_id\":");
+    envelope.extend_from_slice(b"\n{\"type\":\"event\"}\n");
+    envelope.extend_from_slice(&batched_event);
+    // The digest stores the outermost exception, the last of the chain
+    let chained_event = serde_json::json!({
+        "event_id": "0d4f1c5e8a9b4c7d9e2f3a4b5c6d7e8f",
+        "exception": {"values": [
+            {"type": "KeyError"},
+            {"type": "ValueError", "value": "bad input"}
+        ]}
+    })
+    .to_string()
+    .into_bytes();
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[store_event, envelope, chained_event],
+    );
+    let use_case = GetEventUseCase::new(repos, pool, Compressor::new());
+
+    let event = use_case.execute(project_id, "store-event").unwrap();
+    assert_eq!(event["event_id"], "store-event");
+    assert_eq!(event["exception"]["values"][0]["type"], "ValueError");
+    assert_eq!(event["project_id"], project_id);
+    assert!(event["issue_id"].is_i64());
+    // Values the digest derived fill the fields the SDK left out
+    assert_eq!(event["environment"], "production");
+    assert_eq!(event["level"], "error");
+    assert_eq!(event["contexts"]["app"]["app_version"], "1.2.3");
+    assert_eq!(event["exception"]["values"][0]["value"], "bad input");
+    assert!(event.get("resolved").is_none());
+    assert!(event.get("user").is_none());
+
+    let event = use_case.execute(project_id, "envelope-event").unwrap();
+    assert_eq!(event["platform"], "rust");
+    assert_eq!(event["environment"], "staging");
+    // A batched envelope serves each of its events
+    let event = use_case.execute(project_id, "batched-event").unwrap();
+    assert_eq!(event["event_id"], "batched-event");
+    assert_eq!(event["platform"], "go");
+
+    // Found by its id in any form, without moving the outer message inward
+    let event = use_case
+        .execute(project_id, "0D4F1C5E-8A9B-4C7D-9E2F-3A4B5C6D7E8F")
+        .unwrap();
+    assert_eq!(event["event_id"], "0d4f1c5e8a9b4c7d9e2f3a4b5c6d7e8f");
+    let values = event["exception"]["values"].as_array().unwrap();
+    assert_eq!(values.len(), 2);
+    assert!(values[0].get("value").is_none());
+    assert_eq!(values[1]["value"], "bad input");
+
+    assert!(matches!(
+        use_case.execute(project_id, "missing"),
+        Err(DomainError::NotFound(_))
+    ));
+    assert!(matches!(
+        use_case.execute(project_id + 1, "store-event"),
+        Err(DomainError::ProjectNotFound(_))
+    ));
+}
+
+#[test]
+fn test_update_issue_status_and_filter_by_status() {
+    let (repos, pool, project_id) = setup_test_db();
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[
+            crash_event("s1", "production", "python", 10),
+            crash_event("s2", "production", "python", 20),
+        ],
+    );
+    let list = ListIssuesUseCase::new(repos.clone(), pool.clone());
+    let update = UpdateIssueStatusUseCase::new(repos, pool);
+    let issue_id = list
+        .execute(project_id, &IssueListParams::default())
+        .unwrap()
+        .data[0]
+        .id;
+
+    let resolved = update
+        .execute(
+            project_id,
+            issue_id,
+            &IssueStatusParams {
+                status: "resolved".to_string(),
+                resolved_in_release: Some("1.2.3".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(resolved.status, "resolved");
+    assert_eq!(resolved.resolved_in_release.as_deref(), Some("1.2.3"));
+    assert!(resolved.resolved_at.is_some());
+
+    let unresolved = IssueListParams {
+        status: Some("unresolved".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list.execute(project_id, &unresolved).unwrap().data.len(), 1);
+
+    let ignored = update
+        .execute(
+            project_id,
+            issue_id,
+            &IssueStatusParams {
+                status: "ignored".to_string(),
+                ignore_until: Some("2030-01-01T00:00:00Z".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(ignored.status, "ignored");
+    assert!(ignored.resolved_in_release.is_none());
+    assert!(ignored.ignore_until.is_some());
+
+    let invalid = [
+        IssueStatusParams {
+            status: "closed".to_string(),
+            ..Default::default()
+        },
+        IssueStatusParams {
+            status: "unresolved".to_string(),
+            resolved_in_release: Some("1.2.3".to_string()),
+            ..Default::default()
+        },
+        IssueStatusParams {
+            status: "ignored".to_string(),
+            ignore_count: Some(0),
+            ..Default::default()
+        },
+    ];
+    for params in &invalid {
+        assert!(matches!(
+            update.execute(project_id, issue_id, params),
+            Err(DomainError::InvalidRequest(_))
+        ));
+    }
+
+    let unresolve = IssueStatusParams {
+        status: "unresolved".to_string(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        update.execute(project_id, issue_id + 1000, &unresolve),
+        Err(DomainError::NotFound(_))
+    ));
+}
+
+fn tagged_event(event_id: &str, lineno: i32, tags: serde_json::Value) -> Vec<u8> {
+    let mut event: serde_json::Value =
+        serde_json::from_slice(&crash_event(event_id, "production", "python", lineno)).unwrap();
+    event["tags"] = tags;
+    event.to_string().into_bytes()
+}
+
+#[test]
+fn test_issue_tag_distribution_and_tag_filter() {
+    let (repos, pool, project_id) = setup_test_db();
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[
+            tagged_event(
+                "t1",
+                10,
+                serde_json::json!({"flavor": "paid", "tenant": "acme"}),
+            ),
+            tagged_event(
+                "t2",
+                10,
+                serde_json::json!({"flavor": "paid", "tenant": "globex"}),
+            ),
+            tagged_event("t3", 10, serde_json::json!([["flavor", "free"]])),
+            tagged_event("t4", 20, serde_json::json!({"flavor": "free"})),
+        ],
+    );
+    let list = ListIssuesUseCase::new(repos.clone(), pool.clone());
+    let tags = GetIssueTagsUseCase::new(repos, pool);
+
+    let paid = IssueListParams {
+        tag: Some("flavor:paid".to_string()),
+        ..Default::default()
+    };
+    let paid_issues = list.execute(project_id, &paid).unwrap().data;
+    assert_eq!(paid_issues.len(), 1);
+    let issue_id = paid_issues[0].id;
+
+    let free = IssueListParams {
+        tag: Some("flavor:free".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list.execute(project_id, &free).unwrap().data.len(), 2);
+
+    let distributions = tags
+        .execute(project_id, issue_id, &IssueTagsParams::default())
+        .unwrap();
+    assert_eq!(distributions.len(), 2);
+    assert_eq!(distributions[0].key, "flavor");
+    assert_eq!(distributions[0].total, 3);
+    assert_eq!(distributions[0].top_values[0].value, "paid");
+    assert_eq!(distributions[0].top_values[0].count, 2);
+    assert_eq!(distributions[0].top_values[1].value, "free");
+    assert_eq!(distributions[1].key, "tenant");
+    assert_eq!(distributions[1].total, 2);
+
+    let top_one = IssueTagsParams { limit: Some(1) };
+    let distributions = tags.execute(project_id, issue_id, &top_one).unwrap();
+    assert!(distributions.iter().all(|d| d.top_values.len() == 1));
+
+    let bad_tag = IssueListParams {
+        tag: Some("flavor".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        list.execute(project_id, &bad_tag),
+        Err(DomainError::InvalidRequest(_))
+    ));
+    assert!(matches!(
+        tags.execute(project_id, issue_id + 1000, &IssueTagsParams::default()),
+        Err(DomainError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_list_issues_filters_by_level_and_sdk() {
+    let (repos, pool, project_id) = setup_test_db();
+    let with_level_and_sdk = |event_id: &str, lineno: i32, level: &str, sdk_version: &str| {
+        let mut event: serde_json::Value =
+            serde_json::from_slice(&crash_event(event_id, "production", "python", lineno)).unwrap();
+        event["level"] = serde_json::json!(level);
+        event["sdk"] = serde_json::json!({"name": "sentry.python", "version": sdk_version});
+        event.to_string().into_bytes()
+    };
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[
+            with_level_and_sdk("l1", 10, "fatal", "2.0.0"),
+            with_level_and_sdk("l2", 20, "warning", "1.9.0"),
+            with_level_and_sdk("l3", 30, "error", "2.0.0"),
+        ],
+    );
+    let list = ListIssuesUseCase::new(repos.clone(), pool.clone());
+
+    let fatal = IssueListParams {
+        level: Some("fatal".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list.execute(project_id, &fatal).unwrap().data.len(), 1);
+
+    let upgraded = IssueListParams {
+        sdk_name: Some("sentry.python".to_string()),
+        sdk_version: Some("2.0.0".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(list.execute(project_id, &upgraded).unwrap().data.len(), 2);
+
+    let unknown = IssueListParams {
+        level: Some("debug".to_string()),
+        ..Default::default()
+    };
+    assert!(list.execute(project_id, &unknown).unwrap().data.is_empty());
+
+    let event = GetEventUseCase::new(repos, pool, Compressor::new())
+        .execute(project_id, "l2")
+        .unwrap();
+    assert_eq!(event["level"], "warning");
+    assert_eq!(event["sdk"]["name"], "sentry.python");
+    assert_eq!(event["sdk"]["version"], "1.9.0");
+}
+
+fn event_with_breadcrumbs(event_id: &str, breadcrumbs: serde_json::Value) -> Vec<u8> {
+    let mut event: serde_json::Value =
+        serde_json::from_slice(&crash_event(event_id, "production", "python", 10)).unwrap();
+    event["breadcrumbs"] = breadcrumbs;
+    event.to_string().into_bytes()
+}
+
+#[test]
+fn test_event_breadcrumbs_and_breadcrumb_search() {
+    let (repos, pool, project_id) = setup_test_db();
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[
+            event_with_breadcrumbs(
+                "b1",
+                serde_json::json!({"values": [
+                    {"timestamp": 1769076000.5, "category": "navigation", "data": {"from": "/cart", "to": "/Checkout/Payment"}},
+                    {"timestamp": "2026-01-22T10:00:01Z", "type": "http", "category": "http", "level": "error",
+                     "data": {"url": "https://api.example.com/orders?id=1", "method": "POST", "status_code": 500}},
+                    {"category": "ui.click", "message": "button#pay"}
+                ]}),
+            ),
+            event_with_breadcrumbs(
+                "b2",
+                serde_json::json!([
+                    {"category": "navigation", "data": {"to": "/home"}}
+                ]),
+            ),
+        ],
+    );
+    let list = ListEventBreadcrumbsUseCase::new(repos.clone(), pool.clone());
+    let search = SearchEventsUseCase::new(repos, pool);
+
+    let breadcrumbs = list.execute(project_id, "b1").unwrap();
+    assert_eq!(breadcrumbs.len(), 3);
+    assert_eq!(breadcrumbs[0].url.as_deref(), Some("/Checkout/Payment"));
+    assert_eq!(
+        breadcrumbs[0].timestamp.unwrap().to_rfc3339(),
+        "2026-01-22T10:00:00.500+00:00"
+    );
+    assert_eq!(breadcrumbs[1].level.as_deref(), Some("error"));
+    assert_eq!(breadcrumbs[1].data.as_ref().unwrap()["status_code"], 500);
+    assert_eq!(breadcrumbs[2].message.as_deref(), Some("button#pay"));
+    assert!(breadcrumbs[2].url.is_none());
+
+    let by_route = EventSearchParams {
+        breadcrumb: Some("/checkout".to_string()),
+        ..Default::default()
+    };
+    let matches = search.execute(project_id, &by_route).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].event_id, "b1");
+    assert_eq!(matches[0].breadcrumbs.len(), 1);
+
+    let by_url = EventSearchParams {
+        breadcrumb: Some("api.example.com/orders".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(search.execute(project_id, &by_url).unwrap().len(), 1);
+
+    let wildcard = EventSearchParams {
+        breadcrumb: Some("%".to_string()),
+        ..Default::default()
+    };
+    assert!(search.execute(project_id, &wildcard).unwrap().is_empty());
+
+    assert!(matches!(
+        search.execute(project_id, &EventSearchParams::default()),
+        Err(DomainError::InvalidRequest(_))
+    ));
+    assert!(matches!(
+        list.execute(project_id, "missing"),
+        Err(DomainError::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_release_health_rollup() {
+    let (repos, pool, project_id) = setup_test_db();
+    let mut conn = pool.get().unwrap();
+    let now = chrono::Utc::now();
+    let recent = (now - chrono::Duration::hours(1)).naive_utc();
+    let old = (now - chrono::Duration::days(5)).naive_utc();
+    let production = repos
+        .session_environment
+        .get_or_create(&mut conn, "production")
+        .unwrap();
+    let sessions = [
+        ("s1", "ok", 0, Some("u1"), "my-app@1.0.0", &recent),
+        ("s2", "crashed", 1, Some("u2"), "my-app@1.0.0", &recent),
+        ("s3", "exited", 2, Some("u1"), "my-app@1.0.0", &recent),
+        ("s4", "abnormal", 0, None, "my-app@1.0.0", &recent),
+        ("s5", "ok", 0, Some("u3"), "my-app@2.0.0", &recent),
+        ("s6", "crashed", 0, Some("u4"), "my-app@2.0.0", &old),
+    ];
+    for (sid, status, errors, did, release, started) in sessions {
+        let new_session = NewSessionModel {
+            project_id,
+            sid: sid.to_string(),
+            init: 1,
+            started_at: *started,
+            timestamp: *started,
+            errors,
+            status_id: repos
+                .session_status
+                .get_or_create(&mut conn, status)
+                .unwrap(),
+            release_id: repos.release.touch(&mut conn, project_id, release).unwrap(),
+            environment_id: Some(production),
+            distinct_id: did.map(str::to_string),
+            duration: None,
+        };
+        repos.session.upsert(&mut conn, new_session).unwrap();
+    }
+
+    let rollup = RollupReleaseHealthUseCase::new(repos.release_health.clone(), 48);
+    assert_eq!(rollup.execute(now.naive_utc()).unwrap(), 2);
+    // Recomputing replaces the buckets instead of adding to them
+    assert_eq!(rollup.execute(now.naive_utc()).unwrap(), 2);
+
+    let health = GetReleaseHealthUseCase::new(repos, pool.clone());
+    let buckets = health
+        .execute(project_id, &ReleaseHealthParams::default())
+        .unwrap();
+    assert_eq!(buckets.len(), 2);
+
+    let v1 = ReleaseHealthParams {
+        release: Some("1.0.0".to_string()),
+        environment: Some("production".to_string()),
+        ..Default::default()
+    };
+    let buckets = health.execute(project_id, &v1).unwrap();
+    assert_eq!(buckets.len(), 1);
+    let bucket = &buckets[0];
+    assert_eq!(bucket.release.as_deref(), Some("1.0.0"));
+    assert_eq!(bucket.environment.as_deref(), Some("production"));
+    assert_eq!(bucket.total_sessions, 4);
+    assert_eq!(bucket.crashed, 1);
+    assert_eq!(bucket.abnormal, 1);
+    assert_eq!(bucket.errored, 1);
+    assert_eq!(bucket.total_users, 2);
+    assert_eq!(bucket.crashed_users, 1);
+    assert!((bucket.crash_free_session_rate - 0.75).abs() < 1e-9);
+    assert_eq!(bucket.crash_free_user_rate, Some(0.5));
+
+    let unknown = ReleaseHealthParams {
+        release: Some("9.9.9".to_string()),
+        ..Default::default()
+    };
+    assert!(health.execute(project_id, &unknown).unwrap().is_empty());
+    assert!(matches!(
+        health.execute(project_id + 1, &ReleaseHealthParams::default()),
+        Err(DomainError::ProjectNotFound(_))
+    ));
+}
+
+#[test]
+fn test_release_health_includes_session_aggregates() {
+    let (repos, pool, project_id) = setup_test_db();
+    let router = ingest_router(&repos, &pool);
+    let now = chrono::Utc::now();
+    let started = now - chrono::Duration::hours(1);
+    let attrs = serde_json::json!({"release": "my-app@1.0.0", "environment": "production"});
+
+    // Server-mode SDKs send aggregates next to the sessions of single users
+    let mut envelope = b"{}\n".to_vec();
+    for (sid, did) in [
+        ("7a1c3b0e-5f2d-4e8a-9b6c-1d2e3f4a5b6c", "u1"),
+        ("8b2d4c1f-6a3e-4f9b-8c7d-2e3f4a5b6c7d", "u2"),
+    ] {
+        envelope.extend_from_slice(b"{\"type\":\"session\"}\n");
+        envelope.extend_from_slice(
+            serde_json::json!({
+                "sid": sid,
+                "did": did,
+                "init": true,
+                "started": started.to_rfc3339(),
+                "timestamp": started.to_rfc3339(),
+                "status": "exited",
+                "attrs": attrs
+            })
+            .to_string()
+            .as_bytes(),
+        );
+        envelope.push(b'\n');
+    }
+    envelope.extend_from_slice(b"{\"type\":\"sessions\"}\n");
+    envelope.extend_from_slice(
+        serde_json::json!({
+            "aggregates": [
+                {"started": started.to_rfc3339(), "did": "u1", "exited": 3, "crashed": 1},
+                {"started": started.to_rfc3339(), "exited": 5, "errored": 2, "abnormal": 1}
+            ],
+            "attrs": attrs
+        })
+        .to_string()
+        .as_bytes(),
+    );
+
+    let (status, body) = post_envelope(&router, project_id, &envelope);
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["sessions"], 2);
+    assert_eq!(body["aggregates"], 2);
+
+    // A retry of the same envelope does not add the buckets again
+    let (status, body) = post_envelope(&router, project_id, &envelope);
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["aggregates"], 0);
+
+    let rollup = RollupReleaseHealthUseCase::new(repos.release_health.clone(), 48);
+    assert_eq!(rollup.execute(now.naive_utc()).unwrap(), 1);
+
+    let health = GetReleaseHealthUseCase::new(repos, pool.clone());
+    let buckets = health
+        .execute(project_id, &ReleaseHealthParams::default())
+        .unwrap();
+    assert_eq!(buckets.len(), 1);
+    let bucket = &buckets[0];
+    assert_eq!(bucket.release.as_deref(), Some("1.0.0"));
+    assert_eq!(bucket.total_sessions, 14);
+    assert_eq!(bucket.crashed, 1);
+    assert_eq!(bucket.abnormal, 1);
+    assert_eq!(bucket.errored, 2);
+    assert_eq!(bucket.total_users, 2);
+    assert_eq!(bucket.crashed_users, 1);
+    assert!((bucket.crash_free_session_rate - 13.0 / 14.0).abs() < 1e-9);
+    assert_eq!(bucket.crash_free_user_rate, Some(0.5));
+}
+
+fn transaction_envelope(
+    event_id: &str,
+    name: &str,
+    start: chrono::DateTime<chrono::Utc>,
+    duration_ms: i64,
+) -> Vec<u8> {
+    let end = start + chrono::Duration::milliseconds(duration_ms);
+    let mut envelope = format!(r#"{{"event_id":"{}"}}"#, event_id).into_bytes();
+    envelope.extend_from_slice(b"\n{\"type\":\"transaction\"}\n");
+    envelope.extend_from_slice(
+        serde_json::json!({
+            "event_id": event_id,
+            "type": "transaction",
+            "transaction": name,
+            "start_timestamp": start.to_rfc3339(),
+            "timestamp": end.to_rfc3339(),
+            "release": "my-app@1.0.0",
+            "environment": "production",
+            "contexts": {"trace": {"op": "http.server", "status": "ok"}}
+        })
+        .to_string()
+        .as_bytes(),
+    );
+    envelope
+}
+
+#[test]
+fn test_transaction_latency_rollup() {
+    let (repos, pool, project_id) = setup_test_db();
+    let now = chrono::Utc::now();
+    let start = now - chrono::Duration::hours(1);
+    let mut payloads: Vec<Vec<u8>> = (1..=10)
+        .map(|i| transaction_envelope(&format!("checkout-{}", i), "/checkout", start, i * 100))
+        .collect();
+    payloads.push(transaction_envelope("home-1", "/home", start, 50));
+    ingest_and_digest(&repos, &pool, project_id, &payloads);
+
+    let rollup = RollupTransactionLatencyUseCase::new(repos.transaction_latency.clone(), 6);
+    assert_eq!(rollup.execute(now.naive_utc()).unwrap(), 2);
+
+    let latency = GetTransactionLatencyUseCase::new(repos, pool.clone());
+    let buckets = latency
+        .execute(project_id, &TransactionLatencyParams::default())
+        .unwrap();
+    assert_eq!(buckets.len(), 2);
+    // Slowest transaction first within the hour
+    let checkout = &buckets[0];
+    assert_eq!(checkout.name, "/checkout");
+    assert_eq!(checkout.release.as_deref(), Some("1.0.0"));
+    assert_eq!(checkout.environment.as_deref(), Some("production"));
+    assert_eq!(checkout.count, 10);
+    assert!((checkout.avg_ms - 550.0).abs() < 1e-6);
+    assert!((checkout.p50_ms - 550.0).abs() < 1e-6);
+    assert!((checkout.p95_ms - 955.0).abs() < 1e-6);
+    assert!((checkout.p99_ms - 991.0).abs() < 1e-6);
+    assert!((checkout.max_ms - 1000.0).abs() < 1e-6);
+
+    let home = TransactionLatencyParams {
+        name: Some("/home".to_string()),
+        ..Default::default()
+    };
+    let buckets = latency.execute(project_id, &home).unwrap();
+    assert_eq!(buckets.len(), 1);
+    assert!((buckets[0].p99_ms - 50.0).abs() < 1e-6);
+
+    let unknown = TransactionLatencyParams {
+        release: Some("9.9.9".to_string()),
+        ..Default::default()
+    };
+    assert!(latency.execute(project_id, &unknown).unwrap().is_empty());
+    assert!(matches!(
+        latency.execute(project_id + 1, &TransactionLatencyParams::default()),
+        Err(DomainError::ProjectNotFound(_))
+    ));
+}
+
+#[test]
+fn test_issue_user_feedback() {
+    let (repos, pool, project_id) = setup_test_db();
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[
+            crash_event("f1", "production", "python", 10),
+            crash_event("f2", "production", "python", 10),
+        ],
+    );
+    let issue_id = repos
+        .report
+        .find_by_event_id(project_id, "f1")
+        .unwrap()
+        .unwrap()
+        .issue_id
+        .unwrap();
+
+    // Feedback on digested events is linked as soon as it is stored
+    let mut conn = pool.get().unwrap();
+    for (event_id, comments) in [("f1", "Lost my cart"), ("f2", "Crashed again")] {
+        repos
+            .user_feedback
+            .upsert(
+                &mut conn,
+                NewUserFeedbackModel {
+                    project_id,
+                    event_id: Some(event_id.to_string()),
+                    name: Some("Sam".to_string()),
+                    email: None,
+                    comments: comments.to_string(),
+                    report_id: None,
+                    issue_id: None,
+                },
+            )
+            .unwrap();
+    }
+    drop(conn);
+
+    let feedback = ListIssueFeedbackUseCase::new(repos, pool);
+    let entries = feedback
+        .execute(project_id, issue_id, &IssueFeedbackParams::default())
+        .unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].event_id.as_deref(), Some("f2"));
+    assert_eq!(entries[0].comments, "Crashed again");
+    assert_eq!(entries[1].event_id.as_deref(), Some("f1"));
+
+    let one = IssueFeedbackParams { limit: Some(1) };
+    assert_eq!(
+        feedback.execute(project_id, issue_id, &one).unwrap().len(),
+        1
+    );
+    assert!(matches!(
+        feedback.execute(project_id, issue_id + 1000, &one),
+        Err(DomainError::NotFound(_))
+    ));
+    assert!(matches!(
+        feedback.execute(project_id + 1000, issue_id, &one),
+        Err(DomainError::ProjectNotFound(_))
+    ));
+}
+
+fn envelope_with_attachments(event_id: &str, attachments: &[(&str, &str, &[u8])]) -> Vec<u8> {
+    let mut envelope = format!(
+        "{{\"event_id\":\"{event_id}\"}}\n{{\"type\":\"event\"}}\n{}\n",
+        String::from_utf8(crash_event(event_id, "production", "python", 10)).unwrap()
+    )
+    .into_bytes();
+    for (filename, attachment_type, content) in attachments {
+        let header = serde_json::json!({
+            "type": "attachment",
+            "length": content.len(),
+            "filename": filename,
+            "attachment_type": attachment_type,
+            "content_type": "image/png",
+        });
+        envelope.extend_from_slice(format!("{header}\n").as_bytes());
+        envelope.extend_from_slice(content);
+        envelope.push(b'\n');
+    }
+    envelope
+}
+
+#[test]
+fn test_event_attachments_are_listed_and_downloaded() {
+    use crate::shared::persistence::db::schema::attachment_blob;
+    use diesel::prelude::*;
+
+    let (repos, pool, project_id) = setup_test_db();
+    // Binary content with a newline, which only the item length can delimit
+    let screenshot: &[u8] = b"\x89PNG\r\n\x1a\n\x00\xff";
+    ingest_and_digest(
+        &repos,
+        &pool,
+        project_id,
+        &[
+            envelope_with_attachments(
+                "a1",
+                &[
+                    ("screenshot.png", "event.attachment", screenshot),
+                    ("view-hierarchy.json", "event.view_hierarchy", b"{}"),
+                ],
+            ),
+            envelope_with_attachments("a2", &[("screenshot.png", "event.attachment", screenshot)]),
+        ],
+    );
+
+    let list = ListEventAttachmentsUseCase::new(repos.clone(), pool.clone());
+    let attachments = list.execute(project_id, "a1").unwrap();
+    assert_eq!(attachments.len(), 2);
+    assert_eq!(attachments[0].filename, "screenshot.png");
+    assert_eq!(attachments[0].size, screenshot.len() as i32);
+    assert_eq!(attachments[1].attachment_type, "event.view_hierarchy");
+    assert_eq!(list.execute(project_id, "a2").unwrap().len(), 1);
+
+    // Identical content is stored once per project
+    let mut conn = pool.get().unwrap();
+    let blobs: i64 = attachment_blob::table
+        .filter(attachment_blob::project_id.eq(project_id))
+        .count()
+        .get_result(&mut conn)
+        .unwrap();
+    assert_eq!(blobs, 2);
+    drop(conn);
+
+    let get = GetEventAttachmentUseCase::new(repos, pool, Compressor::new());
+    let content = get.execute(project_id, "a1", attachments[0].id).unwrap();
+    assert_eq!(content.payload, screenshot);
+    assert_eq!(content.content_type.as_deref(), Some("image/png"));
+    assert!(matches!(
+        get.execute(project_id, "a2", attachments[1].id),
+        Err(DomainError::NotFound(_))
+    ));
+    assert!(matches!(
+        list.execute(project_id, "missing"),
+        Err(DomainError::NotFound(_))
+    ));
+}
+
+fn check_in(
+    monitor_id: i32,
+    check_in_id: &str,
+    status: &str,
+    duration_ms: Option<f64>,
+    expected_at: Option<chrono::NaiveDateTime>,
+    at: chrono::NaiveDateTime,
+) -> NewMonitorCheckinModel {
+    NewMonitorCheckinModel {
+        monitor_id,
+        check_in_id: check_in_id.to_string(),
+        status: status.to_string(),
+        duration_ms,
+        release: Some("my-app@1.0.0".to_string()),
+        environment: Some("production".to_string()),
+        expected_at,
+        received_at: at,
+        updated_at: at,
+    }
+}
+
+#[test]
+fn test_cron_monitor_missed_and_timed_out_check_ins() {
+    let (repos, pool, project_id) = setup_test_db();
+    let mut conn = pool.get().unwrap();
+
+    let envelope = Envelope::parse(
+        br#"{}
+{"type":"check_in"}
+{"check_in_id":"83A7C03E-D5A3-4A53-9C4D-A8C2D5E7F101","monitor_slug":" nightly-backup ","status":"in_progress","monitor_config":{"schedule":{"type":"interval","value":10,"unit":"minute"},"checkin_margin":5,"max_runtime":30,"timezone":"Europe/Paris"}}
+"#,
+    )
+    .unwrap();
+    let payloads = envelope.find_check_in_payloads();
+    assert_eq!(payloads.len(), 1);
+    let parsed = SentryCheckIn::parse(payloads[0]).unwrap();
+    assert_eq!(parsed.monitor_slug, "nightly-backup");
+    assert_eq!(
+        parsed.check_in_id.as_deref(),
+        Some("83a7c03ed5a34a539c4da8c2d5e7f101")
+    );
+    let config = parsed.monitor_config.unwrap();
+    assert_eq!(config.schedule.value_text().as_deref(), Some("10"));
+
+    let schedule = MonitorSchedule::parse("interval", "10", Some("minute")).unwrap();
+    let monitor = repos
+        .monitor
+        .upsert(
+            &mut conn,
+            NewMonitorModel {
+                project_id,
+                slug: parsed.monitor_slug.clone(),
+                schedule_type: "interval".to_string(),
+                schedule: "10".to_string(),
+                schedule_unit: Some("minute".to_string()),
+                checkin_margin: 5,
+                max_runtime: 30,
+                timezone: "Europe/Paris".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(monitor.status, "active");
+
+    let at = |minutes: i64| {
+        chrono::NaiveDate::from_ymd_opt(2026, 10, 17)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+            + chrono::Duration::minutes(minutes)
+    };
+    let next = |minutes: i64| schedule.next_after(at(minutes), &monitor.timezone);
+
+    // in_progress then ok with the same id finish one run
+    let started = repos
+        .monitor
+        .record_check_in(
+            &mut conn,
+            check_in(monitor.id, "run-a", "in_progress", None, None, at(0)),
+            next(0),
+        )
+        .unwrap();
+    let finished = repos
+        .monitor
+        .record_check_in(
+            &mut conn,
+            check_in(monitor.id, "run-a", "ok", Some(1500.0), None, at(2)),
+            next(2),
+        )
+        .unwrap();
+    assert_eq!(finished.id, started.id);
+    assert_eq!(finished.status, "ok");
+    assert_eq!(finished.duration_ms, Some(1500.0));
+
+    // The next run starts on time and never finishes
+    repos
+        .monitor
+        .record_check_in(
+            &mut conn,
+            check_in(monitor.id, "run-b", "in_progress", None, next(0), at(10)),
+            next(10),
+        )
+        .unwrap();
+
+    // At 10:46 run-b is past its 30 minute max runtime, and the runs expected
+    // at 10:20, 10:30 and 10:40 are past their 5 minute margin
+    let check = CheckMonitorsUseCase::new(repos.monitor.clone(), pool.clone());
+    assert_eq!(
+        check.execute(at(46)).unwrap(),
+        MonitorCheckSummary {
+            missed: 3,
+            timed_out: 1
+        }
+    );
+    // Checking again records nothing new
+    assert_eq!(
+        check.execute(at(46)).unwrap(),
+        MonitorCheckSummary::default()
+    );
+
+    // A late ok does not reopen the timed out run
+    let late = repos
+        .monitor
+        .record_check_in(
+            &mut conn,
+            check_in(monitor.id, "run-b", "ok", Some(100.0), None, at(47)),
+            next(47),
+        )
+        .unwrap();
+    assert_eq!(late.status, "timeout");
+
+    let monitors = ListMonitorsUseCase::new(repos.clone(), pool.clone())
+        .execute(project_id)
+        .unwrap();
+    assert_eq!(monitors.len(), 1);
+    assert_eq!(monitors[0].slug, "nightly-backup");
+    assert_eq!(monitors[0].status, "missed");
+    assert_eq!(monitors[0].schedule.value, serde_json::json!(10));
+    assert_eq!(monitors[0].schedule.unit.as_deref(), Some("minute"));
+    assert_eq!(
+        monitors[0].next_checkin_at.map(|t| t.naive_utc()),
+        Some(at(50))
+    );
+
+    let list = ListMonitorCheckInsUseCase::new(repos.clone(), pool.clone());
+    let all = list
+        .execute(
+            project_id,
+            "nightly-backup",
+            &MonitorCheckInParams::default(),
+        )
+        .unwrap();
+    assert_eq!(all.len(), 5);
+
+    let missed = MonitorCheckInParams {
+        status: Some("missed".to_string()),
+        ..Default::default()
+    };
+    let mut expected: Vec<_> = list
+        .execute(project_id, "nightly-backup", &missed)
+        .unwrap()
+        .into_iter()
+        .map(|c| c.expected_at.unwrap().naive_utc())
+        .collect();
+    expected.sort();
+    assert_eq!(expected, vec![at(20), at(30), at(40)]);
+
+    let timed_out = MonitorCheckInParams {
+        status: Some("timeout".to_string()),
+        ..Default::default()
+    };
+    let timed_out = list
+        .execute(project_id, "nightly-backup", &timed_out)
+        .unwrap();
+    assert_eq!(timed_out.len(), 1);
+    assert_eq!(timed_out[0].id, "run-b");
+
+    let invalid = MonitorCheckInParams {
+        status: Some("late".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        list.execute(project_id, "nightly-backup", &invalid),
+        Err(DomainError::InvalidRequest(_))
+    ));
+    assert!(matches!(
+        list.execute(project_id, "unknown", &MonitorCheckInParams::default()),
+        Err(DomainError::NotFound(_))
+    ));
+    assert!(matches!(
+        list.execute(
+            project_id + 1,
+            "nightly-backup",
+            &MonitorCheckInParams::default()
+        ),
+        Err(DomainError::ProjectNotFound(_))
+    ));
+}
+
+#[test]
+fn test_check_ins_are_stored_from_envelopes() {
+    let (repos, pool, project_id) = setup_test_db();
+    let router = ingest_router(&repos, &pool);
+    let check_in_envelope = |check_in: serde_json::Value| {
+        format!("{{}}\n{{\"type\":\"check_in\"}}\n{}\n", check_in).into_bytes()
+    };
+
+    // A slug seen for the first time needs its schedule
+    let (status, _) = post_envelope(
+        &router,
+        project_id,
+        &check_in_envelope(serde_json::json!({"monitor_slug": "cleanup", "status": "ok"})),
+    );
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let monitors = ListMonitorsUseCase::new(repos.clone(), pool.clone());
+    assert!(monitors.execute(project_id).unwrap().is_empty());
+
+    // Out of range margins are clamped and an unknown timezone falls back to UTC
+    let (status, body) = post_envelope(
+        &router,
+        project_id,
+        &check_in_envelope(serde_json::json!({
+            "check_in_id": "5D1B6A2C-0F3E-4C8A-9E7B-6A5D4C3B2A10",
+            "monitor_slug": "cleanup",
+            "status": "in_progress",
+            "release": "my-app@1.0.0",
+            "monitor_config": {
+                "schedule": {"type": "crontab", "value": "0 3 * * *"},
+                "checkin_margin": -5,
+                "max_runtime": 0,
+                "timezone": "Mars/Olympus"
+            }
+        })),
+    );
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["check_ins"], 1);
+
+    // The finishing check-in names the monitor by slug only, duration in seconds
+    let (status, body) = post_envelope(
+        &router,
+        project_id,
+        &check_in_envelope(serde_json::json!({
+            "check_in_id": "5d1b6a2c0f3e4c8a9e7b6a5d4c3b2a10",
+            "monitor_slug": "cleanup",
+            "status": "ok",
+            "duration": 2.5
+        })),
+    );
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let monitors = monitors.execute(project_id).unwrap();
+    assert_eq!(monitors.len(), 1);
+    let monitor = &monitors[0];
+    assert_eq!(monitor.slug, "cleanup");
+    assert_eq!(monitor.status, "ok");
+    assert_eq!(monitor.checkin_margin, 0);
+    assert_eq!(monitor.max_runtime, 1);
+    assert_eq!(monitor.schedule.timezone, "UTC");
+    assert_eq!(monitor.schedule.value, serde_json::json!("0 3 * * *"));
+
+    let check_ins = ListMonitorCheckInsUseCase::new(repos, pool)
+        .execute(project_id, "cleanup", &MonitorCheckInParams::default())
+        .unwrap();
+    assert_eq!(check_ins.len(), 1);
+    assert_eq!(check_ins[0].id, "5d1b6a2c0f3e4c8a9e7b6a5d4c3b2a10");
+    assert_eq!(check_ins[0].status, "ok");
+    assert_eq!(check_ins[0].duration_ms, Some(2500.0));
+    assert_eq!(check_ins[0].release.as_deref(), Some("my-app@1.0.0"));
+}
+
+#[test]
+fn test_monitor_schedule_next_run() {
+    let at = |s: &str| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+
+    // 09:00 in Paris is 07:00 UTC in summer time and 08:00 UTC after it ends
+    let daily = MonitorSchedule::parse("crontab", "0 9 * * *", None).unwrap();
+    assert_eq!(
+        daily.next_after(at("2026-10-17 08:00:00"), "Europe/Paris"),
+        Some(at("2026-10-18 07:00:00"))
+    );
+    assert_eq!(
+        daily.next_after(at("2026-10-25 12:00:00"), "Europe/Paris"),
+        Some(at("2026-10-26 08:00:00"))
+    );
+    // Unknown timezones fall back to UTC
+    assert_eq!(
+        daily.next_after(at("2026-10-17 08:00:00"), "Mars/Olympus"),
+        Some(at("2026-10-17 09:00:00"))
+    );
+
+    let monthly = MonitorSchedule::parse("interval", "1", Some("month")).unwrap();
+    assert_eq!(
+        monthly.next_after(at("2026-01-31 10:00:00"), "UTC"),
+        Some(at("2026-02-28 10:00:00"))
+    );
+
+    assert!(MonitorSchedule::parse("crontab", "not a cron", None).is_err());
+    assert!(MonitorSchedule::parse("interval", "0", Some("hour")).is_err());
+    assert!(MonitorSchedule::parse("interval", "5", Some("fortnight")).is_err());
+    assert!(MonitorSchedule::parse("weekly", "1", None).is_err());
+}
@@ -0,0 +1,1484 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::shared::compression::Compressor;
+use crate::shared::domain::{
+    CheckInStatus, DomainError, IssueStatus, IssueStatusChange, split_release,
+};
+use crate::shared::parser::{Envelope, normalize_event_id};
+use crate::shared::persistence::db::models::{
+    AttachmentModel, IssueModel, MonitorCheckinModel, MonitorModel, ReportBreadcrumbModel,
+    ReportModel, ReportModuleModel, UserFeedbackModel,
+};
+use crate::shared::persistence::{
+    DbConnection, DbPool, IssueCursor, IssueFilter, IssueSort, ReleaseHealthBucket,
+    ReleaseHealthFilter, Repositories, TagDistribution, TransactionLatencyBucket,
+    TransactionLatencyFilter,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 25;
+const MAX_PAGE_SIZE: i64 = 100;
+
+/// Query string accepted by the issue list endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IssueListParams {
+    pub environment: Option<String>,
+    /// Either a bare version (`1.2.3`) or a full release (`my-app@1.2.3+45`)
+    pub release: Option<String>,
+    pub platform: Option<String>,
+    pub exception_type: Option<String>,
+    /// `fatal`, `error`, `warning`, `info` or `debug`
+    pub level: Option<String>,
+    pub sdk_name: Option<String>,
+    pub sdk_version: Option<String>,
+    /// `unresolved`, `resolved` or `ignored`
+    pub status: Option<String>,
+    /// `key:value`, e.g. `flavor:paid`
+    pub tag: Option<String>,
+    /// RFC 3339 lower bound on event timestamps (inclusive)
+    pub start: Option<String>,
+    /// RFC 3339 upper bound on event timestamps (exclusive)
+    pub end: Option<String>,
+    /// `last_seen` (default), `first_seen` or `event_count`
+    pub sort: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueSummary {
+    pub id: i32,
+    pub title: Option<String>,
+    pub exception_type: Option<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub event_count: i32,
+    pub status: String,
+    pub resolved_in_release: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub ignore_until: Option<DateTime<Utc>>,
+    pub ignore_until_event_count: Option<i32>,
+    pub regressed_at: Option<DateTime<Utc>>,
+    pub grouping_strategy: Option<String>,
+}
+
+/// Body accepted by the issue update endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IssueStatusParams {
+    /// `unresolved`, `resolved` or `ignored`
+    pub status: String,
+    /// Only with `resolved`: events from later releases reopen the issue
+    pub resolved_in_release: Option<String>,
+    /// Only with `ignored`: RFC 3339 date after which the issue reopens
+    pub ignore_until: Option<String>,
+    /// Only with `ignored`: number of further events after which the issue reopens
+    pub ignore_count: Option<i32>,
+}
+
+impl IssueStatusParams {
+    pub fn to_change(&self) -> Result<IssueStatusChange, DomainError> {
+        let status = IssueStatus::parse(&self.status).ok_or_else(|| {
+            DomainError::InvalidRequest(format!(
+                "Invalid status '{}' (expected unresolved, resolved or ignored)",
+                self.status
+            ))
+        })?;
+
+        if status != IssueStatus::Resolved && self.resolved_in_release.is_some() {
+            return Err(DomainError::InvalidRequest(
+                "'resolved_in_release' requires status 'resolved'".to_string(),
+            ));
+        }
+        if status != IssueStatus::Ignored
+            && (self.ignore_until.is_some() || self.ignore_count.is_some())
+        {
+            return Err(DomainError::InvalidRequest(
+                "'ignore_until' and 'ignore_count' require status 'ignored'".to_string(),
+            ));
+        }
+
+        match status {
+            IssueStatus::Unresolved => Ok(IssueStatusChange::Unresolve),
+            IssueStatus::Resolved => Ok(IssueStatusChange::Resolve {
+                in_release: self.resolved_in_release.clone().filter(|r| !r.is_empty()),
+            }),
+            IssueStatus::Ignored => {
+                let until = match &self.ignore_until {
+                    Some(v) => Some(
+                        DateTime::parse_from_rfc3339(v)
+                            .map(|dt| dt.naive_utc())
+                            .map_err(|_| {
+                                DomainError::InvalidRequest(
+                                    "Invalid 'ignore_until': expected RFC 3339".to_string(),
+                                )
+                            })?,
+                    ),
+                    None => None,
+                };
+                if self.ignore_count.is_some_and(|c| c < 1) {
+                    return Err(DomainError::InvalidRequest(
+                        "'ignore_count' must be at least 1".to_string(),
+                    ));
+                }
+                Ok(IssueStatusChange::Ignore {
+                    until,
+                    count: self.ignore_count,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuePage {
+    pub data: Vec<IssueSummary>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct ListIssuesUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl ListIssuesUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    pub fn execute(
+        &self,
+        project_id: i32,
+        params: &IssueListParams,
+    ) -> Result<IssuePage, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let filter = match self.build_filter(&mut conn, project_id, params)? {
+            Some(f) => f,
+            // A filter value that was never seen cannot match any issue
+            None => {
+                return Ok(IssuePage {
+                    data: Vec::new(),
+                    next_cursor: None,
+                });
+            }
+        };
+
+        let issues = self
+            .repos
+            .issue
+            .list_filtered_with_conn(&mut conn, &filter)?;
+
+        let next_cursor = if issues.len() as i64 == filter.limit {
+            issues.last().map(|last| {
+                IssueCursor {
+                    sort: filter.sort,
+                    value: filter.sort.key_of(last),
+                    id: last.id,
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+
+        let data = to_summaries(&self.repos, &mut conn, issues)?;
+
+        Ok(IssuePage { data, next_cursor })
+    }
+
+    /// Resolves the textual filters to dimension ids. Returns `None` when a
+    /// filter references a value that does not exist.
+    fn build_filter(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        params: &IssueListParams,
+    ) -> Result<Option<IssueFilter>, DomainError> {
+        let sort = match params.sort.as_deref() {
+            Some(s) => IssueSort::parse(s).ok_or_else(|| {
+                DomainError::InvalidRequest(format!(
+                    "Invalid sort '{}' (expected last_seen, first_seen or event_count)",
+                    s
+                ))
+            })?,
+            None => IssueSort::default(),
+        };
+
+        let cursor = match params.cursor.as_deref() {
+            Some(c) => {
+                let cursor = IssueCursor::decode(c)
+                    .ok_or_else(|| DomainError::InvalidRequest("Invalid cursor".to_string()))?;
+                if cursor.sort != sort {
+                    return Err(DomainError::InvalidRequest(format!(
+                        "Cursor was issued for sort '{}', not '{}'",
+                        cursor.sort.as_str(),
+                        sort.as_str()
+                    )));
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let status = match params.status.as_deref() {
+            Some(s) => Some(IssueStatus::parse(s).ok_or_else(|| {
+                DomainError::InvalidRequest(format!(
+                    "Invalid status '{}' (expected unresolved, resolved or ignored)",
+                    s
+                ))
+            })?),
+            None => None,
+        };
+
+        let mut filter = IssueFilter {
+            project_id,
+            status,
+            since: parse_time_bound("start", &params.start)?,
+            until: parse_time_bound("end", &params.end)?,
+            sort,
+            cursor,
+            limit,
+            ..Default::default()
+        };
+
+        if let Some(v) = &params.environment {
+            match self.repos.environment.find_by_value(conn, v)? {
+                Some(m) => filter.environment_id = Some(m.id),
+                None => return Ok(None),
+            }
+        }
+
+        if let Some(v) = &params.platform {
+            match self.repos.platform.find_by_value(conn, v)? {
+                Some(m) => filter.platform_id = Some(m.id),
+                None => return Ok(None),
+            }
+        }
+
+        if let Some(v) = &params.exception_type {
+            match self.repos.exception_type.find_by_value(conn, v)? {
+                Some(m) => filter.exception_type_id = Some(m.id),
+                None => return Ok(None),
+            }
+        }
+
+        if let Some(v) = &params.level {
+            match self.repos.level.find_by_value(conn, v)? {
+                Some(m) => filter.level_id = Some(m.id),
+                None => return Ok(None),
+            }
+        }
+
+        if let Some(v) = &params.sdk_name {
+            match self.repos.sdk_name.find_by_value(conn, v)? {
+                Some(m) => filter.sdk_name_id = Some(m.id),
+                None => return Ok(None),
+            }
+        }
+
+        if let Some(v) = &params.sdk_version {
+            match self.repos.sdk_version.find_by_value(conn, v)? {
+                Some(m) => filter.sdk_version_id = Some(m.id),
+                None => return Ok(None),
+            }
+        }
+
+        if let Some(tag) = &params.tag {
+            let (key, value) = tag.split_once(':').ok_or_else(|| {
+                DomainError::InvalidRequest(format!("Invalid tag '{}' (expected key:value)", tag))
+            })?;
+            let key_id = match self.repos.tag_key.find_by_value(conn, key)? {
+                Some(m) => m.id,
+                None => return Ok(None),
+            };
+            match self.repos.tag_value.find_by_value(conn, value)? {
+                Some(m) => filter.tag = Some((key_id, m.id)),
+                None => return Ok(None),
+            }
+        }
+
+        if let Some(release) = &params.release {
+            let (version, build) = split_release(release);
+            match self.repos.app_version.find_by_value(conn, version)? {
+                Some(m) => filter.app_version_id = Some(m.id),
+                None => return Ok(None),
+            }
+            if let Some(build) = build {
+                match self.repos.app_build.find_by_value(conn, build)? {
+                    Some(m) => filter.app_build_id = Some(m.id),
+                    None => return Ok(None),
+                }
+            }
+        }
+
+        Ok(Some(filter))
+    }
+}
+
+#[derive(Clone)]
+pub struct UpdateIssueStatusUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl UpdateIssueStatusUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    pub fn execute(
+        &self,
+        project_id: i32,
+        issue_id: i32,
+        params: &IssueStatusParams,
+    ) -> Result<IssueSummary, DomainError> {
+        let change = params.to_change()?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+        if !self
+            .repos
+            .issue
+            .belongs_to_project_with_conn(&mut conn, issue_id, project_id)?
+        {
+            return Err(DomainError::NotFound(format!(
+                "Issue {} not found",
+                issue_id
+            )));
+        }
+
+        let issue = self
+            .repos
+            .issue
+            .set_status_with_conn(&mut conn, issue_id, &change)?
+            .ok_or_else(|| DomainError::NotFound(format!("Issue {} not found", issue_id)))?;
+
+        let exception_type = match issue.exception_type_id {
+            Some(id) => self
+                .repos
+                .exception_type
+                .find_by_id(&mut conn, id)?
+                .map(|m| m.value),
+            None => None,
+        };
+        Ok(to_summary(issue, exception_type))
+    }
+}
+
+const DEFAULT_TAG_VALUES: usize = 10;
+const MAX_TAG_VALUES: usize = 100;
+
+/// Query string accepted by the issue tags endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IssueTagsParams {
+    /// Number of values returned per tag key (default 10)
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone)]
+pub struct GetIssueTagsUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl GetIssueTagsUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    pub fn execute(
+        &self,
+        project_id: i32,
+        issue_id: i32,
+        params: &IssueTagsParams,
+    ) -> Result<Vec<TagDistribution>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+        if !self
+            .repos
+            .issue
+            .belongs_to_project_with_conn(&mut conn, issue_id, project_id)?
+        {
+            return Err(DomainError::NotFound(format!(
+                "Issue {} not found",
+                issue_id
+            )));
+        }
+
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_TAG_VALUES)
+            .clamp(1, MAX_TAG_VALUES);
+        self.repos
+            .report_tag
+            .top_values_for_issue_with_conn(&mut conn, issue_id, limit)
+    }
+}
+
+/// Query string accepted by the issue user feedback endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IssueFeedbackParams {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserFeedbackEntry {
+    pub id: i32,
+    pub event_id: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub comments: String,
+    pub received_at: DateTime<Utc>,
+}
+
+impl From<UserFeedbackModel> for UserFeedbackEntry {
+    fn from(model: UserFeedbackModel) -> Self {
+        Self {
+            id: model.id,
+            event_id: model.event_id,
+            name: model.name,
+            email: model.email,
+            comments: model.comments,
+            received_at: model.received_at.and_utc(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ListIssueFeedbackUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl ListIssueFeedbackUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Feedback left on the issue's events, most recent first
+    pub fn execute(
+        &self,
+        project_id: i32,
+        issue_id: i32,
+        params: &IssueFeedbackParams,
+    ) -> Result<Vec<UserFeedbackEntry>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+        if !self
+            .repos
+            .issue
+            .belongs_to_project_with_conn(&mut conn, issue_id, project_id)?
+        {
+            return Err(DomainError::NotFound(format!(
+                "Issue {} not found",
+                issue_id
+            )));
+        }
+
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+        Ok(self
+            .repos
+            .user_feedback
+            .list_by_issue_with_conn(&mut conn, issue_id, limit)?
+            .into_iter()
+            .map(UserFeedbackEntry::from)
+            .collect())
+    }
+}
+
+/// Summaries of a page of issues, resolving their exception types in one query
+fn to_summaries(
+    repos: &Repositories,
+    conn: &mut DbConnection,
+    issues: Vec<IssueModel>,
+) -> Result<Vec<IssueSummary>, DomainError> {
+    let ids: Vec<i32> = issues.iter().filter_map(|i| i.exception_type_id).collect();
+    let exception_types = repos.exception_type.find_values_by_ids(conn, &ids)?;
+
+    Ok(issues
+        .into_iter()
+        .map(|issue| {
+            let exception_type = issue
+                .exception_type_id
+                .and_then(|id| exception_types.get(&id).cloned());
+            to_summary(issue, exception_type)
+        })
+        .collect())
+}
+
+fn to_summary(issue: IssueModel, exception_type: Option<String>) -> IssueSummary {
+    IssueSummary {
+        id: issue.id,
+        title: issue.title,
+        exception_type,
+        first_seen: issue.first_seen.and_utc(),
+        last_seen: issue.last_seen.and_utc(),
+        event_count: issue.event_count,
+        status: issue.status,
+        resolved_in_release: issue.resolved_in_release,
+        resolved_at: issue.resolved_at.map(|t| t.and_utc()),
+        ignore_until: issue.ignore_until.map(|t| t.and_utc()),
+        ignore_until_event_count: issue.ignore_until_event_count,
+        regressed_at: issue.regressed_at.map(|t| t.and_utc()),
+        grouping_strategy: issue.grouping_strategy,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BreadcrumbEntry {
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(rename = "type")]
+    pub breadcrumb_type: Option<String>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub url: Option<String>,
+    pub data: Option<Value>,
+}
+
+impl From<ReportBreadcrumbModel> for BreadcrumbEntry {
+    fn from(model: ReportBreadcrumbModel) -> Self {
+        Self {
+            timestamp: model.timestamp.map(|t| t.and_utc()),
+            breadcrumb_type: model.breadcrumb_type,
+            category: model.category,
+            level: model.level,
+            message: model.message,
+            url: model.url,
+            data: model.data,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ListEventBreadcrumbsUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl ListEventBreadcrumbsUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Breadcrumbs of an event, oldest first
+    pub fn execute(
+        &self,
+        project_id: i32,
+        event_id: &str,
+    ) -> Result<Vec<BreadcrumbEntry>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let report = self
+            .repos
+            .report
+            .find_in_project_with_conn(&mut conn, project_id, event_id)?
+            .ok_or_else(|| DomainError::NotFound(format!("Event {} not found", event_id)))?;
+
+        Ok(self
+            .repos
+            .report_breadcrumb
+            .find_by_report_with_conn(&mut conn, report.id)?
+            .into_iter()
+            .map(BreadcrumbEntry::from)
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AttachmentEntry {
+    pub id: i32,
+    pub filename: String,
+    pub content_type: Option<String>,
+    #[serde(rename = "type")]
+    pub attachment_type: String,
+    pub size: i32,
+    /// SHA-256 of the content
+    pub sha256: String,
+}
+
+impl From<AttachmentModel> for AttachmentEntry {
+    fn from(model: AttachmentModel) -> Self {
+        Self {
+            id: model.id,
+            filename: model.filename,
+            content_type: model.content_type,
+            attachment_type: model.attachment_type,
+            size: model.size,
+            sha256: model.hash,
+        }
+    }
+}
+
+/// A module loaded in a crashed native process, as read from its minidump
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleEntry {
+    pub code_file: String,
+    pub code_id: Option<String>,
+    pub debug_file: Option<String>,
+    pub debug_id: Option<String>,
+    /// Load address, hex
+    pub image_addr: String,
+    pub image_size: i64,
+    pub version: Option<String>,
+}
+
+impl From<ReportModuleModel> for ModuleEntry {
+    fn from(model: ReportModuleModel) -> Self {
+        Self {
+            code_file: model.code_file,
+            code_id: model.code_id,
+            debug_file: model.debug_file,
+            debug_id: model.debug_id,
+            image_addr: model.image_addr,
+            image_size: model.image_size,
+            version: model.version,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ListEventAttachmentsUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl ListEventAttachmentsUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Attachments of an event, in envelope order
+    pub fn execute(
+        &self,
+        project_id: i32,
+        event_id: &str,
+    ) -> Result<Vec<AttachmentEntry>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let report = self
+            .repos
+            .report
+            .find_in_project_with_conn(&mut conn, project_id, event_id)?
+            .ok_or_else(|| DomainError::NotFound(format!("Event {} not found", event_id)))?;
+
+        Ok(self
+            .repos
+            .attachment
+            .find_by_report_with_conn(&mut conn, report.id)?
+            .into_iter()
+            .map(AttachmentEntry::from)
+            .collect())
+    }
+}
+
+/// Content of an attachment, ready to be sent as a download
+#[derive(Debug, Clone)]
+pub struct AttachmentContent {
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Clone)]
+pub struct GetEventAttachmentUseCase {
+    repos: Repositories,
+    pool: DbPool,
+    compressor: Compressor,
+}
+
+impl GetEventAttachmentUseCase {
+    pub fn new(repos: Repositories, pool: DbPool, compressor: Compressor) -> Self {
+        Self {
+            repos,
+            pool,
+            compressor,
+        }
+    }
+
+    pub fn execute(
+        &self,
+        project_id: i32,
+        event_id: &str,
+        attachment_id: i32,
+    ) -> Result<AttachmentContent, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let report = self
+            .repos
+            .report
+            .find_in_project_with_conn(&mut conn, project_id, event_id)?
+            .ok_or_else(|| DomainError::NotFound(format!("Event {} not found", event_id)))?;
+
+        let (attachment, compressed) = self
+            .repos
+            .attachment
+            .find_with_payload_with_conn(&mut conn, report.id, attachment_id)?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Attachment {} not found", attachment_id))
+            })?;
+
+        Ok(AttachmentContent {
+            filename: attachment.filename,
+            content_type: attachment.content_type,
+            payload: self.compressor.decompress(&compressed)?,
+        })
+    }
+}
+
+/// Query string accepted by the event search endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventSearchParams {
+    /// Substring of a breadcrumb navigation route or HTTP URL (case-insensitive)
+    pub breadcrumb: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventMatch {
+    pub event_id: String,
+    pub issue_id: Option<i32>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub received_at: DateTime<Utc>,
+    /// Breadcrumbs of the event that matched the search
+    pub breadcrumbs: Vec<BreadcrumbEntry>,
+}
+
+#[derive(Clone)]
+pub struct SearchEventsUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl SearchEventsUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Most recent events whose breadcrumbs contain the searched route or URL
+    pub fn execute(
+        &self,
+        project_id: i32,
+        params: &EventSearchParams,
+    ) -> Result<Vec<EventMatch>, DomainError> {
+        let needle = params
+            .breadcrumb
+            .as_deref()
+            .map(str::trim)
+            .filter(|b| !b.is_empty())
+            .ok_or_else(|| DomainError::InvalidRequest("'breadcrumb' is required".to_string()))?;
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let reports = self
+            .repos
+            .report_breadcrumb
+            .search_reports_by_url(&mut conn, project_id, needle, limit)?;
+
+        let mut matches = Vec::with_capacity(reports.len());
+        for report in reports {
+            let breadcrumbs = self
+                .repos
+                .report_breadcrumb
+                .find_matching_in_report_with_conn(&mut conn, report.id, needle)?
+                .into_iter()
+                .map(BreadcrumbEntry::from)
+                .collect();
+            matches.push(EventMatch {
+                event_id: report.event_id,
+                issue_id: report.issue_id,
+                timestamp: DateTime::from_timestamp(report.timestamp, 0),
+                received_at: report.received_at.and_utc(),
+                breadcrumbs,
+            });
+        }
+
+        Ok(matches)
+    }
+}
+
+/// Default window of the release health endpoint when `start` is omitted
+const DEFAULT_RELEASE_HEALTH_HOURS: i64 = 24;
+
+/// Query string accepted by the release health endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReleaseHealthParams {
+    /// Either a bare version (`1.2.3`) or a full release (`my-app@1.2.3+45`)
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    /// RFC 3339 lower bound on bucket starts (inclusive), defaults to 24 hours ago
+    pub start: Option<String>,
+    /// RFC 3339 upper bound on bucket starts (exclusive)
+    pub end: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct GetReleaseHealthUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl GetReleaseHealthUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Hourly session rollups of the project, most recent first
+    pub fn execute(
+        &self,
+        project_id: i32,
+        params: &ReleaseHealthParams,
+    ) -> Result<Vec<ReleaseHealthBucket>, DomainError> {
+        let since = parse_time_bound("start", &params.start)?
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .unwrap_or_else(|| Utc::now() - Duration::hours(DEFAULT_RELEASE_HEALTH_HOURS));
+        let until =
+            parse_time_bound("end", &params.end)?.and_then(|ts| DateTime::from_timestamp(ts, 0));
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let mut filter = ReleaseHealthFilter {
+            project_id,
+            environment: params.environment.clone(),
+            since: Some(since.naive_utc()),
+            until: until.map(|t| t.naive_utc()),
+            ..Default::default()
+        };
+        if let Some(release) = &params.release {
+            match self
+                .repos
+                .release
+                .find_by_name(&mut conn, project_id, release)?
+            {
+                Some(r) => filter.release_id = Some(r.id),
+                None => return Ok(Vec::new()),
+            }
+        }
+
+        self.repos
+            .release_health
+            .list_buckets_with_conn(&mut conn, &filter)
+    }
+}
+
+/// Default window of the transaction latency endpoint when `start` is omitted
+const DEFAULT_TRANSACTION_LATENCY_HOURS: i64 = 24;
+
+/// Query string accepted by the transaction latency endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TransactionLatencyParams {
+    /// Exact transaction name, e.g. `/api/users/{id}` or `MainActivity`
+    pub name: Option<String>,
+    /// Either a bare version (`1.2.3`) or a full release (`my-app@1.2.3+45`)
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    /// RFC 3339 lower bound on bucket starts (inclusive), defaults to 24 hours ago
+    pub start: Option<String>,
+    /// RFC 3339 upper bound on bucket starts (exclusive)
+    pub end: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct GetTransactionLatencyUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl GetTransactionLatencyUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Hourly duration percentiles per transaction name, most recent first
+    pub fn execute(
+        &self,
+        project_id: i32,
+        params: &TransactionLatencyParams,
+    ) -> Result<Vec<TransactionLatencyBucket>, DomainError> {
+        let since = parse_time_bound("start", &params.start)?
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .unwrap_or_else(|| Utc::now() - Duration::hours(DEFAULT_TRANSACTION_LATENCY_HOURS));
+        let until =
+            parse_time_bound("end", &params.end)?.and_then(|ts| DateTime::from_timestamp(ts, 0));
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let mut filter = TransactionLatencyFilter {
+            project_id,
+            name: params.name.clone(),
+            environment: params.environment.clone(),
+            since: Some(since.naive_utc()),
+            until: until.map(|t| t.naive_utc()),
+            ..Default::default()
+        };
+        if let Some(release) = &params.release {
+            match self
+                .repos
+                .release
+                .find_by_name(&mut conn, project_id, release)?
+            {
+                Some(r) => filter.release_id = Some(r.id),
+                None => return Ok(Vec::new()),
+            }
+        }
+
+        self.repos
+            .transaction_latency
+            .list_buckets_with_conn(&mut conn, &filter)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorSummary {
+    pub slug: String,
+    /// active (no check-in yet), ok, error, missed or timeout
+    pub status: String,
+    pub schedule: MonitorScheduleEntry,
+    pub checkin_margin: i32,
+    pub max_runtime: i32,
+    pub last_checkin_at: Option<DateTime<Utc>>,
+    pub next_checkin_at: Option<DateTime<Utc>>,
+}
+
+/// Schedule in the shape SDKs send it in `monitor_config`
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorScheduleEntry {
+    #[serde(rename = "type")]
+    pub schedule_type: String,
+    pub value: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    pub timezone: String,
+}
+
+impl From<MonitorModel> for MonitorSummary {
+    fn from(model: MonitorModel) -> Self {
+        let value = match model.schedule.parse::<u64>() {
+            Ok(count) if model.schedule_type == "interval" => serde_json::json!(count),
+            _ => Value::String(model.schedule),
+        };
+        Self {
+            slug: model.slug,
+            status: model.status,
+            schedule: MonitorScheduleEntry {
+                schedule_type: model.schedule_type,
+                value,
+                unit: model.schedule_unit,
+                timezone: model.timezone,
+            },
+            checkin_margin: model.checkin_margin,
+            max_runtime: model.max_runtime,
+            last_checkin_at: model.last_checkin_at.map(|t| t.and_utc()),
+            next_checkin_at: model.next_checkin_at.map(|t| t.and_utc()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ListMonitorsUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl ListMonitorsUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Cron monitors of the project by slug, with their last known status
+    pub fn execute(&self, project_id: i32) -> Result<Vec<MonitorSummary>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        Ok(self
+            .repos
+            .monitor
+            .list_by_project(&mut conn, project_id)?
+            .into_iter()
+            .map(MonitorSummary::from)
+            .collect())
+    }
+}
+
+/// Query string accepted by the monitor check-ins endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MonitorCheckInParams {
+    /// in_progress, ok, error, missed or timeout
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckInEntry {
+    pub id: String,
+    pub status: String,
+    pub duration_ms: Option<f64>,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    /// When the run was expected; for missed check-ins, the run that did not happen
+    pub expected_at: Option<DateTime<Utc>>,
+    pub received_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<MonitorCheckinModel> for CheckInEntry {
+    fn from(model: MonitorCheckinModel) -> Self {
+        Self {
+            id: model.check_in_id,
+            status: model.status,
+            duration_ms: model.duration_ms,
+            release: model.release,
+            environment: model.environment,
+            expected_at: model.expected_at.map(|t| t.and_utc()),
+            received_at: model.received_at.and_utc(),
+            updated_at: model.updated_at.and_utc(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ListMonitorCheckInsUseCase {
+    repos: Repositories,
+    pool: DbPool,
+}
+
+impl ListMonitorCheckInsUseCase {
+    pub fn new(repos: Repositories, pool: DbPool) -> Self {
+        Self { repos, pool }
+    }
+
+    /// Check-ins of a monitor, most recent first, including the missed and
+    /// timed out runs recorded by the monitor job
+    pub fn execute(
+        &self,
+        project_id: i32,
+        slug: &str,
+        params: &MonitorCheckInParams,
+    ) -> Result<Vec<CheckInEntry>, DomainError> {
+        let status = params.status.as_deref().filter(|s| !s.is_empty());
+        if let Some(status) = status
+            && CheckInStatus::parse(status).is_none()
+        {
+            return Err(DomainError::InvalidRequest(format!(
+                "Invalid status '{}'",
+                status
+            )));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+        let monitor = self
+            .repos
+            .monitor
+            .find_by_slug(&mut conn, project_id, slug)?
+            .ok_or_else(|| DomainError::NotFound(format!("Monitor {} not found", slug)))?;
+
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+        Ok(self
+            .repos
+            .monitor
+            .list_check_ins(&mut conn, monitor.id, status, limit)?
+            .into_iter()
+            .map(CheckInEntry::from)
+            .collect())
+    }
+}
+
+/// Resolves an optional dimension id to its value through an unwrap repository
+macro_rules! resolve_value {
+    ($repo:expr, $conn:expr, $id:expr) => {
+        match $id {
+            Some(id) => $repo.find_by_id($conn, id)?.map(|m| m.value),
+            None => None,
+        }
+    };
+}
+
+/// A value the digest resolved for an event and its path in the event JSON
+type ResolvedDimension = (&'static [&'static str], Option<String>);
+
+#[derive(Clone)]
+pub struct GetEventUseCase {
+    repos: Repositories,
+    pool: DbPool,
+    compressor: Compressor,
+}
+
+impl GetEventUseCase {
+    pub fn new(repos: Repositories, pool: DbPool, compressor: Compressor) -> Self {
+        Self {
+            repos,
+            pool,
+            compressor,
+        }
+    }
+
+    /// Rebuilds the original event from its archive and fills in the values the
+    /// digest resolved for it where the event itself lacks them.
+    pub fn execute(&self, project_id: i32, event_id: &str) -> Result<Value, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        if !self.repos.project.exists(&mut conn, project_id)? {
+            return Err(DomainError::ProjectNotFound(project_id));
+        }
+
+        let report = self
+            .repos
+            .report
+            .find_in_project_with_conn(&mut conn, project_id, event_id)?
+            .ok_or_else(|| DomainError::NotFound(format!("Event {} not found", event_id)))?;
+
+        let archive = self
+            .repos
+            .archive
+            .find_by_hash(&mut conn, project_id, &report.archive_hash)?
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Archive {} not found", report.archive_hash))
+            })?;
+
+        let decompressed = self.compressor.decompress_archive(&archive)?;
+        let mut event = match extract_event_json(&decompressed, &report.event_id)? {
+            Value::Object(map) => map,
+            _ => {
+                return Err(DomainError::Serialization(
+                    "Event payload is not a JSON object".to_string(),
+                ));
+            }
+        };
+
+        for (path, value) in self.resolve_dimensions(&mut conn, &report)? {
+            if let Some(value) = value {
+                insert_if_missing(&mut event, path, Value::String(value));
+            }
+        }
+        event.insert("issue_id".to_string(), serde_json::json!(report.issue_id));
+        event.insert(
+            "project_id".to_string(),
+            serde_json::json!(report.project_id),
+        );
+        event.insert(
+            "received_at".to_string(),
+            serde_json::json!(report.received_at.and_utc()),
+        );
+
+        let modules = self
+            .repos
+            .report_module
+            .find_by_report(&mut conn, report.id)?;
+        if !modules.is_empty() {
+            let modules: Vec<ModuleEntry> = modules.into_iter().map(ModuleEntry::from).collect();
+            event.insert("modules".to_string(), serde_json::json!(modules));
+        }
+
+        Ok(Value::Object(event))
+    }
+
+    fn resolve_dimensions(
+        &self,
+        conn: &mut DbConnection,
+        report: &ReportModel,
+    ) -> Result<Vec<ResolvedDimension>, DomainError> {
+        let repos = &self.repos;
+        let values: [ResolvedDimension; 21] = [
+            (
+                &["platform"],
+                resolve_value!(repos.platform, conn, report.platform_id),
+            ),
+            (
+                &["environment"],
+                resolve_value!(repos.environment, conn, report.environment_id),
+            ),
+            (
+                &["level"],
+                resolve_value!(repos.level, conn, report.level_id),
+            ),
+            (
+                &["sdk", "name"],
+                resolve_value!(repos.sdk_name, conn, report.sdk_name_id),
+            ),
+            (
+                &["sdk", "version"],
+                resolve_value!(repos.sdk_version, conn, report.sdk_version_id),
+            ),
+            (
+                &["contexts", "os", "name"],
+                resolve_value!(repos.os_name, conn, report.os_name_id),
+            ),
+            (
+                &["contexts", "os", "version"],
+                resolve_value!(repos.os_version, conn, report.os_version_id),
+            ),
+            (
+                &["contexts", "device", "manufacturer"],
+                resolve_value!(repos.manufacturer, conn, report.manufacturer_id),
+            ),
+            (
+                &["contexts", "device", "brand"],
+                resolve_value!(repos.brand, conn, report.brand_id),
+            ),
+            (
+                &["contexts", "device", "model"],
+                resolve_value!(repos.model, conn, report.model_id),
+            ),
+            (
+                &["contexts", "device", "chipset"],
+                resolve_value!(repos.chipset, conn, report.chipset_id),
+            ),
+            (
+                &["contexts", "culture", "locale"],
+                resolve_value!(repos.locale_code, conn, report.locale_code_id),
+            ),
+            (
+                &["contexts", "culture", "timezone"],
+                resolve_value!(repos.timezone, conn, report.timezone_id),
+            ),
+            (
+                &["contexts", "device", "connection_type"],
+                resolve_value!(repos.connection_type, conn, report.connection_type_id),
+            ),
+            (
+                &["contexts", "device", "orientation"],
+                resolve_value!(repos.orientation, conn, report.orientation_id),
+            ),
+            (
+                &["contexts", "app", "app_name"],
+                resolve_value!(repos.app_name, conn, report.app_name_id),
+            ),
+            (
+                &["contexts", "app", "app_version"],
+                resolve_value!(repos.app_version, conn, report.app_version_id),
+            ),
+            (
+                &["contexts", "app", "app_build"],
+                resolve_value!(repos.app_build, conn, report.app_build_id),
+            ),
+            (
+                &["user", "id"],
+                resolve_value!(repos.user, conn, report.user_id),
+            ),
+            (
+                &["exception", "values", LAST, "type"],
+                resolve_value!(repos.exception_type, conn, report.exception_type_id),
+            ),
+            (
+                &["exception", "values", LAST, "value"],
+                resolve_value!(repos.exception_message, conn, report.exception_message_id),
+            ),
+        ];
+
+        Ok(values.into())
+    }
+}
+
+/// Path segment of the last element of an array, like the outermost exception
+/// of a chain, which is the one the digest stores
+const LAST: &str = "last";
+
+/// Sets the value at `path` unless the event already has one there. Object
+/// levels are created as needed; a numeric segment or `LAST` indexes an array,
+/// which is created with a single element when missing. A path through a value
+/// of another type is left alone.
+fn insert_if_missing(event: &mut Map<String, Value>, path: &[&str], value: Value) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    let slot = event.entry(first.to_string()).or_insert(Value::Null);
+    insert_value_if_missing(slot, rest, value);
+}
+
+fn insert_value_if_missing(slot: &mut Value, path: &[&str], value: Value) {
+    let Some((segment, rest)) = path.split_first() else {
+        if slot.is_null() {
+            *slot = value;
+        }
+        return;
+    };
+    if *segment == LAST || segment.parse::<usize>().is_ok() {
+        if slot.is_null() {
+            *slot = Value::Array(Vec::new());
+        }
+        let Value::Array(items) = slot else {
+            return;
+        };
+        let index = segment
+            .parse::<usize>()
+            .unwrap_or(items.len().saturating_sub(1));
+        if items.len() == index {
+            items.push(Value::Null);
+        }
+        if let Some(item) = items.get_mut(index) {
+            insert_value_if_missing(item, rest, value);
+        }
+        return;
+    }
+    if slot.is_null() {
+        *slot = Value::Object(Map::new());
+    }
+    if let Value::Object(map) = slot {
+        insert_if_missing(map, path, value);
+    }
+}
+
+/// Returns the event JSON of a stored payload, either a raw `/store` body or
+/// the event item of an envelope with the given event id (the first one when
+/// none matches).
+fn extract_event_json(data: &[u8], event_id: &str) -> Result<Value, DomainError> {
+    if let Ok(value) = serde_json::from_slice::<Value>(data) {
+        return Ok(value);
+    }
+
+    let envelope = Envelope::parse(data).map_err(|e| {
+        DomainError::Serialization(format!(
+            "Unable to parse payload as JSON or envelope: {}",
+            e
+        ))
+    })?;
+    // Like the digest, skip malformed items so the other events of a batch load
+    let mut events = Vec::new();
+    let mut first_error = None;
+    for payload in envelope.find_event_payloads() {
+        match serde_json::from_slice::<Value>(payload) {
+            Ok(event) => events.push(event),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    // A native crash may carry only its minidump
+    if events.is_empty() && envelope.find_minidump().is_some() {
+        return Ok(Value::Object(Map::new()));
+    }
+    if let Some(e) = first_error.filter(|_| events.is_empty()) {
+        return Err(DomainError::Serialization(format!(
+            "Invalid event JSON: {}",
+            e
+        )));
+    }
+    if events.is_empty() {
+        return Err(DomainError::NotFound(
+            "No event found in envelope".to_string(),
+        ));
+    }
+
+    let event_id = normalize_event_id(event_id);
+    let index = events
+        .iter()
+        .position(|event| {
+            event
+                .get("event_id")
+                .and_then(Value::as_str)
+                .is_some_and(|id| normalize_event_id(id) == event_id)
+        })
+        .unwrap_or(0);
+    Ok(events.swap_remove(index))
+}
+
+fn parse_time_bound(name: &str, value: &Option<String>) -> Result<Option<i64>, DomainError> {
+    match value {
+        Some(v) => DateTime::parse_from_rfc3339(v)
+            .map(|dt| Some(dt.timestamp()))
+            .map_err(|_| {
+                DomainError::InvalidRequest(format!("Invalid '{}': expected RFC 3339", name))
+            }),
+        None => Ok(None),
+    }
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, BufWriter, Write};
 
+use crate::shared::compression::ContentEncoding;
+use crate::shared::domain::ARCHIVE_COMPRESSION;
 use crate::shared::persistence::DbPool;
 use crate::shared::persistence::db::models::ArchiveModel;
 use crate::shared::persistence::db::schema::archive;
@@ -30,6 +32,9 @@ pub enum ArchiveCommand {
     View {
         /// Archive hash
         hash: String,
+        /// Project ID (required when the same payload was sent to several projects)
+        #[arg(short, long)]
+        project: Option<i32>,
     },
 }
 
@@ -38,18 +43,25 @@ struct ArchiveRecord {
     hash: String,
     project_id: i32,
     compressed_payload: String, // base64
+    /// Missing in exports made before archives recorded their compression
+    #[serde(default = "default_compression")]
+    compression: String,
     original_size: Option<i32>,
     created_at: String,
 }
 
-pub fn handle(command: ArchiveCommand, pool: &DbPool) {
+fn default_compression() -> String {
+    ARCHIVE_COMPRESSION.to_string()
+}
+
+pub fn handle(command: ArchiveCommand, pool: &DbPool, max_decompressed_bytes: usize) {
     match command {
         ArchiveCommand::Export { output } => export(pool, output),
         ArchiveCommand::Import {
             input,
             skip_existing,
         } => import(pool, input, skip_existing),
-        ArchiveCommand::View { hash } => view(pool, hash),
+        ArchiveCommand::View { hash, project } => view(pool, hash, project, max_decompressed_bytes),
     }
 }
 
@@ -77,6 +89,7 @@ fn export(pool: &DbPool, output: Option<String>) {
             hash: arch.hash,
             project_id: arch.project_id,
             compressed_payload: BASE64.encode(&arch.compressed_payload),
+            compression: arch.compression,
             original_size: arch.original_size,
             created_at: arch.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
         };
@@ -137,6 +150,17 @@ fn import(pool: &DbPool, input: Option<String>, skip_existing: bool) {
             }
         };
 
+        // The digest could not read an archive with a compression it does not know
+        let Some(encoding) = ContentEncoding::parse(&record.compression) else {
+            eprintln!(
+                "Line {}: unknown compression: {}",
+                line_num + 1,
+                record.compression
+            );
+            errors += 1;
+            continue;
+        };
+
         let created_at =
             chrono::NaiveDateTime::parse_from_str(&record.created_at, "%Y-%m-%dT%H:%M:%S")
                 .unwrap_or_else(|_| chrono::Utc::now().naive_utc());
@@ -147,12 +171,13 @@ fn import(pool: &DbPool, input: Option<String>, skip_existing: bool) {
             compressed_payload: payload,
             original_size: record.original_size,
             created_at,
+            compression: encoding.as_str().to_string(),
         };
 
         let result = if skip_existing {
             diesel::insert_into(archive::table)
                 .values(&model)
-                .on_conflict(archive::hash)
+                .on_conflict((archive::project_id, archive::hash))
                 .do_nothing()
                 .execute(&mut conn)
         } else {
@@ -177,36 +202,58 @@ fn import(pool: &DbPool, input: Option<String>, skip_existing: bool) {
     );
 }
 
-fn view(pool: &DbPool, hash: String) {
-    use flate2::read::GzDecoder;
-    use std::io::Read;
-
+fn view(pool: &DbPool, hash: String, project: Option<i32>, max_decompressed_bytes: usize) {
     let mut conn = pool.get().expect("Failed to get connection");
 
-    let archive: ArchiveModel = match archive::table
+    let mut query = archive::table
         .filter(archive::hash.eq(&hash))
         .select(ArchiveModel::as_select())
-        .first(&mut conn)
-    {
+        .into_boxed();
+    if let Some(project_id) = project {
+        query = query.filter(archive::project_id.eq(project_id));
+    }
+
+    let mut archives: Vec<ArchiveModel> = match query.load(&mut conn) {
         Ok(a) => a,
-        Err(diesel::NotFound) => {
+        Err(e) => {
+            eprintln!("Error: Failed to query archive: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let archive = match archives.len() {
+        0 => {
             eprintln!("Error: Archive not found with hash: {}", hash);
             std::process::exit(1);
         }
-        Err(e) => {
-            eprintln!("Error: Failed to query archive: {}", e);
+        1 => archives.remove(0),
+        _ => {
+            let projects: Vec<String> = archives.iter().map(|a| a.project_id.to_string()).collect();
+            eprintln!(
+                "Error: Archive {} exists in projects {}, pick one with --project",
+                hash,
+                projects.join(", ")
+            );
             std::process::exit(1);
         }
     };
 
     // Decompress the payload
-    let mut decoder = GzDecoder::new(&archive.compressed_payload[..]);
-    let mut decompressed = Vec::new();
-
-    if let Err(e) = decoder.read_to_end(&mut decompressed) {
-        eprintln!("Error: Failed to decompress archive: {}", e);
+    let Some(encoding) = ContentEncoding::parse(&archive.compression) else {
+        eprintln!(
+            "Error: Unknown archive compression: {}",
+            archive.compression
+        );
         std::process::exit(1);
-    }
+    };
+    let decompressed =
+        match encoding.decompress_bounded(&archive.compressed_payload, max_decompressed_bytes) {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("Error: Failed to decompress archive: {}", e);
+                std::process::exit(1);
+            }
+        };
 
     // Try to pretty-print as JSON
     match serde_json::from_slice::<serde_json::Value>(&decompressed) {
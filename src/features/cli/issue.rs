@@ -0,0 +1,224 @@
+use chrono::DateTime;
+use clap::Subcommand;
+
+use crate::shared::domain::{IssueStatus, IssueStatusChange};
+use crate::shared::persistence::db::models::IssueModel;
+use crate::shared::persistence::{IssueRepository, ReportTagRepository, UserFeedbackRepository};
+
+#[derive(Subcommand)]
+pub enum IssueCommand {
+    /// List issues, most recently seen first
+    List {
+        /// Only show issues with this status (unresolved, resolved, ignored)
+        #[arg(short, long)]
+        status: Option<String>,
+    },
+    /// Mark an issue as resolved
+    Resolve {
+        /// Issue ID
+        id: i32,
+        /// Release the fix shipped in; only events from later releases reopen the issue
+        #[arg(short, long)]
+        release: Option<String>,
+    },
+    /// Ignore an issue, optionally until a date or a number of further events
+    Ignore {
+        /// Issue ID
+        id: i32,
+        /// Reopen after this date (RFC 3339)
+        #[arg(long)]
+        until: Option<String>,
+        /// Reopen after this many further events
+        #[arg(long)]
+        count: Option<i32>,
+    },
+    /// Move an issue back to unresolved
+    Unresolve {
+        /// Issue ID
+        id: i32,
+    },
+    /// Show the most frequent values of each tag on an issue's events
+    Tags {
+        /// Issue ID
+        id: i32,
+        /// Values shown per tag key
+        #[arg(short, long, default_value_t = 5)]
+        limit: usize,
+    },
+    /// Show the feedback users left on an issue's events, most recent first
+    Feedback {
+        /// Issue ID
+        id: i32,
+        /// Maximum number of entries shown
+        #[arg(short, long, default_value_t = 20)]
+        limit: i64,
+    },
+}
+
+pub fn handle(
+    command: IssueCommand,
+    repo: &IssueRepository,
+    tag_repo: &ReportTagRepository,
+    feedback_repo: &UserFeedbackRepository,
+) {
+    match command {
+        IssueCommand::List { status } => list(repo, status),
+        IssueCommand::Resolve { id, release } => update(
+            repo,
+            id,
+            IssueStatusChange::Resolve {
+                in_release: release,
+            },
+        ),
+        IssueCommand::Ignore { id, until, count } => {
+            let until = match until.as_deref().map(DateTime::parse_from_rfc3339) {
+                Some(Ok(dt)) => Some(dt.naive_utc()),
+                Some(Err(_)) => {
+                    eprintln!("Invalid --until date, expected RFC 3339");
+                    return;
+                }
+                None => None,
+            };
+            if count.is_some_and(|c| c < 1) {
+                eprintln!("--count must be at least 1");
+                return;
+            }
+            update(repo, id, IssueStatusChange::Ignore { until, count })
+        }
+        IssueCommand::Unresolve { id } => update(repo, id, IssueStatusChange::Unresolve),
+        IssueCommand::Tags { id, limit } => tags(repo, tag_repo, id, limit),
+        IssueCommand::Feedback { id, limit } => feedback(repo, feedback_repo, id, limit),
+    }
+}
+
+fn list(repo: &IssueRepository, status: Option<String>) {
+    let status = match status.as_deref().map(IssueStatus::parse) {
+        Some(Some(s)) => Some(s),
+        Some(None) => {
+            eprintln!("Invalid status, expected unresolved, resolved or ignored");
+            return;
+        }
+        None => None,
+    };
+
+    let issues: Vec<IssueModel> = match repo.list_all() {
+        Ok(issues) => issues
+            .into_iter()
+            .filter(|i| status.is_none_or(|s| i.status == s.as_str()))
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to list issues: {}", e);
+            return;
+        }
+    };
+
+    if issues.is_empty() {
+        println!("No issues found");
+        return;
+    }
+
+    println!(
+        "{:<8} {:<12} {:<8} {:<20} {:<40}",
+        "ID", "STATUS", "EVENTS", "LAST SEEN", "TITLE"
+    );
+    println!("{}", "-".repeat(92));
+    for i in issues {
+        println!(
+            "{:<8} {:<12} {:<8} {:<20} {:<40}",
+            i.id,
+            i.status,
+            i.event_count,
+            i.last_seen.format("%Y-%m-%d %H:%M:%S"),
+            i.title.as_deref().unwrap_or("-")
+        );
+    }
+}
+
+fn update(repo: &IssueRepository, id: i32, change: IssueStatusChange) {
+    match repo.set_status(id, &change) {
+        Ok(Some(issue)) => println!("Issue '{}' is now {}", issue.id, issue.status),
+        Ok(None) => eprintln!("Issue '{}' not found", id),
+        Err(e) => eprintln!("Failed to update issue: {}", e),
+    }
+}
+
+fn tags(repo: &IssueRepository, tag_repo: &ReportTagRepository, id: i32, limit: usize) {
+    match repo.find_by_id(id) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            eprintln!("Issue '{}' not found", id);
+            return;
+        }
+        Err(e) => {
+            eprintln!("Failed to load issue: {}", e);
+            return;
+        }
+    }
+
+    let distributions = match tag_repo.top_values_for_issue(id, limit.max(1)) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("Failed to load tags: {}", e);
+            return;
+        }
+    };
+
+    if distributions.is_empty() {
+        println!("No tags found");
+        return;
+    }
+
+    for distribution in distributions {
+        println!("{} ({} events)", distribution.key, distribution.total);
+        for value in distribution.top_values {
+            let share = value.count as f64 * 100.0 / distribution.total as f64;
+            println!("  {:<40} {:>8} {:>6.1}%", value.value, value.count, share);
+        }
+    }
+}
+
+fn feedback(repo: &IssueRepository, feedback_repo: &UserFeedbackRepository, id: i32, limit: i64) {
+    match repo.find_by_id(id) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            eprintln!("Issue '{}' not found", id);
+            return;
+        }
+        Err(e) => {
+            eprintln!("Failed to load issue: {}", e);
+            return;
+        }
+    }
+
+    let entries = match feedback_repo.list_by_issue(id, limit.max(1)) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to load feedback: {}", e);
+            return;
+        }
+    };
+
+    if entries.is_empty() {
+        println!("No feedback found");
+        return;
+    }
+
+    for entry in entries {
+        let author = match (&entry.name, &entry.email) {
+            (Some(name), Some(email)) => format!("{} <{}>", name, email),
+            (Some(name), None) => name.clone(),
+            (None, Some(email)) => email.clone(),
+            (None, None) => "anonymous".to_string(),
+        };
+        println!(
+            "{}  {}  event {}",
+            entry.received_at.format("%Y-%m-%d %H:%M:%S"),
+            author,
+            entry.event_id.as_deref().unwrap_or("-")
+        );
+        for line in entry.comments.lines() {
+            println!("  {}", line);
+        }
+        println!();
+    }
+}
@@ -1,6 +1,10 @@
 pub mod archive;
+pub mod issue;
 pub mod project;
 pub mod ruminate;
+pub mod session;
 
 pub use archive::ArchiveCommand;
+pub use issue::IssueCommand;
 pub use project::ProjectCommand;
+pub use session::SessionCommand;
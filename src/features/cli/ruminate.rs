@@ -6,7 +6,17 @@ use diesel::sql_query;
 use crate::shared::persistence::DbPool;
 
 const TABLES_TO_CLEAR: &[&str] = &[
+    "attachment",
+    "report_module",
+    "report_breadcrumb",
+    "report_tag",
+    "report_thread",
+    "report_exception",
     "report",
+    "bucket_transaction_latency",
+    "transaction",
+    "unwrap_transaction_name",
+    "unwrap_transaction_op",
     "unwrap_stacktrace",
     "issue",
     "unwrap_exception_message",
@@ -28,18 +38,27 @@ const TABLES_TO_CLEAR: &[&str] = &[
     "unwrap_app_build",
     "unwrap_user",
     "unwrap_exception_type",
+    "unwrap_level",
+    "unwrap_sdk_name",
+    "unwrap_sdk_version",
+    "unwrap_tag_key",
+    "unwrap_tag_value",
     "queue",
     "queue_error",
     // Session tables
+    "bucket_release_health",
+    "session_aggregate",
+    "session_aggregate_receipt",
     "session",
+    "release",
     "unwrap_session_status",
-    "unwrap_session_release",
     "unwrap_session_environment",
     // Analytics bucket tables
     "bucket_rate_limit_global",
     "bucket_rate_limit_dsn",
     "bucket_rate_limit_subnet",
     "bucket_request_latency",
+    "bucket_client_discard",
 ];
 
 pub fn handle(pool: &DbPool, yes: bool) {
@@ -59,6 +78,9 @@ pub fn handle(pool: &DbPool, yes: bool) {
     println!("\n  ✓ KEEP intact:");
     println!("      - archive ({} entries)", archive_count);
     println!("      - project");
+    println!("      - attachment_blob (reused as archives are re-digested)");
+    println!("      - user_feedback (relinked as reports are digested)");
+    println!("      - monitor / monitor_checkin (check-ins are not archived)");
     println!("\n  → RE-QUEUE {} archives for processing\n", archive_count);
 
     if !yes {
@@ -98,8 +120,8 @@ pub fn handle(pool: &DbPool, yes: bool) {
     println!("\n⏳ Re-queuing archives...");
 
     let result = sql_query(
-        "INSERT INTO queue (archive_hash, created_at)
-         SELECT hash, NOW() FROM archive",
+        "INSERT INTO queue (project_id, archive_hash, created_at)
+         SELECT project_id, hash, NOW() FROM archive",
     )
     .execute(&mut conn);
 
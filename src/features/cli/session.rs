@@ -0,0 +1,161 @@
+use chrono::{Duration, Utc};
+use clap::Subcommand;
+use std::collections::BTreeMap;
+
+use crate::features::release_health::RollupReleaseHealthUseCase;
+use crate::shared::persistence::{
+    DbPool, ReleaseHealthBucket, ReleaseHealthFilter, ReleaseHealthRepository, ReleaseRepository,
+};
+
+#[derive(Subcommand)]
+pub enum SessionCommand {
+    /// Show crash-free session and user rates per release and environment
+    Stats {
+        /// Project ID
+        project_id: i32,
+        /// Only this release (`1.2.3` or `my-app@1.2.3+45`)
+        #[arg(short, long)]
+        release: Option<String>,
+        /// Only this environment
+        #[arg(short, long)]
+        environment: Option<String>,
+        /// Hours of hourly buckets to summarize
+        #[arg(long, default_value_t = 24)]
+        hours: i64,
+        /// Recompute the buckets of the window before reading them
+        #[arg(long)]
+        refresh: bool,
+    },
+}
+
+/// Totals of one (release, environment) over the summarized buckets
+#[derive(Default)]
+struct Summary {
+    sessions: i64,
+    crashed: i64,
+    abnormal: i64,
+    errored: i64,
+    users: i64,
+    crashed_users: i64,
+}
+
+pub fn handle(command: SessionCommand, pool: &DbPool) {
+    match command {
+        SessionCommand::Stats {
+            project_id,
+            release,
+            environment,
+            hours,
+            refresh,
+        } => stats(pool, project_id, release, environment, hours, refresh),
+    }
+}
+
+fn stats(
+    pool: &DbPool,
+    project_id: i32,
+    release: Option<String>,
+    environment: Option<String>,
+    hours: i64,
+    refresh: bool,
+) {
+    let hours = hours.max(1);
+    let health_repo = ReleaseHealthRepository::new(pool.clone());
+
+    if refresh {
+        let rollup = RollupReleaseHealthUseCase::new(health_repo.clone(), hours);
+        if let Err(e) = rollup.execute(Utc::now().naive_utc()) {
+            eprintln!("Failed to refresh release health: {}", e);
+            return;
+        }
+    }
+
+    let mut filter = ReleaseHealthFilter {
+        project_id,
+        environment,
+        since: Some((Utc::now() - Duration::hours(hours)).naive_utc()),
+        ..Default::default()
+    };
+    if let Some(release) = release {
+        let mut conn = pool.get().expect("Failed to get connection");
+        match ReleaseRepository::new(pool.clone()).find_by_name(&mut conn, project_id, &release) {
+            Ok(Some(r)) => filter.release_id = Some(r.id),
+            Ok(None) => {
+                eprintln!("Release '{}' not found", release);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to load release: {}", e);
+                return;
+            }
+        }
+    }
+
+    let buckets = match health_repo.list_buckets(&filter) {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("Failed to load release health: {}", e);
+            return;
+        }
+    };
+
+    if buckets.is_empty() {
+        println!("No sessions in the last {} hours", hours);
+        return;
+    }
+
+    println!(
+        "{:<24} {:<14} {:>9} {:>8} {:>9} {:>8} {:>15} {:>13}",
+        "RELEASE",
+        "ENVIRONMENT",
+        "SESSIONS",
+        "CRASHED",
+        "ABNORMAL",
+        "ERRORED",
+        "CRASH-FREE SESS",
+        "CRASH-FREE USR"
+    );
+    println!("{}", "-".repeat(107));
+    for ((release, environment), s) in summarize(&buckets) {
+        let crash_free_sessions = 100.0 * (1.0 - s.crashed as f64 / s.sessions as f64);
+        let crash_free_users = if s.users > 0 {
+            format!(
+                "{:.2}%",
+                100.0 * (1.0 - s.crashed_users as f64 / s.users as f64)
+            )
+        } else {
+            "-".to_string()
+        };
+        println!(
+            "{:<24} {:<14} {:>9} {:>8} {:>9} {:>8} {:>14.2}% {:>13}",
+            release.as_deref().unwrap_or("-"),
+            environment.as_deref().unwrap_or("-"),
+            s.sessions,
+            s.crashed,
+            s.abnormal,
+            s.errored,
+            crash_free_sessions,
+            crash_free_users
+        );
+    }
+}
+
+/// Sums the hourly buckets per (release, environment). Users are counted per
+/// hour, so a user active in several hours counts once per hour.
+fn summarize(
+    buckets: &[ReleaseHealthBucket],
+) -> BTreeMap<(Option<String>, Option<String>), Summary> {
+    let mut summaries: BTreeMap<_, Summary> = BTreeMap::new();
+    for b in buckets {
+        let s = summaries
+            .entry((b.release.clone(), b.environment.clone()))
+            .or_default();
+        s.sessions += b.total_sessions as i64;
+        s.crashed += b.crashed as i64;
+        s.abnormal += b.abnormal as i64;
+        s.errored += b.errored as i64;
+        s.users += b.total_users as i64;
+        s.crashed_users += b.crashed_users as i64;
+    }
+    summaries
+}
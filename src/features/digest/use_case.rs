@@ -2,12 +2,17 @@ use diesel::Connection;
 use sha2::{Digest, Sha256};
 use tracing::{error, info, warn};
 
-use crate::shared::compression::GzipCompressor;
-use crate::shared::domain::{DomainError, QueueItem, SentryReport};
-use crate::shared::parser::{Envelope, SentrySession};
-use crate::shared::persistence::db::models::NewSessionModel;
+use crate::shared::compression::Compressor;
+use crate::shared::domain::{
+    DomainError, GroupingStrategy, IssueStatus, QueueItem, SentryContexts, SentryException,
+    SentryExceptionValue, SentryMechanism, SentryReport, SentryStacktraceFrame, SentryTransaction,
+    is_later_release,
+};
+use crate::shared::parser::{Envelope, Minidump, SentrySession, normalize_event_id};
+use crate::shared::persistence::db::models::NewTransactionModel;
 use crate::shared::persistence::{
-    DbConnection, DbPool, DeviceSpecsParams, NewReport, Repositories,
+    DbConnection, DbPool, DeviceSpecsParams, NewAttachment, NewReport, NewReportBreadcrumb,
+    NewReportException, NewReportModule, NewReportThread, Repositories,
 };
 
 // Type aliases for complex return types
@@ -20,17 +25,34 @@ type DeviceIds = (
 );
 type LocaleIds = (Option<i32>, Option<i32>, Option<i32>, Option<i32>);
 type AppIds = (Option<i32>, Option<i32>, Option<i32>);
-type ExceptionIds = (Option<i32>, Option<i32>, Option<i32>, Option<i32>);
+
+/// Exception, thread and issue data of an event, resolved before the report row exists
+struct ExceptionInfo {
+    exception_type_id: Option<i32>,
+    exception_message_id: Option<i32>,
+    stacktrace_id: Option<i32>,
+    issue_id: Option<i32>,
+    chain: Vec<NewReportException>,
+    threads: Vec<NewReportThread>,
+}
+
+/// Session update applied while digesting an envelope, with its time span in
+/// Unix seconds
+struct DigestedSession {
+    id: i32,
+    started_at: i64,
+    timestamp: i64,
+}
 
 #[derive(Clone)]
 pub struct DigestReportUseCase {
     repos: Repositories,
     pool: DbPool,
-    compressor: GzipCompressor,
+    compressor: Compressor,
 }
 
 impl DigestReportUseCase {
-    pub fn new(repos: Repositories, pool: DbPool, compressor: GzipCompressor) -> Self {
+    pub fn new(repos: Repositories, pool: DbPool, compressor: Compressor) -> Self {
         Self {
             repos,
             pool,
@@ -61,7 +83,9 @@ impl DigestReportUseCase {
                         .pool
                         .get()
                         .map_err(|e| DomainError::Database(e.to_string()))?;
-                    self.repos.queue.remove(&mut conn, &item.archive_hash)?;
+                    self.repos
+                        .queue
+                        .remove(&mut conn, item.project_id, &item.archive_hash)?;
                 }
                 Err(e) => {
                     self.handle_failure(&item, e)?;
@@ -94,25 +118,143 @@ impl DigestReportUseCase {
         let archive = self
             .repos
             .archive
-            .find_by_hash(conn, &item.archive_hash)?
+            .find_by_hash(conn, item.project_id, &item.archive_hash)?
             .ok_or_else(|| {
                 DomainError::NotFound(format!("Archive {} not found", item.archive_hash))
             })?;
 
-        let decompressed = self.compressor.decompress(&archive.compressed_payload)?;
+        let decompressed = self.compressor.decompress_archive(&archive)?;
 
-        // Try to parse as envelope first to extract session
-        let session_id = self.extract_and_store_session(conn, &decompressed, archive.project_id)?;
+        // Raw JSON from the store endpoint, an envelope otherwise
+        match serde_json::from_slice::<SentryReport>(&decompressed) {
+            Ok(report) => {
+                self.store_events(conn, item, vec![report], 0, &[], Vec::new(), Vec::new())?
+            }
+            Err(_) => {
+                let envelope = Envelope::parse(&decompressed).map_err(|e| {
+                    DomainError::Serialization(format!(
+                        "Unable to parse payload as JSON or envelope: {}",
+                        e
+                    ))
+                })?;
+                self.digest_envelope(conn, item, &envelope)?;
+            }
+        }
 
-        // Try parsing as raw JSON first, then as envelope format
-        let sentry_report: SentryReport = self.parse_payload(&decompressed)?;
+        self.repos
+            .queue
+            .remove(conn, item.project_id, &item.archive_hash)?;
+
+        Ok(())
+    }
+
+    /// Digests every item of an envelope, parsed once for all of them
+    fn digest_envelope(
+        &self,
+        conn: &mut DbConnection,
+        item: &QueueItem,
+        envelope: &Envelope,
+    ) -> Result<(), DomainError> {
+        // Session updates were applied at ingest; each report links to one of them
+        let sessions = self.find_sessions(conn, envelope, item.project_id)?;
+
+        for transaction in self.parse_transactions(envelope) {
+            match self.store_transaction(conn, item, &transaction) {
+                Ok(()) => {}
+                Err(DomainError::Serialization(reason)) => {
+                    warn!(
+                        archive_hash = %item.archive_hash,
+                        event_id = ?transaction.event_id,
+                        reason = %reason,
+                        "Skipping invalid transaction"
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut events = self.parse_events(envelope)?;
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        // Attachments and the minidump belong to the event named in the envelope header
+        let primary = primary_event_index(envelope, &events);
+        let minidump = extract_minidump(envelope);
+        if let Some(dump) = &minidump {
+            apply_minidump(&mut events[primary], dump);
+        }
+        let attachments = self.extract_attachments(envelope)?;
+        let modules = minidump.map(extract_modules).unwrap_or_default();
+
+        self.store_events(conn, item, events, primary, &sessions, attachments, modules)
+    }
+
+    /// Stores the events of an archive, skipping those already digested.
+    /// Attachments and modules go to the event at `primary`.
+    #[allow(clippy::too_many_arguments)]
+    fn store_events(
+        &self,
+        conn: &mut DbConnection,
+        item: &QueueItem,
+        events: Vec<SentryReport>,
+        primary: usize,
+        sessions: &[DigestedSession],
+        mut attachments: Vec<NewAttachment>,
+        mut modules: Vec<NewReportModule>,
+    ) -> Result<(), DomainError> {
+        for (index, sentry_report) in events.into_iter().enumerate() {
+            let (attachments, modules) = if index == primary {
+                (
+                    std::mem::take(&mut attachments),
+                    std::mem::take(&mut modules),
+                )
+            } else {
+                (Vec::new(), Vec::new())
+            };
+            match self.store_event(conn, item, sentry_report, sessions, attachments, modules) {
+                Ok(()) => {}
+                Err(DomainError::DuplicateEventId(event_id)) => {
+                    info!(
+                        archive_hash = %item.archive_hash,
+                        event_id = %event_id,
+                        "Duplicate event_id, skipping (already processed)"
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
 
+    /// Stores one event of the archive as a report with its exception chain,
+    /// threads, tags, breadcrumbs, modules and attachments
+    fn store_event(
+        &self,
+        conn: &mut DbConnection,
+        item: &QueueItem,
+        sentry_report: SentryReport,
+        sessions: &[DigestedSession],
+        attachments: Vec<NewAttachment>,
+        modules: Vec<NewReportModule>,
+    ) -> Result<(), DomainError> {
+        let project_id = item.project_id;
         let event_id = sentry_report
             .event_id
             .clone()
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        // Checked first so a duplicate leaves no trace on its issue
+        if self
+            .repos
+            .report
+            .find_in_project_with_conn(conn, project_id, &event_id)?
+            .is_some()
+        {
+            return Err(DomainError::DuplicateEventId(event_id));
+        }
 
         let timestamp = self.parse_timestamp(&sentry_report.timestamp);
+        let session_id = session_for_event(sessions, timestamp);
 
         let platform_id = self.get_or_create_unwrap(conn, &sentry_report.platform, |conn, v| {
             self.repos.platform.get_or_create(conn, v)
@@ -131,14 +273,25 @@ impl DigestReportUseCase {
         let (app_name_id, app_version_id, app_build_id) =
             self.extract_app_info(conn, &sentry_report)?;
         let user_id = self.extract_user_info(conn, &sentry_report)?;
-        let (exception_type_id, exception_message_id, stacktrace_id, issue_id) =
-            self.extract_exception_info(conn, &sentry_report)?;
+        let level_id = Some(
+            self.repos
+                .level
+                .get_or_create(conn, &sentry_report.normalized_level())?,
+        );
+        let (sdk_name_id, sdk_version_id) = self.extract_sdk_info(conn, &sentry_report)?;
+        let release_id = match &sentry_report.release {
+            Some(r) => self.repos.release.touch(conn, project_id, r)?,
+            None => None,
+        };
+        let exception_info = self.extract_exception_info(conn, project_id, &sentry_report)?;
+        let tags = self.extract_tags(conn, &sentry_report)?;
+        let breadcrumbs = extract_breadcrumbs(&sentry_report);
 
         let new_report = NewReport {
-            event_id,
+            event_id: event_id.clone(),
             archive_hash: item.archive_hash.clone(),
             timestamp,
-            project_id: archive.project_id,
+            project_id,
             platform_id,
             environment_id,
             os_name_id,
@@ -156,88 +309,94 @@ impl DigestReportUseCase {
             app_version_id,
             app_build_id,
             user_id,
-            exception_type_id,
-            exception_message_id,
-            stacktrace_id,
-            issue_id,
+            exception_type_id: exception_info.exception_type_id,
+            exception_message_id: exception_info.exception_message_id,
+            stacktrace_id: exception_info.stacktrace_id,
+            issue_id: exception_info.issue_id,
             session_id,
+            level_id,
+            sdk_name_id,
+            sdk_version_id,
+            release_id,
         };
 
-        self.repos.report.create(conn, new_report)?;
-        self.repos.queue.remove(conn, &item.archive_hash)?;
+        let report_id = self.repos.report.create(conn, new_report)?;
+        self.repos
+            .report_exception
+            .create_for_report(conn, report_id, exception_info.chain)?;
+        self.repos
+            .report_thread
+            .create_for_report(conn, report_id, exception_info.threads)?;
+        self.repos
+            .report_tag
+            .create_for_report(conn, report_id, &tags)?;
+        self.repos
+            .report_breadcrumb
+            .create_for_report(conn, report_id, breadcrumbs)?;
+        self.repos
+            .report_module
+            .create_for_report(conn, report_id, modules)?;
+        self.repos
+            .attachment
+            .create_for_report(conn, project_id, report_id, attachments)?;
+        // Feedback can arrive before its event is digested
+        self.repos.user_feedback.link_report(
+            conn,
+            project_id,
+            &normalize_event_id(&event_id),
+            report_id,
+            exception_info.issue_id,
+        )?;
 
         Ok(())
     }
 
-    /// Extract session from envelope and store it (with connection), returning the session_id if found
-    fn extract_and_store_session(
+    /// Sessions of the envelope's session items, as stored at ingest
+    fn find_sessions(
         &self,
         conn: &mut DbConnection,
-        decompressed: &[u8],
+        envelope: &Envelope,
         project_id: i32,
-    ) -> Result<Option<i32>, DomainError> {
-        // Try to parse as envelope
-        let envelope = match Envelope::parse(decompressed) {
-            Some(env) => env,
-            None => return Ok(None), // Not an envelope format, no session
-        };
-
-        // Find session payloads
-        let session_payloads = envelope.find_session_payloads();
-        if session_payloads.is_empty() {
-            return Ok(None);
-        }
-
-        // Process the first (typically only) session
-        let session_data = session_payloads[0];
-        let session = match SentrySession::parse(session_data) {
-            Some(s) => s,
-            None => {
-                warn!("Failed to parse session payload");
-                return Ok(None);
-            }
-        };
-
-        // Get or create status_id
-        let status_id = self
-            .repos
-            .session_status
-            .get_or_create(conn, &session.status)?;
-
-        // Get or create release_id (optional)
-        let release_id = match &session.attrs.release {
-            Some(r) => Some(self.repos.session_release.get_or_create(conn, r)?),
-            None => None,
-        };
-
-        // Get or create environment_id (optional)
-        let environment_id = match &session.attrs.environment {
-            Some(env) => Some(self.repos.session_environment.get_or_create(conn, env)?),
-            None => None,
-        };
-
-        let new_session = NewSessionModel {
-            project_id,
-            sid: session.sid.clone(),
-            init: if session.init { 1 } else { 0 },
-            started_at: session.started.clone(),
-            timestamp: session
-                .timestamp
-                .clone()
-                .unwrap_or_else(|| session.started.clone()),
-            errors: session.errors,
-            status_id,
-            release_id,
-            environment_id,
-        };
-
-        match self.repos.session.upsert(conn, new_session) {
-            Ok(session_id) => Ok(Some(session_id)),
-            Err(e) => {
-                warn!(error = %e, sid = %session.sid, "Failed to store session during digest");
-                Ok(None)
+    ) -> Result<Vec<DigestedSession>, DomainError> {
+        let mut sessions = Vec::new();
+        for session_data in envelope.find_session_payloads() {
+            let Some(session) = SentrySession::parse(session_data) else {
+                continue;
+            };
+            match self
+                .repos
+                .session
+                .find_by_sid_with_conn(conn, project_id, &session.sid)?
+            {
+                Some(stored) => sessions.push(DigestedSession {
+                    id: stored.id,
+                    started_at: stored.started_at.and_utc().timestamp(),
+                    timestamp: stored.timestamp.and_utc().timestamp(),
+                }),
+                None => {
+                    warn!(sid = %session.sid, "Session of the envelope was not stored at ingest")
+                }
             }
         }
+        Ok(sessions)
+    }
+
+    /// Attachment items of an envelope, hashed and compressed for storage
+    fn extract_attachments(&self, envelope: &Envelope) -> Result<Vec<NewAttachment>, DomainError> {
+        envelope
+            .find_attachments()
+            .into_iter()
+            .map(|item| {
+                Ok(NewAttachment {
+                    filename: item.header.filename().unwrap_or("attachment").to_string(),
+                    content_type: item.header.content_type.clone(),
+                    attachment_type: item.header.attachment_type().to_string(),
+                    hash: self.compute_hash(&item.payload),
+                    size: item.payload.len() as i32,
+                    compressed_payload: self.compressor.compress(&item.payload)?,
+                })
+            })
+            .collect()
     }
 
     fn get_or_create_unwrap<F>(
@@ -278,6 +437,23 @@ impl DigestReportUseCase {
         Ok((os_name_id, os_version_id))
     }
 
+    fn extract_sdk_info(
+        &self,
+        conn: &mut DbConnection,
+        report: &SentryReport,
+    ) -> Result<(Option<i32>, Option<i32>), DomainError> {
+        let (sdk_name, sdk_version) = report.extract_sdk_info();
+
+        let sdk_name_id = self.get_or_create_unwrap(conn, &sdk_name, |conn, v| {
+            self.repos.sdk_name.get_or_create(conn, v)
+        })?;
+        let sdk_version_id = self.get_or_create_unwrap(conn, &sdk_version, |conn, v| {
+            self.repos.sdk_version.get_or_create(conn, v)
+        })?;
+
+        Ok((sdk_name_id, sdk_version_id))
+    }
+
     fn extract_device_info(
         &self,
         conn: &mut DbConnection,
@@ -431,23 +607,108 @@ impl DigestReportUseCase {
         }
     }
 
+    /// Resolves the event tags to (key id, value id) pairs
+    fn extract_tags(
+        &self,
+        conn: &mut DbConnection,
+        report: &SentryReport,
+    ) -> Result<Vec<(i32, i32)>, DomainError> {
+        let mut tags = Vec::new();
+        for (key, value) in report.extract_tags() {
+            let key_id = self.repos.tag_key.get_or_create(conn, &key)?;
+            let value_id = self.repos.tag_value.get_or_create(conn, &value)?;
+            tags.push((key_id, value_id));
+        }
+        Ok(tags)
+    }
+
     fn extract_exception_info(
         &self,
         conn: &mut DbConnection,
+        project_id: i32,
         report: &SentryReport,
-    ) -> Result<ExceptionIds, DomainError> {
-        let exception = report
-            .exception
-            .as_ref()
-            .and_then(|e| e.values.as_ref())
-            .and_then(|v| v.first());
+    ) -> Result<ExceptionInfo, DomainError> {
+        let exception = report.primary_exception();
+
+        let default_grouping = self.default_fingerprint(report, exception);
+        let grouping = match report.extract_fingerprint() {
+            Some(parts) => self.custom_fingerprint(&parts, default_grouping),
+            None => default_grouping,
+        };
+        let fingerprint_hash = grouping.as_ref().map(|(hash, _)| hash.clone());
+
+        let mut chain = Vec::new();
+        for value in report.exception_chain() {
+            chain.push(self.extract_exception_value(conn, value, &fingerprint_hash)?);
+        }
+        let primary = chain.last().cloned().unwrap_or_default();
+
+        let crashed_thread = report.crashed_thread();
+        let mut threads = Vec::new();
+        let mut crashed_stacktrace_id = None;
+        for thread in report.thread_list() {
+            let stacktrace_id = self.store_frames(conn, thread.frames(), &fingerprint_hash)?;
+            if crashed_thread.is_some_and(|t| std::ptr::eq(t, thread)) {
+                crashed_stacktrace_id = stacktrace_id;
+            }
+            threads.push(NewReportThread {
+                thread_id: thread.id_string(),
+                name: thread.name.clone(),
+                crashed: thread.crashed.unwrap_or(false),
+                current: thread.current.unwrap_or(false),
+                stacktrace_id,
+            });
+        }
 
-        let exception_type_id = match exception.and_then(|e| e.exception_type.as_ref()) {
+        let issue_id = match &grouping {
+            Some((fp, strategy)) => {
+                let title = exception
+                    .and_then(|e| e.exception_type.clone())
+                    .or_else(|| {
+                        report
+                            .extract_message_template()
+                            .map(|m| truncate_title(&m))
+                    });
+                Some(self.repos.issue.get_or_create(
+                    conn,
+                    project_id,
+                    fp,
+                    primary.exception_type_id,
+                    title,
+                    *strategy,
+                )?)
+            }
+            None => None,
+        };
+
+        if let Some(id) = issue_id {
+            self.apply_issue_lifecycle(conn, id, report)?;
+        }
+
+        Ok(ExceptionInfo {
+            exception_type_id: primary.exception_type_id,
+            exception_message_id: primary.exception_message_id,
+            // Native crashes and ANRs carry the crash stack on the crashed thread
+            stacktrace_id: primary.stacktrace_id.or(crashed_stacktrace_id),
+            issue_id,
+            chain,
+            threads,
+        })
+    }
+
+    /// Resolves the type, message and stack of one value of the exception chain
+    fn extract_exception_value(
+        &self,
+        conn: &mut DbConnection,
+        exception: &SentryExceptionValue,
+        fingerprint_hash: &Option<String>,
+    ) -> Result<NewReportException, DomainError> {
+        let exception_type_id = match exception.exception_type.as_ref() {
             Some(v) => Some(self.repos.exception_type.get_or_create(conn, v)?),
             None => None,
         };
 
-        let exception_message_id = match exception.and_then(|e| e.value.as_ref()) {
+        let exception_message_id = match exception.value.as_ref() {
             Some(msg) => {
                 let hash = self.compute_hash(msg.as_bytes());
                 Some(
@@ -459,73 +720,189 @@ impl DigestReportUseCase {
             None => None,
         };
 
+        let frames = exception
+            .stacktrace
+            .as_ref()
+            .and_then(|s| s.frames.as_deref())
+            .unwrap_or_default();
+        let stacktrace_id = self.store_frames(conn, frames, fingerprint_hash)?;
+
+        let mechanism = exception.mechanism.as_ref();
+        Ok(NewReportException {
+            exception_type_id,
+            exception_message_id,
+            mechanism_type: mechanism.and_then(|m| m.mechanism_type.clone()),
+            handled: mechanism.and_then(|m| m.handled),
+            stacktrace_id,
+        })
+    }
+
+    /// Stores a stack in `unwrap_stacktrace`, deduplicated by the hash of its frames
+    fn store_frames(
+        &self,
+        conn: &mut DbConnection,
+        frames: &[SentryStacktraceFrame],
+        fingerprint_hash: &Option<String>,
+    ) -> Result<Option<i32>, DomainError> {
+        if frames.is_empty() {
+            return Ok(None);
+        }
+
+        let frames_json = serde_json::to_string(frames).unwrap_or_default();
+        let hash = self.compute_hash(frames_json.as_bytes());
+        let frames = serde_json::to_value(frames).unwrap_or(serde_json::Value::Array(vec![]));
+        Ok(Some(self.repos.stacktrace.get_or_create(
+            conn,
+            &hash,
+            fingerprint_hash.clone(),
+            frames,
+        )?))
+    }
+
+    /// Fallback chain used when the SDK sends no fingerprint: in-app frames,
+    /// then all frames, then exception type and normalized message, then the
+    /// logentry template of message events.
+    fn default_fingerprint(
+        &self,
+        report: &SentryReport,
+        exception: Option<&SentryExceptionValue>,
+    ) -> Option<(String, GroupingStrategy)> {
         let in_app_frames = report.extract_in_app_frames();
-        let (fingerprint_hash, stacktrace_hash) = if !in_app_frames.is_empty() {
-            let fingerprint_data = in_app_frames
-                .iter()
-                .map(|f| {
-                    format!(
-                        "{}:{}:{}",
-                        f.filename.as_deref().unwrap_or(""),
-                        f.function.as_deref().unwrap_or(""),
-                        f.lineno.unwrap_or(0)
-                    )
-                })
-                .collect::<Vec<_>>()
-                .join("|");
-            let fingerprint = self.compute_hash(fingerprint_data.as_bytes());
+        if !in_app_frames.is_empty() {
+            let data = Self::frames_fingerprint_data(&in_app_frames);
+            return Some((
+                self.compute_hash(data.as_bytes()),
+                GroupingStrategy::InAppFrames,
+            ));
+        }
 
-            let all_frames = exception
-                .and_then(|e| e.stacktrace.as_ref())
-                .and_then(|s| s.frames.as_ref());
+        let all_frames = report.extract_all_frames();
+        if !all_frames.is_empty() {
+            let data = format!("all_frames|{}", Self::frames_fingerprint_data(&all_frames));
+            return Some((
+                self.compute_hash(data.as_bytes()),
+                GroupingStrategy::AllFrames,
+            ));
+        }
 
-            let stacktrace_hash = all_frames.map(|frames| {
-                let frames_json = serde_json::to_string(frames).unwrap_or_default();
-                self.compute_hash(frames_json.as_bytes())
-            });
+        let exception_type = exception.and_then(|e| e.exception_type.as_deref());
+        let exception_value = exception.and_then(|e| e.value.as_deref());
+        if exception_type.is_some() || exception_value.is_some() {
+            let data = format!(
+                "exception|{}|{}",
+                exception_type.unwrap_or(""),
+                normalize_message(exception_value.unwrap_or(""))
+            );
+            return Some((
+                self.compute_hash(data.as_bytes()),
+                GroupingStrategy::ExceptionMessage,
+            ));
+        }
 
-            (Some(fingerprint), stacktrace_hash)
-        } else {
-            (None, None)
-        };
+        report.extract_message_template().map(|template| {
+            let data = format!("logentry|{}", template.trim());
+            (
+                self.compute_hash(data.as_bytes()),
+                GroupingStrategy::LogEntry,
+            )
+        })
+    }
 
-        let issue_id = match &fingerprint_hash {
-            Some(fp) => {
-                let title = exception.and_then(|e| e.exception_type.as_ref()).cloned();
-                Some(
-                    self.repos
-                        .issue
-                        .get_or_create(conn, fp, exception_type_id, title)?,
+    fn frames_fingerprint_data(frames: &[&SentryStacktraceFrame]) -> String {
+        frames
+            .iter()
+            .map(|f| {
+                format!(
+                    "{}:{}:{}",
+                    f.filename.as_deref().unwrap_or(""),
+                    f.function.as_deref().unwrap_or(""),
+                    f.lineno.unwrap_or(0)
                 )
-            }
-            None => None,
+            })
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
+    /// Hashes an SDK-provided fingerprint, expanding `{{ default }}` to the
+    /// default fingerprint. A fingerprint made only of `{{ default }}` keeps the
+    /// default grouping unchanged.
+    fn custom_fingerprint(
+        &self,
+        parts: &[String],
+        default: Option<(String, GroupingStrategy)>,
+    ) -> Option<(String, GroupingStrategy)> {
+        let is_default = |part: &String| {
+            part.trim()
+                .strip_prefix("{{")
+                .and_then(|p| p.strip_suffix("}}"))
+                .is_some_and(|p| p.trim() == "default")
         };
 
-        let stacktrace_id = match (&stacktrace_hash, &exception) {
-            (Some(hash), Some(exc)) => {
-                let frames = exc
-                    .stacktrace
-                    .as_ref()
-                    .and_then(|s| s.frames.as_ref())
-                    .and_then(|f| serde_json::to_value(f).ok())
-                    .unwrap_or(serde_json::Value::Array(vec![]));
+        if parts.iter().all(is_default) {
+            return default;
+        }
 
-                Some(self.repos.stacktrace.get_or_create(
-                    conn,
-                    hash,
-                    fingerprint_hash.clone(),
-                    frames,
-                )?)
-            }
-            _ => None,
+        let default_hash = default.as_ref().map(|(hash, _)| hash.as_str());
+        let expanded: Vec<&str> = parts
+            .iter()
+            .map(|part| {
+                if is_default(part) {
+                    default_hash.unwrap_or("")
+                } else {
+                    part.as_str()
+                }
+            })
+            .collect();
+        let data = serde_json::to_string(&expanded).unwrap_or_default();
+        Some((self.compute_hash(data.as_bytes()), GroupingStrategy::Custom))
+    }
+
+    /// Reopens a resolved issue hit by a regression, or an ignored issue whose
+    /// ignore window has run out. Called after the event was counted.
+    fn apply_issue_lifecycle(
+        &self,
+        conn: &mut DbConnection,
+        issue_id: i32,
+        report: &SentryReport,
+    ) -> Result<(), DomainError> {
+        let issue = match self.repos.issue.find_by_id_with_conn(conn, issue_id)? {
+            Some(issue) => issue,
+            None => return Ok(()),
         };
 
-        Ok((
-            exception_type_id,
-            exception_message_id,
-            stacktrace_id,
-            issue_id,
-        ))
+        match IssueStatus::parse(&issue.status) {
+            Some(IssueStatus::Resolved) => {
+                let regressed = match &issue.resolved_in_release {
+                    Some(resolved_in) => report
+                        .release
+                        .as_deref()
+                        .is_some_and(|release| is_later_release(release, resolved_in)),
+                    None => true,
+                };
+                if regressed {
+                    info!(
+                        issue_id,
+                        release = report.release.as_deref().unwrap_or("-"),
+                        "Resolved issue regressed, reopening"
+                    );
+                    self.repos.issue.reopen(conn, issue_id, true)?;
+                }
+            }
+            Some(IssueStatus::Ignored) => {
+                let now = chrono::Utc::now().naive_utc();
+                let expired = issue.ignore_until.is_some_and(|until| now >= until)
+                    || issue
+                        .ignore_until_event_count
+                        .is_some_and(|count| issue.event_count >= count);
+                if expired {
+                    info!(issue_id, "Ignore condition reached, reopening issue");
+                    self.repos.issue.reopen(conn, issue_id, false)?;
+                }
+            }
+            Some(IssueStatus::Unresolved) | None => {}
+        }
+
+        Ok(())
     }
 
     fn parse_release(release: &Option<String>) -> (Option<String>, Option<String>, Option<String>) {
@@ -553,26 +930,131 @@ impl DigestReportUseCase {
         hex::encode(hasher.finalize())
     }
 
-    /// Parse payload as either raw JSON or Sentry envelope format
-    fn parse_payload(&self, data: &[u8]) -> Result<SentryReport, DomainError> {
-        // Try raw JSON first (from /store endpoint)
-        if let Ok(report) = serde_json::from_slice::<SentryReport>(data) {
-            return Ok(report);
-        }
+    /// Transactions of an envelope; malformed items are skipped
+    fn parse_transactions(&self, envelope: &Envelope) -> Vec<SentryTransaction> {
+        envelope
+            .find_transaction_payloads()
+            .into_iter()
+            .filter_map(|payload| match serde_json::from_slice(payload) {
+                Ok(transaction) => Some(transaction),
+                Err(e) => {
+                    warn!(error = %e, "Skipping invalid transaction JSON");
+                    None
+                }
+            })
+            .collect()
+    }
 
-        // Try envelope format (from /envelope endpoint)
-        if let Some(envelope) = Envelope::parse(data) {
-            if let Some(event_payload) = envelope.find_event_payload() {
-                return serde_json::from_slice(event_payload)
-                    .map_err(|e| DomainError::Serialization(format!("Invalid event JSON: {}", e)));
+    fn store_transaction(
+        &self,
+        conn: &mut DbConnection,
+        item: &QueueItem,
+        transaction: &SentryTransaction,
+    ) -> Result<(), DomainError> {
+        let (start_timestamp, timestamp, duration_ms) = match (
+            transaction.started_at(),
+            transaction.finished_at(),
+            transaction.duration_ms(),
+        ) {
+            (Some(start), Some(end), Some(duration)) => (start, end, duration),
+            _ => {
+                return Err(DomainError::Serialization(
+                    "Transaction without valid start_timestamp and timestamp".to_string(),
+                ));
             }
-            return Err(DomainError::Serialization(
-                "No event found in envelope".to_string(),
-            ));
+        };
+
+        let event_id = transaction
+            .event_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let trace = transaction.trace();
+
+        let name_id = self
+            .repos
+            .transaction_name
+            .get_or_create(conn, transaction.name())?;
+        let op_id =
+            self.get_or_create_unwrap(conn, &trace.and_then(|t| t.op.clone()), |conn, v| {
+                self.repos.transaction_op.get_or_create(conn, v)
+            })?;
+        let environment_id =
+            self.get_or_create_unwrap(conn, &transaction.environment, |conn, v| {
+                self.repos.environment.get_or_create(conn, v)
+            })?;
+        let release_id = match &transaction.release {
+            Some(r) => self.repos.release.touch(conn, item.project_id, r)?,
+            None => None,
+        };
+
+        let new_transaction = NewTransactionModel {
+            project_id: item.project_id,
+            event_id: event_id.clone(),
+            archive_hash: item.archive_hash.clone(),
+            name_id,
+            op_id,
+            status: trace.and_then(|t| t.status.clone()),
+            trace_id: trace.and_then(|t| t.trace_id.clone()),
+            start_timestamp,
+            timestamp,
+            duration_ms,
+            release_id,
+            environment_id,
+        };
+
+        if self
+            .repos
+            .transaction
+            .create(conn, new_transaction)?
+            .is_none()
+        {
+            info!(
+                archive_hash = %item.archive_hash,
+                event_id = %event_id,
+                "Duplicate transaction, skipping (already processed)"
+            );
         }
 
+        Ok(())
+    }
+
+    /// Error events of an envelope; empty for an envelope carrying only
+    /// transactions. Malformed event items are skipped unless none is valid.
+    fn parse_events(&self, envelope: &Envelope) -> Result<Vec<SentryReport>, DomainError> {
+        let event_payloads = envelope.find_event_payloads();
+        if !event_payloads.is_empty() {
+            let mut events = Vec::new();
+            let mut first_error = None;
+            for payload in event_payloads {
+                match serde_json::from_slice::<SentryReport>(payload) {
+                    Ok(event) => events.push(event),
+                    Err(e) => {
+                        warn!(error = %e, "Skipping invalid event JSON");
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+            return match first_error {
+                Some(e) if events.is_empty() && envelope.find_transaction_payloads().is_empty() => {
+                    Err(DomainError::Serialization(format!(
+                        "Invalid event JSON: {}",
+                        e
+                    )))
+                }
+                _ => Ok(events),
+            };
+        }
+        // A minidump alone is a crash; the event is built from the dump
+        if envelope.find_minidump().is_some() {
+            return serde_json::from_str("{}")
+                .map(|report| vec![report])
+                .map_err(|e| DomainError::Serialization(format!("Invalid event JSON: {}", e)));
+        }
+        if !envelope.find_transaction_payloads().is_empty() {
+            return Ok(Vec::new());
+        }
         Err(DomainError::Serialization(
-            "Unable to parse payload as JSON or envelope".to_string(),
+            "No event found in envelope".to_string(),
         ))
     }
 
@@ -601,13 +1083,265 @@ impl DigestReportUseCase {
             .map_err(|e| DomainError::Database(e.to_string()))?;
 
         // Record the error
-        self.repos
-            .queue_error
-            .record_error(&mut conn, &item.archive_hash, &err.to_string())?;
+        self.repos.queue_error.record_error(
+            &mut conn,
+            item.project_id,
+            &item.archive_hash,
+            &err.to_string(),
+        )?;
 
         // Remove from processing queue
-        self.repos.queue.remove(&mut conn, &item.archive_hash)?;
+        self.repos
+            .queue
+            .remove(&mut conn, item.project_id, &item.archive_hash)?;
 
         Ok(())
     }
 }
+
+const MAX_TITLE_LENGTH: usize = 200;
+/// Most recent breadcrumbs kept per event, like Sentry's default
+const MAX_BREADCRUMBS: usize = 100;
+const MAX_BREADCRUMB_MESSAGE_LENGTH: usize = 1024;
+const MAX_BREADCRUMB_URL_LENGTH: usize = 2048;
+/// Breadcrumb `data` larger than this (serialized) is truncated
+const MAX_BREADCRUMB_DATA_BYTES: usize = 2048;
+/// Key added to breadcrumb `data` that lost keys to the size cap
+const BREADCRUMB_DATA_TRUNCATED_KEY: &str = "_truncated";
+
+fn truncate_title(message: &str) -> String {
+    truncate_chars(message.trim(), MAX_TITLE_LENGTH)
+}
+
+fn truncate_chars(value: &str, max: usize) -> String {
+    match value.char_indices().nth(max) {
+        Some((end, _)) => format!("{}...", &value[..end]),
+        None => value.to_string(),
+    }
+}
+
+/// Index of the event named in the envelope header, the first event otherwise
+fn primary_event_index(envelope: &Envelope, events: &[SentryReport]) -> usize {
+    let Some(header_id) = envelope.header.event_id.as_deref().map(normalize_event_id) else {
+        return 0;
+    };
+    events
+        .iter()
+        .position(|event| {
+            event
+                .event_id
+                .as_deref()
+                .is_some_and(|id| normalize_event_id(id) == header_id)
+        })
+        .unwrap_or(0)
+}
+
+/// The session that was running when the event happened, or the envelope's
+/// only session
+fn session_for_event(sessions: &[DigestedSession], timestamp: i64) -> Option<i32> {
+    if let Some(session) = sessions
+        .iter()
+        .find(|s| s.started_at <= timestamp && timestamp <= s.timestamp)
+    {
+        return Some(session.id);
+    }
+    let first = sessions.first()?;
+    sessions
+        .iter()
+        .all(|s| s.id == first.id)
+        .then_some(first.id)
+}
+
+/// Minidump carried by an envelope, when it is one that can be read
+fn extract_minidump(envelope: &Envelope) -> Option<Minidump> {
+    let data = envelope.find_minidump()?;
+    let dump = Minidump::parse(data);
+    if dump.is_none() {
+        warn!(size = data.len(), "Minidump attachment is not a minidump");
+    }
+    dump
+}
+
+/// Fills what the SDK left out of a native crash event from its minidump:
+/// platform, level, OS, CPU architecture and an unhandled exception naming the
+/// crash reason and the module it happened in. Values sent by the SDK win.
+fn apply_minidump(report: &mut SentryReport, dump: &Minidump) {
+    report.platform.get_or_insert_with(|| "native".to_string());
+    report.level.get_or_insert_with(|| "fatal".to_string());
+
+    if let Some(system) = &dump.system {
+        let contexts = report.contexts.get_or_insert_with(|| SentryContexts {
+            device: None,
+            os: None,
+            app: None,
+            culture: None,
+            extra: Default::default(),
+        });
+        if contexts.os.is_none() {
+            contexts.os = serde_json::from_value(serde_json::json!({
+                "name": system.os_name,
+                "version": system.os_version,
+                "build": system.os_build,
+            }))
+            .ok();
+        }
+        if contexts.device.is_none() {
+            contexts.device =
+                serde_json::from_value(serde_json::json!({"archs": [system.cpu_arch]})).ok();
+        }
+    }
+
+    if report.exception_chain().is_empty()
+        && let (Some(crash), Some(reason)) = (&dump.crash, dump.crash_reason())
+    {
+        let location = match dump.module_at(crash.address) {
+            Some(module) => format!(" in {}", module.name()),
+            None => String::new(),
+        };
+        report.exception = Some(SentryException {
+            values: Some(vec![SentryExceptionValue {
+                value: Some(format!(
+                    "Fatal Error: {} at 0x{:x}{}",
+                    reason, crash.address, location
+                )),
+                exception_type: Some(reason),
+                stacktrace: None,
+                mechanism: Some(SentryMechanism {
+                    mechanism_type: Some("minidump".to_string()),
+                    handled: Some(false),
+                    extra: Default::default(),
+                }),
+                thread_id: Some(serde_json::json!(crash.thread_id)),
+            }]),
+        });
+    }
+}
+
+fn extract_modules(dump: Minidump) -> Vec<NewReportModule> {
+    dump.modules
+        .into_iter()
+        .map(|m| NewReportModule {
+            image_addr: format!("0x{:x}", m.base_address),
+            image_size: m.size as i64,
+            code_file: m.code_file,
+            code_id: m.code_id,
+            debug_file: m.debug_file,
+            debug_id: m.debug_id,
+            version: m.version,
+        })
+        .collect()
+}
+
+/// Keeps the most recent breadcrumbs, with messages, URLs and data capped
+fn extract_breadcrumbs(report: &SentryReport) -> Vec<NewReportBreadcrumb> {
+    let breadcrumbs = report.extract_breadcrumbs();
+    let skip = breadcrumbs.len().saturating_sub(MAX_BREADCRUMBS);
+    breadcrumbs
+        .into_iter()
+        .skip(skip)
+        .map(|b| NewReportBreadcrumb {
+            timestamp: b.parsed_timestamp(),
+            url: b
+                .url()
+                .map(|u| truncate_chars(u, MAX_BREADCRUMB_URL_LENGTH)),
+            breadcrumb_type: b.breadcrumb_type,
+            category: b.category,
+            level: b.level,
+            message: b
+                .message
+                .map(|m| truncate_chars(&m, MAX_BREADCRUMB_MESSAGE_LENGTH)),
+            data: b.data.and_then(truncate_breadcrumb_data),
+        })
+        .collect()
+}
+
+/// Breadcrumb data within MAX_BREADCRUMB_DATA_BYTES: larger data keeps the keys
+/// that fit and is marked with BREADCRUMB_DATA_TRUNCATED_KEY
+fn truncate_breadcrumb_data(data: serde_json::Value) -> Option<serde_json::Value> {
+    if data.is_null() {
+        return None;
+    }
+    if data.to_string().len() <= MAX_BREADCRUMB_DATA_BYTES {
+        return Some(data);
+    }
+
+    let mut truncated = serde_json::Map::new();
+    truncated.insert(BREADCRUMB_DATA_TRUNCATED_KEY.to_string(), true.into());
+    // Braces and the marker, then `"key":value,` per key kept
+    let mut used = serde_json::Value::Object(truncated.clone())
+        .to_string()
+        .len();
+    if let serde_json::Value::Object(map) = data {
+        for (key, value) in map {
+            let entry = serde_json::Value::from(key.as_str()).to_string().len()
+                + 2
+                + value.to_string().len();
+            if used + entry <= MAX_BREADCRUMB_DATA_BYTES {
+                used += entry;
+                truncated.insert(key, value);
+            }
+        }
+    }
+    Some(serde_json::Value::Object(truncated))
+}
+
+/// Replaces the variable parts of an exception message (quoted strings,
+/// numbers, hex values, UUIDs) with placeholders so similar messages group together.
+fn normalize_message(message: &str) -> String {
+    let mut normalized = String::with_capacity(message.len());
+    let mut word = String::new();
+    let mut rest = message.trim();
+
+    while let Some(c) = rest.chars().next() {
+        let after_word = normalized.ends_with(|p: char| p.is_alphanumeric()) || !word.is_empty();
+        if matches!(c, '\'' | '"' | '`')
+            && !after_word
+            && let Some(end) = rest[1..].find(c)
+        {
+            normalized.push_str("<str>");
+            rest = &rest[end + 2..];
+            continue;
+        }
+
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            word.push(c);
+        } else {
+            normalized.push_str(&normalize_word(&word));
+            word.clear();
+            normalized.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    normalized.push_str(&normalize_word(&word));
+    normalized
+}
+
+fn normalize_word(word: &str) -> String {
+    if is_uuid(word) {
+        return "<uuid>".to_string();
+    }
+    word.split('-')
+        .map(|part| {
+            let is_hex = part.len() > 2
+                && part[..2].eq_ignore_ascii_case("0x")
+                && part[2..].chars().all(|c| c.is_ascii_hexdigit());
+            if is_hex {
+                "<hex>"
+            } else if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit() || c == '_') {
+                "<num>"
+            } else {
+                part
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn is_uuid(word: &str) -> bool {
+    let groups: Vec<&str> = word.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
@@ -6,30 +6,46 @@ use axum::{
     response::IntoResponse,
     routing::{get, post},
 };
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use diesel::sql_query;
-use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use flate2::{Compression, write::GzEncoder};
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::io::{Read, Write};
+use std::io::Write;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
 use tracing::{debug, error, info, warn};
 
-use crate::shared::domain::DomainError;
-use crate::shared::parser::{Envelope, SentrySession};
-use crate::shared::persistence::db::models::NewSessionModel;
+use crate::shared::compression::ContentEncoding;
+use crate::shared::domain::{
+    CheckInStatus, DEFAULT_CHECKIN_MARGIN, DEFAULT_MAX_RUNTIME, DomainError, MonitorSchedule,
+    parse_timezone,
+};
+use crate::shared::parser::{
+    Envelope, MINIDUMP_FIELD, SentryCheckIn, SentryClientReport, SentrySession,
+    SentrySessionAggregates, SentryUserReport, minidump_envelope, multipart_boundary,
+    parse_multipart,
+};
+use crate::shared::persistence::db::models::{
+    NewBucketClientDiscardModel, NewMonitorCheckinModel, NewMonitorModel, NewSessionAggregateModel,
+    NewSessionModel, NewUserFeedbackModel,
+};
 use crate::shared::persistence::{
-    DbPool, ProjectRepository, SessionRepository, UnwrapSessionEnvironmentRepository,
-    UnwrapSessionReleaseRepository, UnwrapSessionStatusRepository,
+    AnalyticsRepository, DbPool, MonitorRepository, ProjectRepository, ReleaseRepository,
+    SessionAggregateRepository, SessionRepository, UnwrapSessionEnvironmentRepository,
+    UnwrapSessionStatusRepository, UserFeedbackRepository,
 };
 
 use super::use_case::IngestReportUseCase;
 
 /// Maps DomainError to appropriate HTTP status codes and JSON responses
-fn map_domain_error_to_response(error: &DomainError) -> (StatusCode, Json<serde_json::Value>) {
+pub(crate) fn map_domain_error_to_response(
+    error: &DomainError,
+) -> (StatusCode, Json<serde_json::Value>) {
     match error {
         DomainError::ProjectNotFound(pid) => {
             warn!(project_id = %pid, "Project not found");
@@ -73,6 +89,20 @@ fn map_domain_error_to_response(error: &DomainError) -> (StatusCode, Json<serde_
                 Json(serde_json::json!({"error": "Invalid payload compression"})),
             )
         }
+        DomainError::PayloadTooLarge(max_bytes) => {
+            warn!(max_bytes, "Payload decompresses past the limit");
+            (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(serde_json::json!({"error": error.to_string()})),
+            )
+        }
+        DomainError::NotFound(msg) => {
+            debug!(error = %msg, "Not found");
+            (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": msg})),
+            )
+        }
         DomainError::InvalidRequest(msg) => {
             warn!(error = %msg, "Invalid request");
             (
@@ -147,11 +177,17 @@ pub struct AppState {
     pub health_cache: Arc<RwLock<HealthStats>>,
     pub health_cache_ttl: Duration,
     pub max_uncompressed_payload_bytes: usize,
+    pub max_attachment_bytes: usize,
     // Session repositories
     pub session_repo: SessionRepository,
     pub session_status_repo: UnwrapSessionStatusRepository,
-    pub session_release_repo: UnwrapSessionReleaseRepository,
+    pub release_repo: ReleaseRepository,
     pub session_environment_repo: UnwrapSessionEnvironmentRepository,
+    pub session_aggregate_repo: SessionAggregateRepository,
+    // Client reports (SDK discard counts)
+    pub analytics_repo: AnalyticsRepository,
+    pub user_feedback_repo: UserFeedbackRepository,
+    pub monitor_repo: MonitorRepository,
 }
 
 /// Creates the API router (rate-limited routes)
@@ -161,6 +197,16 @@ pub fn create_api_router(state: AppState) -> Router {
         .route("/api/{project_id}/store", post(store_report))
         .route("/api/{project_id}/envelope/", post(envelope_report))
         .route("/api/{project_id}/envelope", post(envelope_report))
+        .route("/api/{project_id}/minidump/", post(minidump_report))
+        .route("/api/{project_id}/minidump", post(minidump_report))
+        .route(
+            "/api/{project_id}/user-feedback/",
+            post(user_feedback_report),
+        )
+        .route(
+            "/api/{project_id}/user-feedback",
+            post(user_feedback_report),
+        )
         .with_state(state)
 }
 
@@ -203,9 +249,15 @@ async fn store_report(
         return response;
     }
 
+    let (encoding, decoded) =
+        match decode_body(&headers, &body, state.max_uncompressed_payload_bytes) {
+            Ok(decoded) => decoded,
+            Err(response) => return response,
+        };
     let (hash, compressed, original_size) = match prepare_payload(
-        &headers,
+        encoding,
         &body,
+        &decoded,
         &state.compression_semaphore,
         state.max_uncompressed_payload_bytes,
     )
@@ -289,74 +341,215 @@ async fn envelope_report(
         return response;
     }
 
-    let (hash, compressed, original_size) = match prepare_payload(
+    // Leaves room for one attachment before the exact check in prepare_payload
+    let (encoding, decompressed) = match decode_body(
         &headers,
         &body,
-        &state.compression_semaphore,
-        state.max_uncompressed_payload_bytes,
-    )
-    .await
-    {
-        Ok(result) => result,
+        state.max_uncompressed_payload_bytes + state.max_attachment_bytes,
+    ) {
+        Ok(decoded) => decoded,
         Err(response) => return response,
     };
 
-    let decompressed = match decompress(&compressed) {
-        Ok(d) => d,
+    let envelope = match Envelope::parse(&decompressed) {
+        Ok(e) => e,
         Err(e) => {
-            error!(error = %e, "Failed to decompress for parsing");
+            warn!(project_id = %project_id, error = %e, "Failed to parse envelope format");
             return (
                 StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"error": "Invalid gzip payload"})),
+                Json(serde_json::json!({"error": format!("Invalid envelope: {}", e)})),
             );
         }
     };
 
-    let envelope = match Envelope::parse(&decompressed) {
-        Some(e) => e,
-        None => {
-            warn!("Failed to parse envelope format");
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"error": "Invalid envelope format"})),
-            );
-        }
+    if let Some(item) = envelope
+        .find_attachments()
+        .into_iter()
+        .find(|item| item.payload.len() > state.max_attachment_bytes)
+    {
+        warn!(
+            project_id = %project_id,
+            filename = ?item.header.filename(),
+            size = item.payload.len(),
+            "Attachment too large"
+        );
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({
+                "error": format!(
+                    "Attachment too large: {} bytes (max {})",
+                    item.payload.len(),
+                    state.max_attachment_bytes
+                )
+            })),
+        );
+    }
+
+    // Attachments have their own limit and do not count against the payload limit
+    let (hash, compressed, original_size) = match prepare_payload(
+        encoding,
+        &body,
+        &decompressed,
+        &state.compression_semaphore,
+        state.max_uncompressed_payload_bytes + attachment_bytes(&envelope),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(response) => return response,
     };
 
-    let has_event = envelope.find_event_payload().is_some();
+    // SDKs attach client reports to any envelope; they are counted at ingest, never
+    // digested, and only once per envelope body
+    let client_reports: Vec<SentryClientReport> = envelope
+        .find_client_report_payloads()
+        .into_iter()
+        .filter_map(SentryClientReport::parse)
+        .collect();
 
-    if !has_event {
-        let session_payloads = envelope.find_session_payloads();
-        let mut sessions_stored = 0;
-        let mut first_error: Option<DomainError> = None;
-
-        for session_data in session_payloads {
-            if let Some(session) = SentrySession::parse(session_data) {
-                match store_session(&state, &mut conn, project_id, &session) {
-                    Ok(_sid_id) => {
-                        sessions_stored += 1;
+    let mut first_error: Option<DomainError> = None;
+
+    // User feedback is stored here and linked to its event once that is digested
+    let mut feedback_stored = 0;
+    let feedback = envelope
+        .find_user_report_payloads()
+        .into_iter()
+        .filter_map(SentryUserReport::parse)
+        .chain(
+            envelope
+                .find_feedback_payloads()
+                .into_iter()
+                .filter_map(SentryUserReport::parse_feedback),
+        );
+    for report in feedback {
+        match store_user_feedback(&state, &mut conn, project_id, report) {
+            Ok(_id) => {
+                feedback_stored += 1;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to store user feedback");
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+            }
+        }
+    }
+
+    // Cron check-ins are stored here like sessions, never archived
+    let mut check_ins_stored = 0;
+    for check_in_data in envelope.find_check_in_payloads() {
+        let Some(check_in) = SentryCheckIn::parse(check_in_data) else {
+            warn!("Invalid check-in");
+            continue;
+        };
+        match store_check_in(&state, &mut conn, project_id, &check_in) {
+            Ok(_id) => {
+                check_ins_stored += 1;
+            }
+            Err(e) => {
+                warn!(error = %e, monitor = %check_in.monitor_slug, "Failed to store check-in");
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+            }
+        }
+    }
+
+    // Every session update is applied here, even next to events; the digest
+    // only looks the sessions up to link reports to them
+    let mut sessions_stored = 0;
+    for session_data in envelope.find_session_payloads() {
+        if let Some(session) = SentrySession::parse(session_data) {
+            match store_session(&state, &mut conn, project_id, &session) {
+                Ok(_sid_id) => {
+                    sessions_stored += 1;
+                }
+                Err(e) => {
+                    warn!(error = %e, sid = %session.sid, "Failed to store session");
+                    if first_error.is_none() {
+                        first_error = Some(e);
                     }
-                    Err(e) => {
-                        warn!(error = %e, sid = %session.sid, "Failed to store session");
-                        if first_error.is_none() {
-                            first_error = Some(e);
-                        }
+                }
+            }
+        }
+    }
+
+    // Aggregates are only stored here, the digest skips them. A retry is
+    // recognized by its body hash, even when the envelope carries an event.
+    let aggregates: Vec<SentrySessionAggregates> = envelope
+        .find_session_aggregate_payloads()
+        .into_iter()
+        .filter_map(SentrySessionAggregates::parse)
+        .collect();
+    let mut aggregates_stored = 0;
+    let mut aggregates_accepted = false;
+    if !aggregates.is_empty() {
+        match store_session_aggregates(&state, &mut conn, project_id, &hash, &aggregates) {
+            Ok(stored) => {
+                aggregates_stored = stored;
+                aggregates_accepted = true;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to store session aggregates");
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+            }
+        }
+    }
+
+    // Transactions and native crashes are archived and digested like error events
+    let has_event = !envelope.find_event_payloads().is_empty()
+        || !envelope.find_transaction_payloads().is_empty()
+        || envelope.find_minidump().is_some();
+
+    if !has_event {
+        // Not archived, so a retry is recognized by its body hash
+        let mut client_reports_stored = 0;
+        let mut client_reports_accepted = false;
+        if !client_reports.is_empty() {
+            match store_client_reports(&state, &mut conn, project_id, Some(&hash), &client_reports)
+            {
+                Ok(stored) => {
+                    client_reports_stored = stored;
+                    client_reports_accepted = true;
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to store client reports");
+                    if first_error.is_none() {
+                        first_error = Some(e);
                     }
                 }
             }
         }
 
-        if sessions_stored > 0 {
+        // A retried client report or aggregate is acknowledged without being counted again
+        if sessions_stored > 0
+            || aggregates_accepted
+            || client_reports_accepted
+            || feedback_stored > 0
+            || check_ins_stored > 0
+        {
             info!(
                 project_id = %project_id,
                 sessions_stored,
+                aggregates_stored,
+                client_reports_stored,
+                feedback_stored,
+                check_ins_stored,
                 payload_size,
                 duration_ms = start.elapsed().as_millis(),
                 "Envelope session OK"
             );
             return (
                 StatusCode::OK,
-                Json(serde_json::json!({"sessions": sessions_stored})),
+                Json(serde_json::json!({
+                    "sessions": sessions_stored,
+                    "aggregates": aggregates_stored,
+                    "client_reports": client_reports_stored,
+                    "feedback": feedback_stored,
+                    "check_ins": check_ins_stored,
+                })),
             );
         }
 
@@ -382,7 +575,9 @@ async fn envelope_report(
         );
         return (
             StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "No event or session in envelope"})),
+            Json(
+                serde_json::json!({"error": "No event, transaction, session or check-in in envelope"}),
+            ),
         );
     }
 
@@ -403,6 +598,12 @@ async fn envelope_report(
                     "Envelope DUP"
                 );
             } else {
+                // The archive dedup already rejected a retry of this envelope
+                if let Err(e) =
+                    store_client_reports(&state, &mut conn, project_id, None, &client_reports)
+                {
+                    warn!(error = %e, "Failed to store client reports");
+                }
                 info!(
                     project_id = %project_id,
                     payload_size,
@@ -428,6 +629,273 @@ async fn envelope_report(
     }
 }
 
+/// Native crash upload (Breakpad, Crashpad, sentry-native): a multipart form with
+/// the minidump and optional event fields. It is archived as an envelope so the
+/// digest handles it like any other event with attachments.
+async fn minidump_report(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<SentryQueryParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let start = std::time::Instant::now();
+    let payload_size = body.len();
+
+    let mut conn = match state.pool.get() {
+        Ok(c) => c,
+        Err(e) => {
+            error!(error = %e, "Failed to get DB connection");
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": "Service temporarily unavailable"})),
+            );
+        }
+    };
+
+    let sentry_key = extract_sentry_key(&headers, &query);
+    if let Err(response) = validate_project_key(
+        &state.project_repo,
+        &state.project_cache,
+        &mut conn,
+        project_id,
+        sentry_key,
+    ) {
+        return response;
+    }
+
+    let Some(boundary) = headers
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .and_then(multipart_boundary)
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Expected multipart/form-data with a boundary"})),
+        );
+    };
+
+    let form = match decode_body(
+        &headers,
+        &body,
+        state.max_uncompressed_payload_bytes + state.max_attachment_bytes,
+    ) {
+        Ok((_, form)) => form,
+        Err(response) => return response,
+    };
+
+    let Some(parts) = parse_multipart(&form, boundary) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid multipart body"})),
+        );
+    };
+    let Some(envelope) = minidump_envelope(parts) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("No {} part", MINIDUMP_FIELD)})),
+        );
+    };
+
+    if let Some(item) = envelope
+        .find_attachments()
+        .into_iter()
+        .find(|item| item.payload.len() > state.max_attachment_bytes)
+    {
+        warn!(
+            project_id = %project_id,
+            filename = ?item.header.filename(),
+            size = item.payload.len(),
+            "Attachment too large"
+        );
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({
+                "error": format!(
+                    "Attachment too large: {} bytes (max {})",
+                    item.payload.len(),
+                    state.max_attachment_bytes
+                )
+            })),
+        );
+    }
+
+    let attachment_bytes = attachment_bytes(&envelope);
+    let envelope_bytes = envelope.to_bytes();
+    let (hash, compressed, original_size) = match prepare_payload(
+        ContentEncoding::Identity,
+        &envelope_bytes,
+        &envelope_bytes,
+        &state.compression_semaphore,
+        state.max_uncompressed_payload_bytes + attachment_bytes,
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(response) => return response,
+    };
+
+    match state.ingest_use_case.execute(
+        &mut conn,
+        project_id,
+        hash.clone(),
+        compressed,
+        original_size,
+    ) {
+        Ok(result) => {
+            info!(
+                project_id = %project_id,
+                payload_size,
+                duration_ms = start.elapsed().as_millis(),
+                hash = %result.hash,
+                duplicate = result.duplicate,
+                "Minidump OK"
+            );
+            (StatusCode::OK, Json(serde_json::json!({"id": result.hash})))
+        }
+        Err(e) => {
+            let response = map_domain_error_to_response(&e);
+            warn!(
+                project_id = %project_id,
+                payload_size,
+                status = response.0.as_u16(),
+                duration_ms = start.elapsed().as_millis(),
+                error = ?e,
+                "Minidump FAIL"
+            );
+            response
+        }
+    }
+}
+
+async fn user_feedback_report(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<SentryQueryParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let mut conn = match state.pool.get() {
+        Ok(c) => c,
+        Err(e) => {
+            error!(error = %e, "Failed to get DB connection");
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": "Service temporarily unavailable"})),
+            );
+        }
+    };
+
+    let sentry_key = extract_sentry_key(&headers, &query);
+    if let Err(response) = validate_project_key(
+        &state.project_repo,
+        &state.project_cache,
+        &mut conn,
+        project_id,
+        sentry_key,
+    ) {
+        return response;
+    }
+
+    let Some(report) = SentryUserReport::parse(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid user feedback, comments are required"})),
+        );
+    };
+
+    match store_user_feedback(&state, &mut conn, project_id, report) {
+        Ok(id) => {
+            info!(project_id = %project_id, feedback_id = id, "User feedback OK");
+            (StatusCode::OK, Json(serde_json::json!({"id": id})))
+        }
+        Err(e) => map_domain_error_to_response(&e),
+    }
+}
+
+/// Stores a cron check-in and returns its id. The monitor is created or its
+/// schedule updated when the check-in carries a monitor config; check-ins for
+/// an unknown monitor without one are rejected.
+fn store_check_in(
+    state: &AppState,
+    conn: &mut crate::shared::persistence::DbConnection,
+    project_id: i32,
+    check_in: &SentryCheckIn,
+) -> Result<i32, DomainError> {
+    let status = CheckInStatus::from_sdk(&check_in.status).ok_or_else(|| {
+        DomainError::InvalidRequest(format!("Invalid check-in status '{}'", check_in.status))
+    })?;
+
+    let monitor = match &check_in.monitor_config {
+        Some(config) => {
+            let value = config.schedule.value_text().unwrap_or_default();
+            let schedule = MonitorSchedule::parse(
+                &config.schedule.schedule_type,
+                &value,
+                config.schedule.unit.as_deref(),
+            )?;
+            let timezone = config
+                .timezone
+                .clone()
+                .filter(|tz| parse_timezone(tz).is_some())
+                .unwrap_or_else(|| "UTC".to_string());
+            state.monitor_repo.upsert(
+                conn,
+                NewMonitorModel {
+                    project_id,
+                    slug: check_in.monitor_slug.clone(),
+                    schedule_type: schedule.schedule_type().to_string(),
+                    schedule: value,
+                    schedule_unit: schedule.unit().map(str::to_string),
+                    checkin_margin: config
+                        .checkin_margin
+                        .unwrap_or(DEFAULT_CHECKIN_MARGIN)
+                        .max(0),
+                    max_runtime: config.max_runtime.unwrap_or(DEFAULT_MAX_RUNTIME).max(1),
+                    timezone,
+                },
+            )?
+        }
+        None => state
+            .monitor_repo
+            .find_by_slug(conn, project_id, &check_in.monitor_slug)?
+            .ok_or_else(|| {
+                DomainError::InvalidRequest(format!(
+                    "Unknown monitor '{}' and no monitor_config",
+                    check_in.monitor_slug
+                ))
+            })?,
+    };
+
+    let schedule = MonitorSchedule::parse(
+        &monitor.schedule_type,
+        &monitor.schedule,
+        monitor.schedule_unit.as_deref(),
+    )?;
+    let now = Utc::now().naive_utc();
+
+    let recorded = state.monitor_repo.record_check_in(
+        conn,
+        NewMonitorCheckinModel {
+            monitor_id: monitor.id,
+            check_in_id: check_in
+                .check_in_id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string()),
+            status: status.as_str().to_string(),
+            duration_ms: check_in.duration.map(|secs| secs * 1000.0),
+            release: check_in.release.clone(),
+            environment: check_in.environment.clone(),
+            expected_at: monitor.next_checkin_at,
+            received_at: now,
+            updated_at: now,
+        },
+        schedule.next_after(now, &monitor.timezone),
+    )?;
+
+    Ok(recorded.id)
+}
+
 /// Stores a session and returns the session_id for linking with reports
 fn store_session(
     state: &AppState,
@@ -435,6 +903,10 @@ fn store_session(
     project_id: i32,
     session: &SentrySession,
 ) -> Result<i32, DomainError> {
+    let started_at = session.started_at().ok_or_else(|| {
+        DomainError::InvalidRequest(format!("Invalid session start '{}'", session.started))
+    })?;
+
     // Get or create status ID
     let status_id = state
         .session_status_repo
@@ -442,7 +914,7 @@ fn store_session(
 
     // Get or create release ID (optional)
     let release_id = match &session.attrs.release {
-        Some(r) => Some(state.session_release_repo.get_or_create(conn, r)?),
+        Some(r) => state.release_repo.touch(conn, project_id, r)?,
         None => None,
     };
 
@@ -456,15 +928,14 @@ fn store_session(
         project_id,
         sid: session.sid.clone(),
         init: if session.init { 1 } else { 0 },
-        started_at: session.started.clone(),
-        timestamp: session
-            .timestamp
-            .clone()
-            .unwrap_or_else(|| session.started.clone()),
+        started_at,
+        timestamp: session.updated_at().unwrap_or(started_at),
         errors: session.errors,
         status_id,
         release_id,
         environment_id,
+        distinct_id: session.did.clone(),
+        duration: session.duration,
     };
 
     let session_id = state.session_repo.upsert(conn, new_session)?;
@@ -472,31 +943,234 @@ fn store_session(
     Ok(session_id)
 }
 
-async fn prepare_payload(
+/// Stores the buckets of an envelope's `sessions` items in one transaction and
+/// returns how many were stored. They are stored once per envelope body `hash`,
+/// so a retried envelope is acknowledged without adding them again.
+fn store_session_aggregates(
+    state: &AppState,
+    conn: &mut crate::shared::persistence::DbConnection,
+    project_id: i32,
+    hash: &str,
+    aggregates: &[SentrySessionAggregates],
+) -> Result<usize, DomainError> {
+    let mut failure = None;
+    conn.transaction(|conn| {
+        let mut store = || {
+            if !state
+                .session_aggregate_repo
+                .record_receipt(conn, project_id, hash)?
+            {
+                return Ok(0);
+            }
+            let mut stored = 0;
+            for item in aggregates {
+                stored += store_session_aggregate_item(state, conn, project_id, item)?;
+            }
+            Ok(stored)
+        };
+        store().map_err(|e: DomainError| {
+            failure = Some(e);
+            diesel::result::Error::RollbackTransaction
+        })
+    })
+    .map_err(|e| failure.unwrap_or_else(|| DomainError::Database(e.to_string())))
+}
+
+/// Stores the buckets of one `sessions` item and returns how many were stored.
+/// Buckets whose `started` is not an RFC 3339 timestamp are skipped.
+fn store_session_aggregate_item(
+    state: &AppState,
+    conn: &mut crate::shared::persistence::DbConnection,
+    project_id: i32,
+    aggregates: &SentrySessionAggregates,
+) -> Result<usize, DomainError> {
+    let release_id = match &aggregates.attrs.release {
+        Some(r) => state.release_repo.touch(conn, project_id, r)?,
+        None => None,
+    };
+
+    let environment_id = match &aggregates.attrs.environment {
+        Some(env) => Some(state.session_environment_repo.get_or_create(conn, env)?),
+        None => None,
+    };
+
+    let rows: Vec<NewSessionAggregateModel> = aggregates
+        .aggregates
+        .iter()
+        .filter_map(|bucket| {
+            let started_at = match DateTime::parse_from_rfc3339(&bucket.started) {
+                Ok(dt) => dt.naive_utc(),
+                Err(_) => {
+                    warn!(started = %bucket.started, "Skipping aggregate with invalid start");
+                    return None;
+                }
+            };
+            Some(NewSessionAggregateModel {
+                project_id,
+                started_at,
+                release_id,
+                environment_id,
+                distinct_id: bucket.did.clone(),
+                exited: bucket.exited,
+                errored: bucket.errored,
+                abnormal: bucket.abnormal,
+                crashed: bucket.crashed,
+            })
+        })
+        .collect();
+
+    state.session_aggregate_repo.create_many(conn, &rows)
+}
+
+fn store_user_feedback(
+    state: &AppState,
+    conn: &mut crate::shared::persistence::DbConnection,
+    project_id: i32,
+    report: SentryUserReport,
+) -> Result<i32, DomainError> {
+    state.user_feedback_repo.upsert(
+        conn,
+        NewUserFeedbackModel {
+            project_id,
+            event_id: report.event_id,
+            name: report.name,
+            email: report.email,
+            comments: report.comments,
+            report_id: None,
+            issue_id: None,
+        },
+    )
+}
+
+/// Adds the discards of an envelope's client reports to their minute buckets.
+/// With a `receipt` hash they are counted once per envelope body, so a retried
+/// envelope is acknowledged without counting them again. Returns the number of
+/// reports counted.
+fn store_client_reports(
+    state: &AppState,
+    conn: &mut crate::shared::persistence::DbConnection,
+    project_id: i32,
+    receipt: Option<&str>,
+    reports: &[SentryClientReport],
+) -> Result<usize, DomainError> {
+    conn.transaction(|conn| {
+        if let Some(hash) = receipt
+            && !state
+                .analytics_repo
+                .record_client_report_receipt(conn, project_id, hash)?
+        {
+            return Ok(0);
+        }
+        for report in reports {
+            state
+                .analytics_repo
+                .record_client_discards(conn, &client_discard_rows(project_id, report))?;
+        }
+        Ok(reports.len())
+    })
+    .map_err(|e: diesel::result::Error| DomainError::Database(e.to_string()))
+}
+
+/// Discard counts of a `client_report` item, in the minute bucket of its
+/// timestamp. Reports without a valid timestamp, or from the future, count now.
+fn client_discard_rows(
+    project_id: i32,
+    report: &SentryClientReport,
+) -> Vec<NewBucketClientDiscardModel> {
+    let now = Utc::now().naive_utc();
+    let created_at = report.created_at().filter(|at| *at <= now).unwrap_or(now);
+    let bucket_start = AnalyticsRepository::minute_start(created_at);
+
+    report
+        .discarded_events
+        .iter()
+        .filter(|discarded| discarded.quantity > 0)
+        .map(|discarded| NewBucketClientDiscardModel {
+            project_id,
+            bucket_start,
+            reason: discarded.reason.clone(),
+            category: discarded.category.clone(),
+            quantity: discarded.quantity,
+        })
+        .collect()
+}
+
+/// A request body and the Content-Encoding it was sent with
+type DecodedBody<'a> = (ContentEncoding, Cow<'a, [u8]>);
+
+/// Returns the request body with its Content-Encoding undone, refusing with
+/// 413 a body that decompresses past `max_size` and with 415 an encoding
+/// this server cannot decode
+fn decode_body<'a>(
     headers: &HeaderMap,
-    body: &[u8],
-    semaphore: &Semaphore,
+    body: &'a [u8],
     max_size: usize,
-) -> Result<(String, Vec<u8>, Option<i32>), (StatusCode, Json<serde_json::Value>)> {
-    let is_gzip = headers
+) -> Result<DecodedBody<'a>, (StatusCode, Json<serde_json::Value>)> {
+    let value = headers
         .get("content-encoding")
         .and_then(|v| v.to_str().ok())
-        .map(|v| v.contains("gzip"))
-        .unwrap_or(false);
+        .unwrap_or_default();
+    let Some(encoding) = ContentEncoding::parse(value) else {
+        warn!(content_encoding = %value, "Unsupported content encoding");
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(serde_json::json!({
+                "error": format!("Unsupported Content-Encoding: {}", value)
+            })),
+        ));
+    };
+    if encoding == ContentEncoding::Identity {
+        return Ok((encoding, Cow::Borrowed(body)));
+    }
 
-    if is_gzip {
-        let hash = compute_hash(body);
-        Ok((hash, body.to_vec(), None))
-    } else {
-        if body.len() > max_size {
-            return Err((
+    match encoding.decompress_bounded(body, max_size) {
+        Ok(decompressed) => Ok((encoding, Cow::Owned(decompressed))),
+        Err(e @ DomainError::PayloadTooLarge(_)) => {
+            warn!(
+                content_encoding = encoding.as_str(),
+                compressed_size = body.len(),
+                max_size,
+                "Compressed body past the size limit"
+            );
+            Err((
                 StatusCode::PAYLOAD_TOO_LARGE,
+                Json(serde_json::json!({"error": e.to_string()})),
+            ))
+        }
+        Err(e) => {
+            warn!(content_encoding = encoding.as_str(), error = %e, "Failed to decompress request body");
+            Err((
+                StatusCode::BAD_REQUEST,
                 Json(serde_json::json!({
-                    "error": format!("Payload too large: {} bytes (max {})", body.len(), max_size)
+                    "error": format!("Invalid {} payload", encoding.as_str())
                 })),
-            ));
+            ))
         }
+    }
+}
 
+/// Checks the decoded size against `max_size` and returns the payload to
+/// archive: gzip bodies are kept as sent, others are gzip-compressed here
+async fn prepare_payload(
+    encoding: ContentEncoding,
+    body: &[u8],
+    decoded: &[u8],
+    semaphore: &Semaphore,
+    max_size: usize,
+) -> Result<(String, Vec<u8>, Option<i32>), (StatusCode, Json<serde_json::Value>)> {
+    if decoded.len() > max_size {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({
+                "error": format!("Payload too large: {} bytes (max {})", decoded.len(), max_size)
+            })),
+        ));
+    }
+
+    if encoding == ContentEncoding::Gzip {
+        let hash = compute_hash(body);
+        Ok((hash, body.to_vec(), Some(decoded.len() as i32)))
+    } else {
         let permit = semaphore.try_acquire();
         if permit.is_err() {
             warn!("Compression semaphore exhausted - service overloaded");
@@ -506,9 +1180,9 @@ async fn prepare_payload(
             ));
         }
 
-        let original_size = body.len() as i32;
-        let body_clone = body.to_vec();
-        let compressed = tokio::task::spawn_blocking(move || compress(&body_clone))
+        let original_size = decoded.len() as i32;
+        let decoded = decoded.to_vec();
+        let compressed = tokio::task::spawn_blocking(move || compress(&decoded))
             .await
             .map_err(|e| {
                 (
@@ -528,6 +1202,15 @@ async fn prepare_payload(
     }
 }
 
+/// Total size of the attachment items of an envelope
+fn attachment_bytes(envelope: &Envelope) -> usize {
+    envelope
+        .find_attachments()
+        .iter()
+        .map(|item| item.payload.len())
+        .sum()
+}
+
 fn compute_hash(data: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(data);
@@ -540,13 +1223,6 @@ fn compress(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
     encoder.finish()
 }
 
-fn decompress(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
-    let mut decoder = GzDecoder::new(data);
-    let mut decompressed = Vec::new();
-    decoder.read_to_end(&mut decompressed)?;
-    Ok(decompressed)
-}
-
 /// Extracts sentry_key from X-Sentry-Auth header or query params.
 /// Header format: "Sentry sentry_key=abc123, sentry_version=7, ..."
 fn extract_sentry_key(headers: &HeaderMap, query: &SentryQueryParams) -> Option<String> {
@@ -647,13 +1323,15 @@ pub fn compute_health_stats(conn: &mut crate::shared::persistence::DbConnection)
         })
         .unwrap_or(0);
 
-    let reports = sql_query("SELECT COUNT(*) as c FROM report")
-        .get_result::<Count>(conn)
-        .map(|r| r.c)
-        .map_err(|e| {
-            warn!(error = %e, "Failed to query report count");
-        })
-        .unwrap_or(0);
+    // Archives digest into either a report or a transaction
+    let reports =
+        sql_query("SELECT (SELECT COUNT(*) FROM report) + (SELECT COUNT(*) FROM transaction) as c")
+            .get_result::<Count>(conn)
+            .map(|r| r.c)
+            .map_err(|e| {
+                warn!(error = %e, "Failed to query report count");
+            })
+            .unwrap_or(0);
 
     let queue = sql_query("SELECT COUNT(*) as c FROM queue")
         .get_result::<Count>(conn)
@@ -672,7 +1350,7 @@ pub fn compute_health_stats(conn: &mut crate::shared::persistence::DbConnection)
         .unwrap_or(0);
 
     // Calculate orphaned instead of querying (much faster!)
-    // Orphaned = archives not in reports, queue, or queue_error
+    // Orphaned = archives not in reports, transactions, queue, or queue_error
     let orphaned = archives - reports - queue - regurgitated;
 
     HealthStats {
@@ -9,3 +9,5 @@ pub use handler::{
     create_health_router,
 };
 pub use use_case::IngestReportUseCase;
+
+pub(crate) use handler::map_domain_error_to_response;
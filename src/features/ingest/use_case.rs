@@ -40,13 +40,13 @@ impl IngestReportUseCase {
             return Err(DomainError::ProjectNotFound(project_id));
         }
 
-        let archive_exists = self.archive_repo.exists(conn, &hash)?;
+        let archive_exists = self.archive_repo.exists(conn, project_id, &hash)?;
 
         if !archive_exists {
             let archive = Archive::new(hash.clone(), project_id, compressed_payload, original_size);
             self.archive_repo.save(conn, &archive)?;
 
-            let queue_item = QueueItem::new(hash.clone());
+            let queue_item = QueueItem::new(project_id, hash.clone());
             self.queue_repo.enqueue(conn, &queue_item)?;
         }
 
@@ -1,4 +1,10 @@
+pub mod api;
 pub mod cli;
 pub mod digest;
 pub mod ingest;
+pub mod monitors;
+pub mod performance;
+pub mod release_health;
 pub mod serve;
+#[cfg(test)]
+mod test_support;
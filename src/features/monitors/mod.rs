@@ -0,0 +1,5 @@
+mod use_case;
+mod worker;
+
+pub use use_case::{CheckMonitorsUseCase, MonitorCheckSummary};
+pub use worker::MonitorWorker;
@@ -0,0 +1,97 @@
+use chrono::{Duration, NaiveDateTime};
+use tracing::warn;
+
+use crate::shared::domain::{DomainError, MonitorSchedule};
+use crate::shared::persistence::{DbPool, MonitorRepository};
+
+/// Missed runs recorded for one monitor per check; after a longer outage the
+/// monitor skips ahead to its next run instead
+const MAX_MISSED_PER_CHECK: usize = 100;
+
+/// Records the runs cron monitors missed and the check-ins that stayed in
+/// progress longer than their monitor's max runtime.
+#[derive(Clone)]
+pub struct CheckMonitorsUseCase {
+    repo: MonitorRepository,
+    pool: DbPool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MonitorCheckSummary {
+    pub missed: usize,
+    pub timed_out: usize,
+}
+
+impl CheckMonitorsUseCase {
+    pub fn new(repo: MonitorRepository, pool: DbPool) -> Self {
+        Self { repo, pool }
+    }
+
+    pub fn execute(&self, now: NaiveDateTime) -> Result<MonitorCheckSummary, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+
+        let timed_out = self.repo.time_out_check_ins(&mut conn, now)?;
+
+        let mut missed = 0;
+        for monitor in self.repo.find_overdue(&mut conn, now)? {
+            let Some(overdue) = monitor.next_checkin_at else {
+                continue;
+            };
+            let schedule = match MonitorSchedule::parse(
+                &monitor.schedule_type,
+                &monitor.schedule,
+                monitor.schedule_unit.as_deref(),
+            ) {
+                Ok(schedule) => schedule,
+                Err(e) => {
+                    warn!(monitor = %monitor.slug, error = %e, "Invalid monitor schedule");
+                    continue;
+                }
+            };
+
+            let margin = Duration::minutes(monitor.checkin_margin.max(0).into());
+            let (runs, next_checkin_at) =
+                missed_runs(&schedule, &monitor.timezone, overdue, margin, now);
+            if runs.is_empty() {
+                continue;
+            }
+            missed += self.repo.record_missed(
+                &mut conn,
+                monitor.id,
+                overdue,
+                &runs,
+                next_checkin_at,
+                now,
+            )?;
+        }
+
+        Ok(MonitorCheckSummary { missed, timed_out })
+    }
+}
+
+/// Expected runs from `first` on whose margin has passed at `now`, and the
+/// next run still to come
+fn missed_runs(
+    schedule: &MonitorSchedule,
+    timezone: &str,
+    first: NaiveDateTime,
+    margin: Duration,
+    now: NaiveDateTime,
+) -> (Vec<NaiveDateTime>, Option<NaiveDateTime>) {
+    let mut missed = Vec::new();
+    let mut expected = Some(first);
+    while let Some(at) = expected {
+        if at + margin > now {
+            break;
+        }
+        if missed.len() == MAX_MISSED_PER_CHECK {
+            return (missed, schedule.next_after(now, timezone));
+        }
+        missed.push(at);
+        expected = schedule.next_after(at, timezone);
+    }
+    (missed, expected)
+}
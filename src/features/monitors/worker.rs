@@ -0,0 +1,32 @@
+use chrono::NaiveDateTime;
+use std::time::Duration;
+use tracing::{debug, info};
+
+use super::{CheckMonitorsUseCase, MonitorCheckSummary};
+use crate::shared::domain::DomainError;
+use crate::shared::worker::{PeriodicJob, PeriodicWorker};
+
+pub type MonitorWorker = PeriodicWorker<CheckMonitorsUseCase>;
+
+impl PeriodicJob for CheckMonitorsUseCase {
+    type Output = MonitorCheckSummary;
+
+    const NAME: &'static str = "monitors";
+
+    fn execute(&self, now: NaiveDateTime) -> Result<MonitorCheckSummary, DomainError> {
+        CheckMonitorsUseCase::execute(self, now)
+    }
+
+    fn log_success(summary: &MonitorCheckSummary, elapsed: Duration) {
+        if summary.missed > 0 || summary.timed_out > 0 {
+            info!(
+                missed = summary.missed,
+                timed_out = summary.timed_out,
+                elapsed_ms = elapsed.as_millis(),
+                "Monitor check found failed runs"
+            );
+        } else {
+            debug!(elapsed_ms = elapsed.as_millis(), "Monitor check complete");
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod use_case;
+mod worker;
+
+pub use use_case::RollupTransactionLatencyUseCase;
+pub use worker::TransactionLatencyWorker;
@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+
+use crate::features::release_health::window_start;
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::TransactionLatencyRepository;
+
+/// Recomputes the hourly transaction latency buckets of the last hours.
+///
+/// Percentiles cannot be merged incrementally, so each run rebuilds every
+/// bucket of the lookback window, picking up transactions digested late.
+#[derive(Clone)]
+pub struct RollupTransactionLatencyUseCase {
+    repo: TransactionLatencyRepository,
+    lookback_hours: i64,
+}
+
+impl RollupTransactionLatencyUseCase {
+    pub fn new(repo: TransactionLatencyRepository, lookback_hours: i64) -> Self {
+        Self {
+            repo,
+            lookback_hours,
+        }
+    }
+
+    /// Returns the number of buckets written
+    pub fn execute(&self, now: NaiveDateTime) -> Result<usize, DomainError> {
+        self.repo
+            .refresh_since(window_start(now, self.lookback_hours))
+    }
+}
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use std::time::Duration;
+use tracing::debug;
+
+use super::RollupTransactionLatencyUseCase;
+use crate::shared::domain::DomainError;
+use crate::shared::worker::{PeriodicJob, PeriodicWorker};
+
+pub type TransactionLatencyWorker = PeriodicWorker<RollupTransactionLatencyUseCase>;
+
+impl PeriodicJob for RollupTransactionLatencyUseCase {
+    type Output = usize;
+
+    const NAME: &'static str = "transaction_latency";
+
+    fn execute(&self, now: NaiveDateTime) -> Result<usize, DomainError> {
+        RollupTransactionLatencyUseCase::execute(self, now)
+    }
+
+    fn log_success(buckets: &usize, elapsed: Duration) {
+        debug!(
+            buckets = buckets,
+            elapsed_ms = elapsed.as_millis(),
+            "Transaction latency rollup complete"
+        );
+    }
+}
@@ -0,0 +1,6 @@
+mod use_case;
+mod worker;
+
+pub use use_case::RollupReleaseHealthUseCase;
+pub(crate) use use_case::window_start;
+pub use worker::ReleaseHealthWorker;
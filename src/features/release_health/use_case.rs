@@ -0,0 +1,39 @@
+use chrono::{Duration, NaiveDateTime, Timelike};
+
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::ReleaseHealthRepository;
+
+/// Recomputes the hourly release health buckets of the last hours.
+///
+/// Sessions keep being updated after they start (status, errors), so each run
+/// rebuilds every bucket of the lookback window instead of appending.
+#[derive(Clone)]
+pub struct RollupReleaseHealthUseCase {
+    repo: ReleaseHealthRepository,
+    lookback_hours: i64,
+}
+
+impl RollupReleaseHealthUseCase {
+    pub fn new(repo: ReleaseHealthRepository, lookback_hours: i64) -> Self {
+        Self {
+            repo,
+            lookback_hours,
+        }
+    }
+
+    /// Returns the number of buckets written
+    pub fn execute(&self, now: NaiveDateTime) -> Result<usize, DomainError> {
+        self.repo
+            .refresh_since(window_start(now, self.lookback_hours))
+    }
+}
+
+/// Start of the hour `lookback_hours` before `now`
+pub(crate) fn window_start(now: NaiveDateTime, lookback_hours: i64) -> NaiveDateTime {
+    let start = now - Duration::hours(lookback_hours.max(1));
+    start
+        .with_minute(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(start)
+}
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use std::time::Duration;
+use tracing::debug;
+
+use super::RollupReleaseHealthUseCase;
+use crate::shared::domain::DomainError;
+use crate::shared::worker::{PeriodicJob, PeriodicWorker};
+
+pub type ReleaseHealthWorker = PeriodicWorker<RollupReleaseHealthUseCase>;
+
+impl PeriodicJob for RollupReleaseHealthUseCase {
+    type Output = usize;
+
+    const NAME: &'static str = "release_health";
+
+    fn execute(&self, now: NaiveDateTime) -> Result<usize, DomainError> {
+        RollupReleaseHealthUseCase::execute(self, now)
+    }
+
+    fn log_success(buckets: &usize, elapsed: Duration) {
+        debug!(
+            buckets = buckets,
+            elapsed_ms = elapsed.as_millis(),
+            "Release health rollup complete"
+        );
+    }
+}
@@ -10,12 +10,22 @@ use tracing::{Level, info};
 use tracing_subscriber::FmtSubscriber;
 
 use crate::config::Settings;
+use crate::features::api::{
+    ApiState, GetEventAttachmentUseCase, GetEventUseCase, GetIssueTagsUseCase,
+    GetReleaseHealthUseCase, GetTransactionLatencyUseCase, ListEventAttachmentsUseCase,
+    ListEventBreadcrumbsUseCase, ListIssueFeedbackUseCase, ListIssuesUseCase,
+    ListMonitorCheckInsUseCase, ListMonitorsUseCase, SearchEventsUseCase, UpdateIssueStatusUseCase,
+    create_rest_api_router,
+};
 use crate::features::digest::{DigestReportUseCase, DigestWorker};
 use crate::features::ingest::{
     AppState, HealthStats, IngestReportUseCase, create_api_router, create_health_router,
 };
+use crate::features::monitors::{CheckMonitorsUseCase, MonitorWorker};
+use crate::features::performance::{RollupTransactionLatencyUseCase, TransactionLatencyWorker};
+use crate::features::release_health::{ReleaseHealthWorker, RollupReleaseHealthUseCase};
 use crate::shared::analytics::AnalyticsCollector;
-use crate::shared::compression::GzipCompressor;
+use crate::shared::compression::Compressor;
 use crate::shared::persistence::{Repositories, establish_connection_pool, run_migrations};
 use crate::shared::rate_limit::{
     AnalyticsLayer, RateLimitAnalyticsLayer, RateLimitType, create_global_rate_limiter,
@@ -40,7 +50,8 @@ pub async fn run_server() {
     info!("Database initialized");
 
     let repos = Repositories::new(pool.clone());
-    let compressor = GzipCompressor::new();
+    let compressor =
+        Compressor::new().with_max_decompressed_bytes(settings.max_decompressed_bytes());
 
     let analytics_collector = AnalyticsCollector::new(
         repos.analytics.clone(),
@@ -60,7 +71,7 @@ pub async fn run_server() {
         repos.project.clone(),
     );
 
-    let digest_use_case = DigestReportUseCase::new(repos.clone(), pool.clone(), compressor);
+    let digest_use_case = DigestReportUseCase::new(repos.clone(), pool.clone(), compressor.clone());
 
     let worker = DigestWorker::new(
         digest_use_case,
@@ -74,6 +85,39 @@ pub async fn run_server() {
         worker.run().await;
     });
 
+    let release_health_worker = ReleaseHealthWorker::new(
+        RollupReleaseHealthUseCase::new(
+            repos.release_health.clone(),
+            settings.release_health_lookback_hours,
+        ),
+        settings.release_health_interval_secs,
+    );
+    let release_health_shutdown = release_health_worker.shutdown_handle();
+    let release_health_handle = tokio::spawn(async move {
+        release_health_worker.run().await;
+    });
+
+    let transaction_latency_worker = TransactionLatencyWorker::new(
+        RollupTransactionLatencyUseCase::new(
+            repos.transaction_latency.clone(),
+            settings.transaction_rollup_lookback_hours,
+        ),
+        settings.transaction_rollup_interval_secs,
+    );
+    let transaction_latency_shutdown = transaction_latency_worker.shutdown_handle();
+    let transaction_latency_handle = tokio::spawn(async move {
+        transaction_latency_worker.run().await;
+    });
+
+    let monitor_worker = MonitorWorker::new(
+        CheckMonitorsUseCase::new(repos.monitor.clone(), pool.clone()),
+        settings.monitor_check_interval_secs,
+    );
+    let monitor_shutdown = monitor_worker.shutdown_handle();
+    let monitor_handle = tokio::spawn(async move {
+        monitor_worker.run().await;
+    });
+
     // Spawn health stats refresh task
     let health_cache = Arc::new(RwLock::new(HealthStats::default()));
     let health_cache_for_task = health_cache.clone();
@@ -121,6 +165,7 @@ pub async fn run_server() {
         "Project cache initialized"
     );
 
+    let pool_for_api = pool.clone();
     let app_state = AppState {
         ingest_use_case,
         compression_semaphore,
@@ -130,11 +175,16 @@ pub async fn run_server() {
         health_cache,
         health_cache_ttl: Duration::from_secs(settings.worker_interval_secs),
         max_uncompressed_payload_bytes: settings.max_uncompressed_payload_bytes,
+        max_attachment_bytes: settings.max_attachment_bytes,
         // Session repositories
         session_repo: repos.session.clone(),
         session_status_repo: repos.session_status.clone(),
-        session_release_repo: repos.session_release.clone(),
+        release_repo: repos.release.clone(),
         session_environment_repo: repos.session_environment.clone(),
+        session_aggregate_repo: repos.session_aggregate.clone(),
+        analytics_repo: repos.analytics.clone(),
+        user_feedback_repo: repos.user_feedback.clone(),
+        monitor_repo: repos.monitor.clone(),
     };
 
     info!(
@@ -144,8 +194,11 @@ pub async fn run_server() {
         "Rate limiting configured (0 = disabled)"
     );
 
+    // Attachments (screenshots, log files) rarely compress, so leave room for one
     let mut api_router = create_api_router(app_state.clone())
-        .layer(DefaultBodyLimit::max(settings.max_compressed_payload_bytes))
+        .layer(DefaultBodyLimit::max(
+            settings.max_compressed_payload_bytes + settings.max_attachment_bytes,
+        ))
         .layer(AnalyticsLayer::new(analytics_collector.clone()));
 
     if let Some(layer) = create_global_rate_limiter(
@@ -191,7 +244,70 @@ pub async fn run_server() {
     let health_router = create_health_router(app_state);
 
     // Merge routers
-    let app = api_router.merge(health_router);
+    let mut app = api_router.merge(health_router);
+
+    // REST API (bearer token, no rate limiting), only when a token is configured
+    match settings.api_token.clone() {
+        Some(api_token) => {
+            let rest_state = ApiState {
+                list_issues_use_case: ListIssuesUseCase::new(repos.clone(), pool_for_api.clone()),
+                get_event_use_case: GetEventUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                    compressor.clone(),
+                ),
+                update_issue_status_use_case: UpdateIssueStatusUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                get_issue_tags_use_case: GetIssueTagsUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                list_issue_feedback_use_case: ListIssueFeedbackUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                list_event_breadcrumbs_use_case: ListEventBreadcrumbsUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                list_event_attachments_use_case: ListEventAttachmentsUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                get_event_attachment_use_case: GetEventAttachmentUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                    compressor.clone(),
+                ),
+                search_events_use_case: SearchEventsUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                get_release_health_use_case: GetReleaseHealthUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                get_transaction_latency_use_case: GetTransactionLatencyUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                list_monitors_use_case: ListMonitorsUseCase::new(
+                    repos.clone(),
+                    pool_for_api.clone(),
+                ),
+                list_monitor_check_ins_use_case: ListMonitorCheckInsUseCase::new(
+                    repos.clone(),
+                    pool_for_api,
+                ),
+                api_token,
+            };
+            app = app.merge(create_rest_api_router(rest_state));
+            info!("REST API enabled under /api/0/");
+        }
+        None => info!("REST API disabled (API_TOKEN not set)"),
+    }
 
     let addr = settings.server_addr();
     info!(addr = %addr, "Server listening");
@@ -204,15 +320,23 @@ pub async fn run_server() {
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
-    .with_graceful_shutdown(shutdown_signal(shutdown_handle))
+    .with_graceful_shutdown(shutdown_signal(vec![
+        shutdown_handle,
+        release_health_shutdown,
+        transaction_latency_shutdown,
+        monitor_shutdown,
+    ]))
     .await
     .expect("Server error");
 
     worker_handle.await.ok();
+    release_health_handle.await.ok();
+    transaction_latency_handle.await.ok();
+    monitor_handle.await.ok();
     info!("Server shutdown complete");
 }
 
-async fn shutdown_signal(shutdown_handle: Arc<std::sync::atomic::AtomicBool>) {
+async fn shutdown_signal(shutdown_handles: Vec<Arc<std::sync::atomic::AtomicBool>>) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -236,5 +360,7 @@ async fn shutdown_signal(shutdown_handle: Arc<std::sync::atomic::AtomicBool>) {
     }
 
     info!("Shutdown signal received");
-    shutdown_handle.store(true, Ordering::SeqCst);
+    for handle in shutdown_handles {
+        handle.store(true, Ordering::SeqCst);
+    }
 }
@@ -1,10 +1,14 @@
 use clap::{Parser, Subcommand};
 
 use crash_cache::config::Settings;
-use crash_cache::features::cli::{ArchiveCommand, ProjectCommand, archive, project, ruminate};
+use crash_cache::features::cli::{
+    ArchiveCommand, IssueCommand, ProjectCommand, SessionCommand, archive, issue, project,
+    ruminate, session,
+};
 use crash_cache::features::serve::run_server;
 use crash_cache::shared::persistence::{
-    ProjectRepository, establish_connection_pool, run_migrations,
+    IssueRepository, ProjectRepository, ReportTagRepository, UserFeedbackRepository,
+    establish_connection_pool, run_migrations,
 };
 
 #[derive(Parser)]
@@ -24,6 +28,16 @@ enum Commands {
         #[command(subcommand)]
         action: ProjectCommand,
     },
+    /// Triage issues (resolve, ignore, unresolve) and inspect their tags
+    Issue {
+        #[command(subcommand)]
+        action: IssueCommand,
+    },
+    /// Release health: crash-free session and user rates
+    Session {
+        #[command(subcommand)]
+        action: SessionCommand,
+    },
     /// Export/import archives
     Archive {
         #[command(subcommand)]
@@ -58,6 +72,29 @@ async fn main() {
             let server_addr = settings.server_addr();
             project::handle(action, &project_repo, &server_addr);
         }
+        Commands::Issue { action } => {
+            let settings = Settings::from_env();
+            let pool = establish_connection_pool(
+                &settings.database_url,
+                settings.db_pool_size,
+                settings.db_pool_timeout_secs,
+            );
+            run_migrations(&pool);
+            let issue_repo = IssueRepository::new(pool.clone());
+            let tag_repo = ReportTagRepository::new(pool.clone());
+            let feedback_repo = UserFeedbackRepository::new(pool.clone());
+            issue::handle(action, &issue_repo, &tag_repo, &feedback_repo);
+        }
+        Commands::Session { action } => {
+            let settings = Settings::from_env();
+            let pool = establish_connection_pool(
+                &settings.database_url,
+                settings.db_pool_size,
+                settings.db_pool_timeout_secs,
+            );
+            run_migrations(&pool);
+            session::handle(action, &pool);
+        }
         Commands::Archive { action } => {
             let settings = Settings::from_env();
             let pool = establish_connection_pool(
@@ -66,7 +103,7 @@ async fn main() {
                 settings.db_pool_timeout_secs,
             );
             run_migrations(&pool);
-            archive::handle(action, &pool);
+            archive::handle(action, &pool, settings.max_decompressed_bytes());
         }
         Commands::Ruminate { yes } => {
             let settings = Settings::from_env();
@@ -0,0 +1,74 @@
+use flate2::Compression;
+use flate2::read::GzEncoder;
+use std::io::Read;
+
+use super::ContentEncoding;
+use crate::shared::domain::{Archive, DomainError};
+
+/// Compresses archive payloads with gzip and decompresses them with the
+/// compression recorded on the archive
+#[derive(Clone)]
+pub struct Compressor {
+    level: Compression,
+    max_decompressed_bytes: Option<usize>,
+}
+
+impl Compressor {
+    pub fn new() -> Self {
+        Self {
+            level: Compression::default(),
+            max_decompressed_bytes: None,
+        }
+    }
+
+    pub fn with_level(level: u32) -> Self {
+        Self {
+            level: Compression::new(level),
+            max_decompressed_bytes: None,
+        }
+    }
+
+    /// Makes `decompress` stop with `PayloadTooLarge` past `max_bytes`
+    pub fn with_max_decompressed_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_decompressed_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, DomainError> {
+        let mut encoder = GzEncoder::new(data, self.level);
+        let mut compressed = Vec::new();
+        encoder
+            .read_to_end(&mut compressed)
+            .map_err(|e| DomainError::Compression(e.to_string()))?;
+        Ok(compressed)
+    }
+
+    pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, DomainError> {
+        self.decompress_as(ContentEncoding::Gzip, data)
+    }
+
+    /// Decompresses an archive payload with the compression recorded on it
+    pub fn decompress_archive(&self, archive: &Archive) -> Result<Vec<u8>, DomainError> {
+        let encoding = ContentEncoding::parse(&archive.compression).ok_or_else(|| {
+            DomainError::Decompression(format!(
+                "Unknown archive compression '{}'",
+                archive.compression
+            ))
+        })?;
+        self.decompress_as(encoding, &archive.compressed_payload)
+    }
+
+    fn decompress_as(
+        &self,
+        encoding: ContentEncoding,
+        data: &[u8],
+    ) -> Result<Vec<u8>, DomainError> {
+        encoding.decompress_bounded(data, self.max_decompressed_bytes.unwrap_or(usize::MAX))
+    }
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
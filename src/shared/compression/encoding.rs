@@ -0,0 +1,73 @@
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::io::Read;
+
+use crate::shared::domain::DomainError;
+
+/// Compression of a request body (`Content-Encoding`) or of a stored archive
+/// (`archive.compression`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Identity,
+    Gzip,
+    /// zlib-wrapped deflate, as HTTP defines it
+    Deflate,
+    Brotli,
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Returns None for encodings this server cannot decode, including a list
+    /// of several encodings
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "identity" => Some(Self::Identity),
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "br" => Some(Self::Brotli),
+            "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+            Self::Brotli => "br",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    /// Decompresses `data`, streaming at most `max_bytes + 1` bytes out of the
+    /// decoder so a small body cannot expand into an unbounded allocation
+    pub fn decompress_bounded(
+        &self,
+        data: &[u8],
+        max_bytes: usize,
+    ) -> Result<Vec<u8>, DomainError> {
+        match self {
+            Self::Identity => read_bounded(data, max_bytes),
+            Self::Gzip => read_bounded(GzDecoder::new(data), max_bytes),
+            Self::Deflate => read_bounded(ZlibDecoder::new(data), max_bytes),
+            Self::Brotli => read_bounded(brotli::Decompressor::new(data, 4096), max_bytes),
+            Self::Zstd => {
+                let decoder = zstd::stream::read::Decoder::new(data)
+                    .map_err(|e| DomainError::Decompression(e.to_string()))?;
+                read_bounded(decoder, max_bytes)
+            }
+        }
+    }
+}
+
+fn read_bounded(reader: impl Read, max_bytes: usize) -> Result<Vec<u8>, DomainError> {
+    let mut decompressed = Vec::new();
+    reader
+        .take((max_bytes as u64).saturating_add(1))
+        .read_to_end(&mut decompressed)
+        .map_err(|e| DomainError::Decompression(e.to_string()))?;
+    if decompressed.len() > max_bytes {
+        return Err(DomainError::PayloadTooLarge(max_bytes));
+    }
+    Ok(decompressed)
+}
@@ -1,3 +1,5 @@
-mod gzip;
+mod compressor;
+mod encoding;
 
-pub use gzip::GzipCompressor;
+pub use compressor::Compressor;
+pub use encoding::ContentEncoding;
@@ -1,10 +1,15 @@
 use chrono::{DateTime, Utc};
 
+/// Compression of every archive written by ingest, stored in
+/// `archive.compression`; other content encodings are recompressed to it
+pub const ARCHIVE_COMPRESSION: &str = "gzip";
+
 #[derive(Debug, Clone)]
 pub struct Archive {
     pub hash: String,
     pub project_id: i32,
     pub compressed_payload: Vec<u8>,
+    pub compression: String,
     pub original_size: Option<i32>,
     pub created_at: DateTime<Utc>,
 }
@@ -20,6 +25,7 @@ impl Archive {
             hash,
             project_id,
             compressed_payload,
+            compression: ARCHIVE_COMPRESSION.to_string(),
             original_size,
             created_at: Utc::now(),
         }
@@ -14,6 +14,9 @@ pub enum DomainError {
     #[error("Decompression error: {0}")]
     Decompression(String),
 
+    #[error("Payload decompresses to more than {0} bytes")]
+    PayloadTooLarge(usize),
+
     #[error("Serialization error: {0}")]
     Serialization(String),
 
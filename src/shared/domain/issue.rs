@@ -0,0 +1,125 @@
+use std::cmp::Ordering;
+
+use chrono::NaiveDateTime;
+
+use super::release::split_release;
+
+/// Triage state of an issue, stored as text in `issue.status`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IssueStatus {
+    #[default]
+    Unresolved,
+    Resolved,
+    Ignored,
+}
+
+impl IssueStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "unresolved" => Some(Self::Unresolved),
+            "resolved" => Some(Self::Resolved),
+            "ignored" => Some(Self::Ignored),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unresolved => "unresolved",
+            Self::Resolved => "resolved",
+            Self::Ignored => "ignored",
+        }
+    }
+}
+
+/// How the fingerprint of an issue was produced, stored in `issue.grouping_strategy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupingStrategy {
+    /// `fingerprint` sent by the SDK
+    Custom,
+    /// `filename:function:lineno` of in-app frames
+    InAppFrames,
+    /// Same as `InAppFrames`, over every frame when none is in-app
+    AllFrames,
+    /// Exception type plus normalized exception message
+    ExceptionMessage,
+    /// Logentry template or plain message of a message event
+    LogEntry,
+}
+
+impl GroupingStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Custom => "custom",
+            Self::InAppFrames => "in_app_frames",
+            Self::AllFrames => "all_frames",
+            Self::ExceptionMessage => "exception_message",
+            Self::LogEntry => "logentry",
+        }
+    }
+}
+
+/// A requested status change, validated before it reaches the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueStatusChange {
+    Unresolve,
+    /// Resolve the issue; with a release, only events from later releases reopen it
+    Resolve {
+        in_release: Option<String>,
+    },
+    /// Ignore the issue until a date and/or until `count` more events arrive
+    Ignore {
+        until: Option<NaiveDateTime>,
+        count: Option<i32>,
+    },
+}
+
+impl IssueStatusChange {
+    pub fn status(&self) -> IssueStatus {
+        match self {
+            Self::Unresolve => IssueStatus::Unresolved,
+            Self::Resolve { .. } => IssueStatus::Resolved,
+            Self::Ignore { .. } => IssueStatus::Ignored,
+        }
+    }
+}
+
+/// Returns true when `release` is strictly later than `baseline`.
+///
+/// Both accept `name@version+build`; the package name is ignored. Versions are
+/// compared component by component (numerically when both sides are numbers),
+/// then by build when the versions are equal.
+pub fn is_later_release(release: &str, baseline: &str) -> bool {
+    let (version, build) = split_release(release);
+    let (baseline_version, baseline_build) = split_release(baseline);
+
+    match compare_dotted(version, baseline_version) {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => match (build, baseline_build) {
+            (Some(b), Some(base)) => compare_dotted(b, base) == Ordering::Greater,
+            _ => false,
+        },
+    }
+}
+
+fn compare_dotted(a: &str, b: &str) -> Ordering {
+    let mut left = a.split(['.', '-']);
+    let mut right = b.split(['.', '-']);
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(l), Some(r)) => {
+                let ordering = match (l.parse::<u64>(), r.parse::<u64>()) {
+                    (Ok(l), Ok(r)) => l.cmp(&r),
+                    _ => l.cmp(r),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,31 @@
 mod archive;
 mod error;
+mod issue;
+mod monitor;
 mod project;
 mod queue;
+mod release;
 mod sentry_report;
+mod sentry_transaction;
+mod session;
 
-pub use archive::Archive;
+pub use archive::{ARCHIVE_COMPRESSION, Archive};
 pub use error::DomainError;
+pub use issue::{GroupingStrategy, IssueStatus, IssueStatusChange, is_later_release};
+pub use monitor::{
+    CheckInStatus, DEFAULT_CHECKIN_MARGIN, DEFAULT_MAX_RUNTIME, IntervalUnit, MonitorSchedule,
+    parse_timezone,
+};
 pub use project::Project;
 pub use queue::{QueueError, QueueItem};
+pub use release::split_release;
 pub use sentry_report::{
-    SentryAppContext, SentryContext, SentryContexts, SentryCultureContext, SentryDeviceContext,
-    SentryException, SentryExceptionValue, SentryOsContext, SentryReport, SentrySdk,
-    SentryStacktrace, SentryStacktraceFrame, SentryUser,
+    SentryAppContext, SentryBreadcrumb, SentryContext, SentryContexts, SentryCultureContext,
+    SentryDeviceContext, SentryException, SentryExceptionValue, SentryLogEntry, SentryMechanism,
+    SentryOsContext, SentryReport, SentrySdk, SentryStacktrace, SentryStacktraceFrame,
+    SentryThread, SentryThreads, SentryUser,
+};
+pub use sentry_transaction::{
+    SentryTraceContext, SentryTransaction, SentryTransactionContexts, UNLABELED_TRANSACTION,
 };
+pub use session::SessionStatus;
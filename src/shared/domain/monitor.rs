@@ -0,0 +1,185 @@
+use chrono::{Duration, Months, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use croner::Cron;
+
+use super::DomainError;
+
+/// Minutes a check-in may be late when the monitor config sets no margin
+pub const DEFAULT_CHECKIN_MARGIN: i32 = 1;
+/// Minutes a run may stay in progress when the monitor config sets no max runtime
+pub const DEFAULT_MAX_RUNTIME: i32 = 30;
+
+/// Status of a cron check-in, stored as text in `monitor_checkin.status`.
+/// The last finished check-in also sets `monitor.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckInStatus {
+    InProgress,
+    Ok,
+    Error,
+    /// No check-in arrived by the expected time plus the margin
+    Missed,
+    /// Still in progress after the monitor's max runtime
+    Timeout,
+}
+
+impl CheckInStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "in_progress" => Some(Self::InProgress),
+            "ok" => Some(Self::Ok),
+            "error" => Some(Self::Error),
+            "missed" => Some(Self::Missed),
+            "timeout" => Some(Self::Timeout),
+            _ => None,
+        }
+    }
+
+    /// Statuses an SDK can send; `missed` and `timeout` are set by the server
+    pub fn from_sdk(value: &str) -> Option<Self> {
+        Self::parse(value).filter(|s| !matches!(s, Self::Missed | Self::Timeout))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Ok => "ok",
+            Self::Error => "error",
+            Self::Missed => "missed",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::InProgress)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalUnit {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl IntervalUnit {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "minute" => Some(Self::Minute),
+            "hour" => Some(Self::Hour),
+            "day" => Some(Self::Day),
+            "week" => Some(Self::Week),
+            "month" => Some(Self::Month),
+            "year" => Some(Self::Year),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+}
+
+/// When a monitored job is expected to run, stored in `monitor.schedule_type`,
+/// `monitor.schedule` and `monitor.schedule_unit`
+#[derive(Debug, Clone)]
+pub enum MonitorSchedule {
+    /// Five-field crontab expression (or `@daily`, `@hourly`, ...)
+    Crontab(Box<Cron>),
+    /// Every `value` units after the last check-in
+    Interval { value: u32, unit: IntervalUnit },
+}
+
+impl MonitorSchedule {
+    pub fn parse(
+        schedule_type: &str,
+        schedule: &str,
+        unit: Option<&str>,
+    ) -> Result<Self, DomainError> {
+        match schedule_type {
+            "crontab" => Cron::new(schedule.trim())
+                .parse()
+                .map(|cron| Self::Crontab(Box::new(cron)))
+                .map_err(|e| {
+                    DomainError::InvalidRequest(format!("Invalid crontab '{}': {}", schedule, e))
+                }),
+            "interval" => {
+                let value = schedule
+                    .trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|v| *v > 0)
+                    .ok_or_else(|| {
+                        DomainError::InvalidRequest(format!("Invalid interval '{}'", schedule))
+                    })?;
+                let unit = unit.and_then(IntervalUnit::parse).ok_or_else(|| {
+                    DomainError::InvalidRequest(format!(
+                        "Invalid interval unit '{}'",
+                        unit.unwrap_or_default()
+                    ))
+                })?;
+                Ok(Self::Interval { value, unit })
+            }
+            other => Err(DomainError::InvalidRequest(format!(
+                "Unknown schedule type '{}'",
+                other
+            ))),
+        }
+    }
+
+    pub fn schedule_type(&self) -> &'static str {
+        match self {
+            Self::Crontab(_) => "crontab",
+            Self::Interval { .. } => "interval",
+        }
+    }
+
+    pub fn unit(&self) -> Option<&'static str> {
+        match self {
+            Self::Crontab(_) => None,
+            Self::Interval { unit, .. } => Some(unit.as_str()),
+        }
+    }
+
+    /// First expected run strictly after `after` (UTC). Crontabs are evaluated
+    /// in `timezone`, falling back to UTC when it is not an IANA name.
+    pub fn next_after(&self, after: NaiveDateTime, timezone: &str) -> Option<NaiveDateTime> {
+        match self {
+            Self::Crontab(cron) => {
+                let tz = parse_timezone(timezone).unwrap_or(Tz::UTC);
+                cron.find_next_occurrence(&tz.from_utc_datetime(&after), false)
+                    .ok()
+                    .map(|next| next.naive_utc())
+            }
+            Self::Interval { value, unit } => {
+                let value = *value;
+                match unit {
+                    IntervalUnit::Minute => {
+                        after.checked_add_signed(Duration::minutes(value.into()))
+                    }
+                    IntervalUnit::Hour => after.checked_add_signed(Duration::hours(value.into())),
+                    IntervalUnit::Day => after.checked_add_signed(Duration::days(value.into())),
+                    IntervalUnit::Week => after.checked_add_signed(Duration::weeks(value.into())),
+                    IntervalUnit::Month => after.checked_add_months(Months::new(value)),
+                    IntervalUnit::Year => {
+                        after.checked_add_months(Months::new(value.saturating_mul(12)))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// IANA timezone name, e.g. `Europe/Paris`
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
@@ -3,14 +3,16 @@ use chrono::{DateTime, Utc};
 #[derive(Debug, Clone)]
 pub struct QueueItem {
     pub id: Option<i32>,
+    pub project_id: i32,
     pub archive_hash: String,
     pub created_at: DateTime<Utc>,
 }
 
 impl QueueItem {
-    pub fn new(archive_hash: String) -> Self {
+    pub fn new(project_id: i32, archive_hash: String) -> Self {
         Self {
             id: None,
+            project_id,
             archive_hash,
             created_at: Utc::now(),
         }
@@ -20,6 +22,7 @@ impl QueueItem {
 #[derive(Debug, Clone)]
 pub struct QueueError {
     pub id: i32,
+    pub project_id: i32,
     pub archive_hash: String,
     pub error: String,
     pub created_at: DateTime<Utc>,
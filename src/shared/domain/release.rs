@@ -0,0 +1,14 @@
+/// Splits `package@version+build` into version and build.
+///
+/// The package name is optional and dropped; the build is everything after
+/// the first `+`.
+pub fn split_release(release: &str) -> (&str, Option<&str>) {
+    let version_build = release
+        .split_once('@')
+        .map(|(_, rest)| rest)
+        .unwrap_or(release);
+    match version_build.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version_build, None),
+    }
+}
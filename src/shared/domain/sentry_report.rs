@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+const MAX_TAG_KEY_LENGTH: usize = 200;
+const MAX_TAG_VALUE_LENGTH: usize = 200;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SentryReport {
     pub event_id: Option<String>,
@@ -12,11 +15,20 @@ pub struct SentryReport {
     pub environment: Option<String>,
     pub sdk: Option<SentrySdk>,
     pub contexts: Option<SentryContexts>,
-    pub tags: Option<HashMap<String, serde_json::Value>>,
+    /// Object of key/value pairs, or an array of `[key, value]` pairs
+    pub tags: Option<serde_json::Value>,
     pub exception: Option<SentryException>,
+    /// Stacks of every thread; native crashes and ANRs put the crash stack here
+    pub threads: Option<SentryThreads>,
     pub user: Option<SentryUser>,
     pub request: Option<serde_json::Value>,
+    /// `{"values": [...]}` or a bare list of breadcrumbs
     pub breadcrumbs: Option<serde_json::Value>,
+    /// Plain string, or an object with `message`/`formatted` (legacy SDKs)
+    pub message: Option<serde_json::Value>,
+    pub logentry: Option<SentryLogEntry>,
+    /// Custom grouping sent by the SDK, may contain `{{ default }}`
+    pub fingerprint: Option<Vec<serde_json::Value>>,
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
 }
@@ -28,6 +40,56 @@ pub struct SentryUser {
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryBreadcrumb {
+    /// RFC 3339 string or unix seconds
+    pub timestamp: Option<serde_json::Value>,
+    #[serde(rename = "type")]
+    pub breadcrumb_type: Option<String>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub data: Option<serde_json::Value>,
+}
+
+impl SentryBreadcrumb {
+    pub fn parsed_timestamp(&self) -> Option<chrono::NaiveDateTime> {
+        parse_event_timestamp(self.timestamp.as_ref()?)
+    }
+
+    /// Navigation target (`data.to`) or HTTP request URL (`data.url`)
+    pub fn url(&self) -> Option<&str> {
+        let data = self.data.as_ref()?;
+        data.get("url")
+            .or_else(|| data.get("to"))
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// Parses an RFC 3339 string or unix seconds (with fraction) into UTC
+pub(super) fn parse_event_timestamp(value: &serde_json::Value) -> Option<chrono::NaiveDateTime> {
+    match value {
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| dt.naive_utc()),
+        serde_json::Value::Number(n) => {
+            let secs = n.as_f64()?;
+            chrono::DateTime::from_timestamp_micros((secs * 1_000_000.0) as i64)
+                .map(|dt| dt.naive_utc())
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryLogEntry {
+    /// Template before parameter substitution, e.g. `User %s not found`
+    pub message: Option<String>,
+    pub formatted: Option<String>,
+    pub params: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SentrySdk {
     pub name: Option<String>,
@@ -111,6 +173,61 @@ pub struct SentryExceptionValue {
     pub exception_type: Option<String>,
     pub value: Option<String>,
     pub stacktrace: Option<SentryStacktrace>,
+    pub mechanism: Option<SentryMechanism>,
+    /// Id of the thread that raised the exception, number or string
+    pub thread_id: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryMechanism {
+    /// How the exception was captured, e.g. `generic`, `onerror`, `UncaughtExceptionHandler`
+    #[serde(rename = "type")]
+    pub mechanism_type: Option<String>,
+    /// `false` when the exception reached a global handler (a crash)
+    pub handled: Option<bool>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryThreads {
+    pub values: Option<Vec<SentryThread>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryThread {
+    /// Number or string depending on the SDK
+    pub id: Option<serde_json::Value>,
+    pub name: Option<String>,
+    /// The thread that crashed (native crashes)
+    pub crashed: Option<bool>,
+    /// The thread that was running when the event was captured (ANRs, messages)
+    pub current: Option<bool>,
+    pub stacktrace: Option<SentryStacktrace>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A thread id as text; SDKs send it as a number or a string
+fn thread_id_string(id: &serde_json::Value) -> Option<String> {
+    match id {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+impl SentryThread {
+    pub fn id_string(&self) -> Option<String> {
+        self.id.as_ref().and_then(thread_id_string)
+    }
+
+    pub fn frames(&self) -> &[SentryStacktraceFrame] {
+        self.stacktrace
+            .as_ref()
+            .and_then(|s| s.frames.as_deref())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,18 +269,44 @@ impl SentryReport {
         None
     }
 
-    pub fn extract_error_info(&self) -> (Option<String>, Option<String>) {
-        if let Some(first) = self
-            .exception
+    /// Exception values in the order sent, innermost cause first
+    pub fn exception_chain(&self) -> &[SentryExceptionValue] {
+        self.exception
             .as_ref()
-            .and_then(|e| e.values.as_ref())
-            .and_then(|v| v.first())
-        {
-            return (first.exception_type.clone(), first.value.clone());
+            .and_then(|e| e.values.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// The exception Sentry shows for the event: the last value of the chain,
+    /// i.e. the outermost exception wrapping the others.
+    pub fn primary_exception(&self) -> Option<&SentryExceptionValue> {
+        self.exception_chain().last()
+    }
+
+    pub fn extract_error_info(&self) -> (Option<String>, Option<String>) {
+        if let Some(primary) = self.primary_exception() {
+            return (primary.exception_type.clone(), primary.value.clone());
         }
         (None, None)
     }
 
+    /// Event level normalized the way Sentry does: lowercase, with the
+    /// `critical`/`warn`/`log` aliases mapped and `error` when missing.
+    pub fn normalized_level(&self) -> String {
+        let level = self
+            .level
+            .as_deref()
+            .map(|l| l.trim().to_lowercase())
+            .filter(|l| !l.is_empty())
+            .unwrap_or_else(|| "error".to_string());
+        match level.as_str() {
+            "critical" => "fatal".to_string(),
+            "warn" => "warning".to_string(),
+            "log" => "info".to_string(),
+            _ => level,
+        }
+    }
+
     pub fn extract_sdk_info(&self) -> (Option<String>, Option<String>) {
         self.sdk
             .as_ref()
@@ -171,19 +314,150 @@ impl SentryReport {
             .unwrap_or((None, None))
     }
 
-    pub fn extract_in_app_frames(&self) -> Vec<&SentryStacktraceFrame> {
-        let mut frames = Vec::new();
-        if let Some(values) = self.exception.as_ref().and_then(|e| e.values.as_ref()) {
-            for value in values {
-                if let Some(st_frames) = value.stacktrace.as_ref().and_then(|s| s.frames.as_ref()) {
-                    for frame in st_frames {
-                        if frame.in_app.unwrap_or(false) {
-                            frames.push(frame);
-                        }
-                    }
-                }
+    /// Returns the event tags as (key, value) strings in the order sent.
+    /// Numbers and booleans are stringified; empty, null and over-long entries
+    /// are dropped like Sentry does, and the first value of a repeated key wins.
+    pub fn extract_tags(&self) -> Vec<(String, String)> {
+        let pairs: Vec<(&str, &serde_json::Value)> = match &self.tags {
+            Some(serde_json::Value::Object(map)) => {
+                map.iter().map(|(k, v)| (k.as_str(), v)).collect()
+            }
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|item| match item.as_array().map(Vec::as_slice) {
+                    Some([serde_json::Value::String(k), v]) => Some((k.as_str(), v)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let mut tags: Vec<(String, String)> = Vec::new();
+        for (key, value) in pairs {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::Bool(b) => b.to_string(),
+                _ => continue,
+            };
+            let key = key.trim();
+            let value = value.trim();
+            if key.is_empty()
+                || value.is_empty()
+                || key.chars().count() > MAX_TAG_KEY_LENGTH
+                || value.chars().count() > MAX_TAG_VALUE_LENGTH
+                || tags.iter().any(|(k, _)| k == key)
+            {
+                continue;
             }
+            tags.push((key.to_string(), value.to_string()));
+        }
+        tags
+    }
+
+    /// Breadcrumbs in the order sent (oldest first); malformed entries are skipped
+    pub fn extract_breadcrumbs(&self) -> Vec<SentryBreadcrumb> {
+        let items = match &self.breadcrumbs {
+            Some(serde_json::Value::Array(items)) => items,
+            Some(serde_json::Value::Object(obj)) => match obj.get("values") {
+                Some(serde_json::Value::Array(items)) => items,
+                _ => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+        items
+            .iter()
+            .filter_map(|item| serde_json::from_value(item.clone()).ok())
+            .collect()
+    }
+
+    /// Returns the SDK fingerprint as strings (numbers and booleans are
+    /// stringified like Sentry does), or `None` when absent or empty.
+    pub fn extract_fingerprint(&self) -> Option<Vec<String>> {
+        let parts: Vec<String> = self
+            .fingerprint
+            .as_ref()?
+            .iter()
+            .filter_map(|v| match v {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                serde_json::Value::Bool(b) => Some(b.to_string()),
+                _ => None,
+            })
+            .collect();
+        if parts.is_empty() { None } else { Some(parts) }
+    }
+
+    /// Returns the message template of a message event: the logentry template,
+    /// then its formatted text, then the legacy `message` field.
+    pub fn extract_message_template(&self) -> Option<String> {
+        let logentry = self.logentry.as_ref();
+        let legacy = match &self.message {
+            Some(serde_json::Value::String(s)) => Some(s.clone()),
+            Some(serde_json::Value::Object(obj)) => obj
+                .get("message")
+                .or_else(|| obj.get("formatted"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            _ => None,
+        };
+
+        logentry
+            .and_then(|l| l.message.clone())
+            .or_else(|| logentry.and_then(|l| l.formatted.clone()))
+            .or(legacy)
+            .filter(|m| !m.trim().is_empty())
+    }
+
+    /// Every thread of the event, in the order sent
+    pub fn thread_list(&self) -> &[SentryThread] {
+        self.threads
+            .as_ref()
+            .and_then(|t| t.values.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// The thread the crash happened on: the one the primary exception names,
+    /// then the one flagged `crashed`, then the one flagged `current`.
+    pub fn crashed_thread(&self) -> Option<&SentryThread> {
+        let threads = self.thread_list();
+        let exception_thread = self
+            .primary_exception()
+            .and_then(|e| e.thread_id.as_ref())
+            .and_then(thread_id_string)
+            .and_then(|id| {
+                threads
+                    .iter()
+                    .find(|t| t.id_string().as_deref() == Some(id.as_str()))
+            });
+
+        exception_thread
+            .or_else(|| threads.iter().find(|t| t.crashed.unwrap_or(false)))
+            .or_else(|| threads.iter().find(|t| t.current.unwrap_or(false)))
+    }
+
+    /// Every frame of every exception, in order. When no exception carries a
+    /// stack (native crashes, ANRs), the frames of the crashed thread.
+    pub fn extract_all_frames(&self) -> Vec<&SentryStacktraceFrame> {
+        let frames: Vec<&SentryStacktraceFrame> = self
+            .exception_chain()
+            .iter()
+            .filter_map(|value| value.stacktrace.as_ref().and_then(|s| s.frames.as_ref()))
+            .flatten()
+            .collect();
+        if !frames.is_empty() {
+            return frames;
         }
-        frames
+
+        self.crashed_thread()
+            .map(|t| t.frames().iter().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn extract_in_app_frames(&self) -> Vec<&SentryStacktraceFrame> {
+        self.extract_all_frames()
+            .into_iter()
+            .filter(|frame| frame.in_app.unwrap_or(false))
+            .collect()
     }
 }
@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use super::sentry_report::parse_event_timestamp;
+
+/// Name given by Sentry to transactions sent without one
+pub const UNLABELED_TRANSACTION: &str = "<unlabeled transaction>";
+
+/// Performance event sent as a `transaction` envelope item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryTransaction {
+    pub event_id: Option<String>,
+    /// Route, screen or task name
+    pub transaction: Option<String>,
+    /// RFC 3339 string or unix seconds
+    pub start_timestamp: Option<serde_json::Value>,
+    /// End of the transaction, RFC 3339 string or unix seconds
+    pub timestamp: Option<serde_json::Value>,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    pub contexts: Option<SentryTransactionContexts>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryTransactionContexts {
+    pub trace: Option<SentryTraceContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentryTraceContext {
+    pub trace_id: Option<String>,
+    /// Operation, e.g. `http.server` or `ui.load`
+    pub op: Option<String>,
+    /// `ok`, `internal_error`, `deadline_exceeded`, ...
+    pub status: Option<String>,
+}
+
+impl SentryTransaction {
+    pub fn name(&self) -> &str {
+        self.transaction
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(UNLABELED_TRANSACTION)
+    }
+
+    pub fn trace(&self) -> Option<&SentryTraceContext> {
+        self.contexts.as_ref()?.trace.as_ref()
+    }
+
+    pub fn started_at(&self) -> Option<NaiveDateTime> {
+        parse_event_timestamp(self.start_timestamp.as_ref()?)
+    }
+
+    pub fn finished_at(&self) -> Option<NaiveDateTime> {
+        parse_event_timestamp(self.timestamp.as_ref()?)
+    }
+
+    /// Milliseconds between start and end, None when either is missing or
+    /// the end is before the start
+    pub fn duration_ms(&self) -> Option<f64> {
+        let micros = (self.finished_at()? - self.started_at()?).num_microseconds()?;
+        (micros >= 0).then(|| micros as f64 / 1000.0)
+    }
+}
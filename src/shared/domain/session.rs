@@ -0,0 +1,61 @@
+/// Status of a session, stored as text in `unwrap_session_status`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SessionStatus {
+    #[default]
+    Ok,
+    Exited,
+    Errored,
+    Abnormal,
+    Crashed,
+}
+
+impl SessionStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "ok" => Some(Self::Ok),
+            "exited" => Some(Self::Exited),
+            "errored" => Some(Self::Errored),
+            "abnormal" => Some(Self::Abnormal),
+            "crashed" => Some(Self::Crashed),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Exited => "exited",
+            Self::Errored => "errored",
+            Self::Abnormal => "abnormal",
+            Self::Crashed => "crashed",
+        }
+    }
+
+    /// Every status but `ok` ends the session
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Ok)
+    }
+
+    /// Status of a session in `self` after receiving an update with `update`.
+    ///
+    /// Updates can arrive out of order, so a terminal status is never replaced
+    /// by `ok`, and of two terminal statuses the more severe one wins
+    /// (crashed, then abnormal, errored, exited).
+    pub fn merge(self, update: Self) -> Self {
+        if update.severity() > self.severity() {
+            update
+        } else {
+            self
+        }
+    }
+
+    fn severity(&self) -> u8 {
+        match self {
+            Self::Ok => 0,
+            Self::Exited => 1,
+            Self::Errored => 2,
+            Self::Abnormal => 3,
+            Self::Crashed => 4,
+        }
+    }
+}
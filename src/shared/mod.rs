@@ -4,3 +4,4 @@ pub mod domain;
 pub mod parser;
 pub mod persistence;
 pub mod rate_limit;
+pub mod worker;
@@ -1,10 +1,37 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use thiserror::Error;
+
+/// Why a body is not a valid envelope. Items are numbered from 0.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeError {
+    #[error("Empty envelope")]
+    Empty,
+
+    #[error("Invalid envelope header: {0}")]
+    InvalidHeader(String),
 
+    #[error("Invalid header of item {index}: {reason}")]
+    InvalidItemHeader { index: usize, reason: String },
+
+    #[error("Item {index} declares {length} bytes but only {available} remain")]
+    TruncatedPayload {
+        index: usize,
+        length: usize,
+        available: usize,
+    },
+
+    #[error("Item {index} payload is not followed by a newline")]
+    MissingNewline { index: usize },
+}
+
+/// A parsed envelope. Item payloads borrow from the parsed body.
 #[derive(Debug, Clone)]
-pub struct Envelope {
+pub struct Envelope<'a> {
     pub header: EnvelopeHeader,
-    pub items: Vec<EnvelopeItem>,
+    pub items: Vec<EnvelopeItem<'a>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,9 +49,9 @@ pub struct EnvelopeHeader {
 }
 
 #[derive(Debug, Clone)]
-pub struct EnvelopeItem {
+pub struct EnvelopeItem<'a> {
     pub header: ItemHeader,
-    pub payload: Vec<u8>,
+    pub payload: Cow<'a, [u8]>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,78 +66,306 @@ pub struct ItemHeader {
     pub extra: std::collections::HashMap<String, Value>,
 }
 
-impl Envelope {
-    pub fn parse(data: &[u8]) -> Option<Self> {
-        let mut lines = data.split(|&b| b == b'\n');
+impl ItemHeader {
+    /// `filename` of an attachment item
+    pub fn filename(&self) -> Option<&str> {
+        self.extra.get("filename").and_then(Value::as_str)
+    }
 
-        let header_line = lines.next()?;
-        let header: EnvelopeHeader = serde_json::from_slice(header_line).ok()?;
+    /// `attachment_type` of an attachment item, `event.attachment` when absent
+    pub fn attachment_type(&self) -> &str {
+        self.extra
+            .get("attachment_type")
+            .and_then(Value::as_str)
+            .unwrap_or("event.attachment")
+    }
+}
+
+impl<'a> Envelope<'a> {
+    /// Parses an envelope in a single pass. Items with a `length` take exactly
+    /// that many bytes, newlines included; other items end at the next newline.
+    /// The newline after the last item and the payload of a final item header
+    /// may be missing.
+    pub fn parse(data: &'a [u8]) -> Result<Self, EnvelopeError> {
+        if data.is_empty() {
+            return Err(EnvelopeError::Empty);
+        }
+        let (header_line, mut rest) = split_line(data);
+        let header: EnvelopeHeader = serde_json::from_slice(header_line)
+            .map_err(|e| EnvelopeError::InvalidHeader(e.to_string()))?;
 
         let mut items = Vec::new();
-        while let Some(item_header_line) = lines.next() {
+        while !rest.is_empty() {
+            let (item_header_line, after_header) = split_line(rest);
+            rest = after_header;
+            // Blank lines between items are tolerated
             if item_header_line.is_empty() {
                 continue;
             }
 
-            let item_header: ItemHeader = match serde_json::from_slice(item_header_line) {
-                Ok(h) => h,
-                Err(_) => continue,
-            };
+            let index = items.len();
+            let item_header: ItemHeader =
+                serde_json::from_slice(item_header_line).map_err(|e| {
+                    EnvelopeError::InvalidItemHeader {
+                        index,
+                        reason: e.to_string(),
+                    }
+                })?;
 
-            let payload = if let Some(length) = item_header.length {
-                let remaining: Vec<u8> = lines
-                    .clone()
-                    .flat_map(|l| {
-                        let mut v = l.to_vec();
-                        v.push(b'\n');
-                        v
-                    })
-                    .collect();
-
-                let payload = remaining.get(..length)?.to_vec();
-
-                let mut consumed = 0;
-                while consumed < length {
-                    if let Some(line) = lines.next() {
-                        consumed += line.len() + 1;
-                    } else {
-                        break;
+            let payload = match item_header.length {
+                Some(length) => {
+                    if length > rest.len() {
+                        return Err(EnvelopeError::TruncatedPayload {
+                            index,
+                            length,
+                            available: rest.len(),
+                        });
                     }
+                    let (payload, after_payload) = rest.split_at(length);
+                    rest = match after_payload.split_first() {
+                        None => after_payload,
+                        Some((b'\n', after_newline)) => after_newline,
+                        Some(_) => return Err(EnvelopeError::MissingNewline { index }),
+                    };
+                    payload
+                }
+                None => {
+                    let (payload, after_payload) = split_line(rest);
+                    rest = after_payload;
+                    payload
                 }
-                payload
-            } else {
-                let next_line = lines.next().unwrap_or(&[]);
-                next_line.to_vec()
             };
 
             items.push(EnvelopeItem {
                 header: item_header,
-                payload,
+                payload: Cow::Borrowed(payload),
             });
         }
 
-        Some(Envelope { header, items })
+        Ok(Envelope { header, items })
+    }
+
+    /// Serializes the envelope, with an explicit length on every item
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = serde_json::to_vec(&self.header).unwrap_or_default();
+        data.push(b'\n');
+        for item in &self.items {
+            let mut header = item.header.clone();
+            header.length = Some(item.payload.len());
+            data.extend(serde_json::to_vec(&header).unwrap_or_default());
+            data.push(b'\n');
+            data.extend_from_slice(&item.payload);
+            data.push(b'\n');
+        }
+        data
     }
 
-    pub fn find_event_payload(&self) -> Option<&[u8]> {
+    /// Payloads of `event` items; batched envelopes carry several
+    pub fn find_event_payloads(&self) -> Vec<&[u8]> {
         self.items
             .iter()
-            .find(|item| item.header.item_type == "event")
-            .map(|item| item.payload.as_slice())
+            .filter(|item| item.header.item_type == "event")
+            .map(|item| item.payload.as_ref())
+            .collect()
     }
 
-    pub fn find_transaction_payload(&self) -> Option<&[u8]> {
+    pub fn find_transaction_payloads(&self) -> Vec<&[u8]> {
         self.items
             .iter()
-            .find(|item| item.header.item_type == "transaction")
-            .map(|item| item.payload.as_slice())
+            .filter(|item| item.header.item_type == "transaction")
+            .map(|item| item.payload.as_ref())
+            .collect()
     }
 
     pub fn find_session_payloads(&self) -> Vec<&[u8]> {
         self.items
             .iter()
             .filter(|item| item.header.item_type == "session")
-            .map(|item| item.payload.as_slice())
+            .map(|item| item.payload.as_ref())
+            .collect()
+    }
+
+    /// Payloads of `sessions` items (session aggregates)
+    pub fn find_session_aggregate_payloads(&self) -> Vec<&[u8]> {
+        self.items
+            .iter()
+            .filter(|item| item.header.item_type == "sessions")
+            .map(|item| item.payload.as_ref())
+            .collect()
+    }
+
+    /// Payloads of `user_report` items (legacy user feedback)
+    pub fn find_user_report_payloads(&self) -> Vec<&[u8]> {
+        self.items
+            .iter()
+            .filter(|item| item.header.item_type == "user_report")
+            .map(|item| item.payload.as_ref())
+            .collect()
+    }
+
+    /// Payloads of `feedback` items (user feedback sent as an event)
+    pub fn find_feedback_payloads(&self) -> Vec<&[u8]> {
+        self.items
+            .iter()
+            .filter(|item| item.header.item_type == "feedback")
+            .map(|item| item.payload.as_ref())
+            .collect()
+    }
+
+    /// Payloads of `client_report` items (events discarded by the SDK)
+    pub fn find_client_report_payloads(&self) -> Vec<&[u8]> {
+        self.items
+            .iter()
+            .filter(|item| item.header.item_type == "client_report")
+            .map(|item| item.payload.as_ref())
+            .collect()
+    }
+
+    /// Payloads of `check_in` items (cron monitor runs)
+    pub fn find_check_in_payloads(&self) -> Vec<&[u8]> {
+        self.items
+            .iter()
+            .filter(|item| item.header.item_type == "check_in")
+            .map(|item| item.payload.as_ref())
             .collect()
     }
+
+    /// Content of the `attachment` item holding the minidump of a native crash
+    pub fn find_minidump(&self) -> Option<&[u8]> {
+        self.items
+            .iter()
+            .find(|item| {
+                item.header.item_type == "attachment"
+                    && item.header.attachment_type() == "event.minidump"
+            })
+            .map(|item| item.payload.as_ref())
+    }
+
+    /// `attachment` items (screenshots, view hierarchies, log files)
+    pub fn find_attachments(&self) -> Vec<&EnvelopeItem<'a>> {
+        self.items
+            .iter()
+            .filter(|item| item.header.item_type == "attachment")
+            .collect()
+    }
+}
+
+/// Splits at the first newline: the line without it, and what follows it
+fn split_line(data: &[u8]) -> (&[u8], &[u8]) {
+    match data.iter().position(|&b| b == b'\n') {
+        Some(end) => (&data[..end], &data[end + 1..]),
+        None => (data, &[]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_prefixed_payload_keeps_its_newlines() {
+        let body = b"{}\n{\"type\":\"attachment\",\"length\":7,\"filename\":\"a.bin\"}\n\x00\n\n\xff\n{}\n{\"type\":\"event\"}\n{}\n";
+
+        let envelope = Envelope::parse(body).unwrap();
+        assert_eq!(envelope.items.len(), 2);
+        assert_eq!(envelope.items[0].payload.as_ref(), b"\x00\n\n\xff\n{}");
+        assert_eq!(envelope.items[0].header.filename(), Some("a.bin"));
+        assert_eq!(envelope.find_event_payloads(), vec![&b"{}"[..]]);
+    }
+
+    #[test]
+    fn test_header_without_trailing_newline() {
+        let envelope =
+            Envelope::parse(br#"{"event_id":"9ec79c33ec9942ab8353589fcb2e04dc"}"#).unwrap();
+        assert_eq!(
+            envelope.header.event_id.as_deref(),
+            Some("9ec79c33ec9942ab8353589fcb2e04dc")
+        );
+        assert!(envelope.items.is_empty());
+
+        // A final item header may come without newline or payload
+        let envelope = Envelope::parse(b"{}\n{\"type\":\"attachment\",\"length\":0}").unwrap();
+        assert_eq!(envelope.items.len(), 1);
+        assert!(envelope.items[0].payload.is_empty());
+    }
+
+    #[test]
+    fn test_missing_final_newline() {
+        let envelope = Envelope::parse(b"{}\n{\"type\":\"session\"}\n{\"sid\":\"s-1\"}").unwrap();
+        assert_eq!(
+            envelope.find_session_payloads(),
+            vec![&b"{\"sid\":\"s-1\"}"[..]]
+        );
+
+        let envelope = Envelope::parse(b"{}\n{\"type\":\"attachment\",\"length\":3}\nabc").unwrap();
+        assert_eq!(envelope.items[0].payload.as_ref(), b"abc");
+    }
+
+    #[test]
+    fn test_blank_lines_between_items_are_skipped() {
+        let envelope =
+            Envelope::parse(b"{}\n\n{\"type\":\"event\"}\n{}\n\n\n{\"type\":\"event\"}\n[]\n")
+                .unwrap();
+        assert_eq!(envelope.find_event_payloads(), vec![&b"{}"[..], &b"[]"[..]]);
+    }
+
+    #[test]
+    fn test_empty_envelope() {
+        assert_eq!(Envelope::parse(b"").unwrap_err(), EnvelopeError::Empty);
+        // A header alone is an envelope without items
+        assert!(Envelope::parse(b"{}\n").unwrap().items.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        assert!(matches!(
+            Envelope::parse(b"not json\n{\"type\":\"event\"}\n{}"),
+            Err(EnvelopeError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_item_header() {
+        // The second item header has no type
+        assert!(matches!(
+            Envelope::parse(b"{}\n{\"type\":\"event\"}\n{}\n{\"length\":2}\n{}"),
+            Err(EnvelopeError::InvalidItemHeader { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_truncated_payload() {
+        assert_eq!(
+            Envelope::parse(b"{}\n{\"type\":\"attachment\",\"length\":10}\nshort").unwrap_err(),
+            EnvelopeError::TruncatedPayload {
+                index: 0,
+                length: 10,
+                available: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_newline_after_payload() {
+        let err =
+            Envelope::parse(b"{}\n{\"type\":\"attachment\",\"length\":2}\nabc\n").unwrap_err();
+        assert_eq!(err, EnvelopeError::MissingNewline { index: 0 });
+        assert_eq!(
+            err.to_string(),
+            "Item 0 payload is not followed by a newline"
+        );
+    }
+
+    #[test]
+    fn test_to_bytes_round_trips() {
+        let body = b"{}\n{\"type\":\"attachment\",\"length\":3}\na\nb\n{\"type\":\"event\"}\n{}";
+        let envelope = Envelope::parse(body).unwrap();
+
+        let bytes = envelope.to_bytes();
+        let reparsed = Envelope::parse(&bytes).unwrap();
+        assert_eq!(reparsed.items.len(), 2);
+        assert_eq!(reparsed.items[0].payload.as_ref(), b"a\nb");
+        assert_eq!(reparsed.items[1].header.length, Some(2));
+        assert_eq!(reparsed.items[1].payload, envelope.items[1].payload);
+    }
 }
@@ -0,0 +1,552 @@
+const MINIDUMP_SIGNATURE: u32 = 0x504d_444d; // "MDMP"
+const MAX_STREAMS: u32 = 1024;
+const MAX_MODULES: u32 = 4096;
+
+const MODULE_LIST_STREAM: u32 = 4;
+const EXCEPTION_STREAM: u32 = 6;
+const SYSTEM_INFO_STREAM: u32 = 7;
+
+const MODULE_SIZE: usize = 108;
+const FIXED_FILE_INFO_SIGNATURE: u32 = 0xfeef_04bd;
+const CV_SIGNATURE_PDB70: u32 = 0x5344_5352; // "RSDS"
+const CV_SIGNATURE_ELF: u32 = 0x4c45_7042; // "BpEL"
+
+/// What a minidump (Breakpad, Crashpad or Windows) tells about a native crash
+/// before symbolication: OS, crash reason and loaded modules
+#[derive(Debug, Clone, Default)]
+pub struct Minidump {
+    pub system: Option<MinidumpSystemInfo>,
+    pub crash: Option<MinidumpCrash>,
+    pub modules: Vec<MinidumpModule>,
+}
+
+/// Operating system and CPU of the crashed process
+#[derive(Debug, Clone)]
+pub struct MinidumpSystemInfo {
+    pub os_name: String,
+    /// `major.minor.build`
+    pub os_version: String,
+    /// Service pack on Windows, build or kernel string elsewhere
+    pub os_build: Option<String>,
+    pub cpu_arch: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MinidumpCrash {
+    pub thread_id: u32,
+    pub code: u32,
+    pub address: u64,
+}
+
+/// A loaded executable or library
+#[derive(Debug, Clone)]
+pub struct MinidumpModule {
+    pub base_address: u64,
+    pub size: u64,
+    pub code_file: String,
+    pub code_id: Option<String>,
+    pub debug_file: Option<String>,
+    pub debug_id: Option<String>,
+    pub version: Option<String>,
+}
+
+impl MinidumpModule {
+    /// File name of the module without its directory
+    pub fn name(&self) -> &str {
+        self.code_file
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(&self.code_file)
+    }
+}
+
+impl Minidump {
+    /// Reads the streams this crate understands; `None` when the data is not a minidump.
+    /// Streams that are missing or truncated are left empty.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if read_u32(data, 0)? != MINIDUMP_SIGNATURE {
+            return None;
+        }
+        let stream_count = read_u32(data, 8)?.min(MAX_STREAMS);
+        let directory = read_u32(data, 12)? as usize;
+
+        let mut dump = Minidump::default();
+        for i in 0..stream_count as usize {
+            let entry = directory + i * 12;
+            let (Some(stream_type), Some(size), Some(rva)) = (
+                read_u32(data, entry),
+                read_u32(data, entry + 4),
+                read_u32(data, entry + 8),
+            ) else {
+                break;
+            };
+            let Some(stream) = data.get(rva as usize..(rva as usize).checked_add(size as usize)?)
+            else {
+                continue;
+            };
+
+            match stream_type {
+                SYSTEM_INFO_STREAM => dump.system = parse_system_info(data, stream),
+                EXCEPTION_STREAM => dump.crash = parse_exception(stream),
+                MODULE_LIST_STREAM => dump.modules = parse_modules(data, stream),
+                _ => {}
+            }
+        }
+
+        Some(dump)
+    }
+
+    /// Module whose address range contains `address`
+    pub fn module_at(&self, address: u64) -> Option<&MinidumpModule> {
+        self.modules
+            .iter()
+            .find(|m| address >= m.base_address && address - m.base_address < m.size)
+    }
+
+    /// Name of the exception code for the crashed OS, e.g. `SIGSEGV` or
+    /// `EXCEPTION_ACCESS_VIOLATION`; the hex code when unknown
+    pub fn crash_reason(&self) -> Option<String> {
+        let crash = self.crash.as_ref()?;
+        let os = self.system.as_ref().map(|s| s.os_name.as_str());
+        let name = match os {
+            Some("Windows") => windows_exception_name(crash.code),
+            Some("macOS") | Some("iOS") => mach_exception_name(crash.code),
+            Some(_) => signal_name(crash.code),
+            None => None,
+        };
+        Some(
+            name.map(str::to_string)
+                .unwrap_or_else(|| format!("0x{:08x}", crash.code)),
+        )
+    }
+}
+
+fn parse_system_info(data: &[u8], stream: &[u8]) -> Option<MinidumpSystemInfo> {
+    let arch = read_u16(stream, 0)?;
+    let major = read_u32(stream, 8)?;
+    let minor = read_u32(stream, 12)?;
+    let build = read_u32(stream, 16)?;
+    let platform = read_u32(stream, 20)?;
+    let csd_version = read_u32(stream, 24)
+        .and_then(|rva| read_string(data, rva as usize))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let os_name = match platform {
+        0..=2 => "Windows",
+        0x8101 => "macOS",
+        0x8102 => "iOS",
+        0x8201 => "Linux",
+        0x8202 => "Solaris",
+        0x8203 => "Android",
+        0x8206 => "Fuchsia",
+        _ => "Unknown",
+    };
+    let cpu_arch = match arch {
+        0 => "x86",
+        5 => "arm",
+        9 => "x86_64",
+        12 | 0x8003 => "arm64",
+        1 => "mips",
+        3 => "ppc",
+        0x8001 => "sparc",
+        0x8002 => "ppc64",
+        0x8004 => "mips64",
+        _ => "unknown",
+    };
+
+    Some(MinidumpSystemInfo {
+        os_name: os_name.to_string(),
+        os_version: format!("{}.{}.{}", major, minor, build),
+        os_build: csd_version,
+        cpu_arch: cpu_arch.to_string(),
+    })
+}
+
+fn parse_exception(stream: &[u8]) -> Option<MinidumpCrash> {
+    Some(MinidumpCrash {
+        thread_id: read_u32(stream, 0)?,
+        code: read_u32(stream, 8)?,
+        address: read_u64(stream, 24)?,
+    })
+}
+
+fn parse_modules(data: &[u8], stream: &[u8]) -> Vec<MinidumpModule> {
+    let count = read_u32(stream, 0).unwrap_or(0).min(MAX_MODULES) as usize;
+    (0..count)
+        .map_while(|i| stream.get(4 + i * MODULE_SIZE..4 + (i + 1) * MODULE_SIZE))
+        .filter_map(|raw| parse_module(data, raw))
+        .collect()
+}
+
+fn parse_module(data: &[u8], raw: &[u8]) -> Option<MinidumpModule> {
+    let base_address = read_u64(raw, 0)?;
+    let size = read_u32(raw, 8)? as u64;
+    let code_file = read_string(data, read_u32(raw, 20)? as usize)?;
+
+    let version = (read_u32(raw, 24)? == FIXED_FILE_INFO_SIGNATURE)
+        .then(|| {
+            let ms = read_u32(raw, 32)?;
+            let ls = read_u32(raw, 36)?;
+            Some(format!(
+                "{}.{}.{}.{}",
+                ms >> 16,
+                ms & 0xffff,
+                ls >> 16,
+                ls & 0xffff
+            ))
+        })
+        .flatten();
+
+    let cv_size = read_u32(raw, 76)? as usize;
+    let cv_rva = read_u32(raw, 80)? as usize;
+    let codeview = data.get(cv_rva..cv_rva.checked_add(cv_size)?);
+    let (code_id, debug_file, debug_id) = codeview.map(parse_codeview).unwrap_or_default();
+
+    Some(MinidumpModule {
+        base_address,
+        size,
+        code_file,
+        code_id,
+        debug_file,
+        debug_id,
+        version,
+    })
+}
+
+/// Code id, debug file and debug id from a CodeView record (PDB 7.0 or ELF build id)
+fn parse_codeview(record: &[u8]) -> (Option<String>, Option<String>, Option<String>) {
+    match read_u32(record, 0) {
+        Some(CV_SIGNATURE_PDB70) => {
+            let Some(guid) = record.get(4..20) else {
+                return (None, None, None);
+            };
+            let age = read_u32(record, 20).unwrap_or(0);
+            let debug_file = record
+                .get(24..)
+                .map(|name| name.split(|&b| b == 0).next().unwrap_or_default())
+                .map(|name| String::from_utf8_lossy(name).into_owned())
+                .filter(|name| !name.is_empty());
+            (
+                None,
+                debug_file,
+                Some(format!("{}-{:x}", format_guid(guid), age)),
+            )
+        }
+        Some(CV_SIGNATURE_ELF) => {
+            let build_id = &record[4..];
+            if build_id.is_empty() {
+                return (None, None, None);
+            }
+            // The debug id is the first 16 bytes of the build id read as a GUID
+            let mut guid = [0u8; 16];
+            let len = build_id.len().min(16);
+            guid[..len].copy_from_slice(&build_id[..len]);
+            (Some(hex::encode(build_id)), None, Some(format_guid(&guid)))
+        }
+        _ => (None, None, None),
+    }
+}
+
+/// Formats a little-endian GUID as `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`
+fn format_guid(bytes: &[u8]) -> String {
+    let data1 = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let data2 = u16::from_le_bytes([bytes[4], bytes[5]]);
+    let data3 = u16::from_le_bytes([bytes[6], bytes[7]]);
+    format!(
+        "{:08x}-{:04x}-{:04x}-{}-{}",
+        data1,
+        data2,
+        data3,
+        hex::encode(&bytes[8..10]),
+        hex::encode(&bytes[10..16])
+    )
+}
+
+/// MINIDUMP_STRING: byte length followed by UTF-16LE text
+fn read_string(data: &[u8], rva: usize) -> Option<String> {
+    let len = read_u32(data, rva)? as usize;
+    let bytes = data.get(rva + 4..(rva + 4).checked_add(len)?)?;
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset.checked_add(8)?)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn signal_name(code: u32) -> Option<&'static str> {
+    Some(match code {
+        4 => "SIGILL",
+        5 => "SIGTRAP",
+        6 => "SIGABRT",
+        7 => "SIGBUS",
+        8 => "SIGFPE",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        31 => "SIGSYS",
+        _ => return None,
+    })
+}
+
+fn mach_exception_name(code: u32) -> Option<&'static str> {
+    Some(match code {
+        1 => "EXC_BAD_ACCESS",
+        2 => "EXC_BAD_INSTRUCTION",
+        3 => "EXC_ARITHMETIC",
+        5 => "EXC_SOFTWARE",
+        6 => "EXC_BREAKPOINT",
+        10 => "EXC_CRASH",
+        11 => "EXC_RESOURCE",
+        12 => "EXC_GUARD",
+        _ => return None,
+    })
+}
+
+fn windows_exception_name(code: u32) -> Option<&'static str> {
+    Some(match code {
+        0x8000_0003 => "EXCEPTION_BREAKPOINT",
+        0xc000_0005 => "EXCEPTION_ACCESS_VIOLATION",
+        0xc000_001d => "EXCEPTION_ILLEGAL_INSTRUCTION",
+        0xc000_008c => "EXCEPTION_ARRAY_BOUNDS_EXCEEDED",
+        0xc000_0094 => "EXCEPTION_INT_DIVIDE_BY_ZERO",
+        0xc000_00fd => "EXCEPTION_STACK_OVERFLOW",
+        0xc000_0374 => "STATUS_HEAP_CORRUPTION",
+        0xc000_0409 => "STATUS_STACK_BUFFER_OVERRUN",
+        0xe06d_7363 => "EXCEPTION_CPP",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIRECTORY_ENTRIES: usize = 4;
+
+    /// Minidump laid out as header, stream directory, then appended data
+    struct DumpBuilder {
+        data: Vec<u8>,
+        streams: usize,
+    }
+
+    impl DumpBuilder {
+        fn new() -> Self {
+            let mut data = vec![0u8; 32 + DIRECTORY_ENTRIES * 12];
+            data[0..4].copy_from_slice(&MINIDUMP_SIGNATURE.to_le_bytes());
+            data[12..16].copy_from_slice(&32u32.to_le_bytes());
+            Self { data, streams: 0 }
+        }
+
+        fn append(&mut self, bytes: &[u8]) -> u32 {
+            let rva = self.data.len() as u32;
+            self.data.extend_from_slice(bytes);
+            rva
+        }
+
+        fn string(&mut self, value: &str) -> u32 {
+            let units: Vec<u8> = value.encode_utf16().flat_map(u16::to_le_bytes).collect();
+            let mut bytes = (units.len() as u32).to_le_bytes().to_vec();
+            bytes.extend_from_slice(&units);
+            self.append(&bytes)
+        }
+
+        fn stream(&mut self, stream_type: u32, bytes: &[u8]) {
+            let rva = self.append(bytes);
+            let entry = 32 + self.streams * 12;
+            put(&mut self.data, entry, stream_type);
+            put(&mut self.data, entry + 4, bytes.len() as u32);
+            put(&mut self.data, entry + 8, rva);
+            self.streams += 1;
+            put(&mut self.data, 8, self.streams as u32);
+        }
+    }
+
+    fn put(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn system_info(arch: u16, platform: u32) -> Vec<u8> {
+        let mut stream = vec![0u8; 56];
+        stream[0..2].copy_from_slice(&arch.to_le_bytes());
+        put(&mut stream, 8, 6);
+        put(&mut stream, 12, 1);
+        put(&mut stream, 16, 42);
+        put(&mut stream, 20, platform);
+        stream
+    }
+
+    fn module(base: u64, size: u32, name_rva: u32, cv: Option<(u32, u32)>) -> Vec<u8> {
+        let mut raw = vec![0u8; MODULE_SIZE];
+        raw[0..8].copy_from_slice(&base.to_le_bytes());
+        put(&mut raw, 8, size);
+        put(&mut raw, 20, name_rva);
+        put(&mut raw, 24, FIXED_FILE_INFO_SIGNATURE);
+        put(&mut raw, 32, (1 << 16) | 2);
+        put(&mut raw, 36, (3 << 16) | 4);
+        if let Some((cv_size, cv_rva)) = cv {
+            put(&mut raw, 76, cv_size);
+            put(&mut raw, 80, cv_rva);
+        }
+        raw
+    }
+
+    #[test]
+    fn test_parses_system_info_crash_and_modules() {
+        let mut dump = DumpBuilder::new();
+
+        let guid: Vec<u8> = (1..=16).collect();
+        let mut pdb = CV_SIGNATURE_PDB70.to_le_bytes().to_vec();
+        pdb.extend_from_slice(&guid);
+        pdb.extend_from_slice(&2u32.to_le_bytes());
+        pdb.extend_from_slice(b"app.pdb\0");
+        let pdb_rva = dump.append(&pdb);
+
+        let mut elf = CV_SIGNATURE_ELF.to_le_bytes().to_vec();
+        elf.extend((0..20).collect::<Vec<u8>>());
+        let elf_rva = dump.append(&elf);
+
+        let app = dump.string("C:\\Program Files\\App\\app.exe");
+        let lib = dump.string("/usr/lib/libc.so.6");
+
+        let mut modules = 2u32.to_le_bytes().to_vec();
+        modules.extend(module(
+            0x1000,
+            0x1000,
+            app,
+            Some((pdb.len() as u32, pdb_rva)),
+        ));
+        modules.extend(module(
+            0x7000,
+            0x500,
+            lib,
+            Some((elf.len() as u32, elf_rva)),
+        ));
+        dump.stream(MODULE_LIST_STREAM, &modules);
+        dump.stream(SYSTEM_INFO_STREAM, &system_info(0x8002, 0x8201));
+
+        let mut exception = vec![0u8; 168];
+        put(&mut exception, 0, 77);
+        put(&mut exception, 8, 11);
+        exception[24..32].copy_from_slice(&0x7123u64.to_le_bytes());
+        dump.stream(EXCEPTION_STREAM, &exception);
+
+        let parsed = Minidump::parse(&dump.data).unwrap();
+        let system = parsed.system.as_ref().unwrap();
+        assert_eq!(system.os_name, "Linux");
+        assert_eq!(system.os_version, "6.1.42");
+        assert_eq!(system.cpu_arch, "ppc64");
+        assert_eq!(parsed.crash.as_ref().unwrap().thread_id, 77);
+        assert_eq!(parsed.crash_reason().as_deref(), Some("SIGSEGV"));
+
+        assert_eq!(parsed.modules.len(), 2);
+        let app = &parsed.modules[0];
+        assert_eq!(app.name(), "app.exe");
+        assert_eq!(app.version.as_deref(), Some("1.2.3.4"));
+        assert_eq!(app.debug_file.as_deref(), Some("app.pdb"));
+        assert_eq!(
+            app.debug_id.as_deref(),
+            Some("04030201-0605-0807-090a-0b0c0d0e0f10-2")
+        );
+        assert_eq!(app.code_id, None);
+
+        let lib = parsed.module_at(0x7123).unwrap();
+        assert_eq!(lib.name(), "libc.so.6");
+        assert_eq!(
+            lib.code_id.as_deref(),
+            Some("000102030405060708090a0b0c0d0e0f10111213")
+        );
+        assert_eq!(
+            lib.debug_id.as_deref(),
+            Some("03020100-0504-0706-0809-0a0b0c0d0e0f")
+        );
+        assert!(parsed.module_at(0x7500).is_none());
+    }
+
+    #[test]
+    fn test_cpu_architectures() {
+        for (arch, name) in [
+            (0, "x86"),
+            (3, "ppc"),
+            (9, "x86_64"),
+            (12, "arm64"),
+            (0x8001, "sparc"),
+            (0x8002, "ppc64"),
+            (0x8003, "arm64"),
+            (0x8004, "mips64"),
+            (0x7fff, "unknown"),
+        ] {
+            let mut dump = DumpBuilder::new();
+            dump.stream(SYSTEM_INFO_STREAM, &system_info(arch, 2));
+            let parsed = Minidump::parse(&dump.data).unwrap();
+            assert_eq!(parsed.system.unwrap().cpu_arch, name, "arch {:#x}", arch);
+        }
+    }
+
+    #[test]
+    fn test_truncated_stream_directory() {
+        let mut dump = DumpBuilder::new();
+        dump.stream(SYSTEM_INFO_STREAM, &system_info(9, 2));
+        let mut data = dump.data;
+
+        // A stream past the end of the data is skipped
+        put(&mut data, 8, 3);
+        put(&mut data, 44, EXCEPTION_STREAM);
+        put(&mut data, 48, 168);
+        put(&mut data, 52, 0x10_0000);
+        let parsed = Minidump::parse(&data).unwrap();
+        assert!(parsed.system.is_some());
+        assert!(parsed.crash.is_none());
+
+        // A directory cut short by the end of the data keeps the streams before the cut
+        let entry = data[32..44].to_vec();
+        let directory = data.len() as u32;
+        data.extend_from_slice(&entry);
+        data.extend_from_slice(&[0u8; 6]);
+        put(&mut data, 8, 5);
+        put(&mut data, 12, directory);
+        let parsed = Minidump::parse(&data).unwrap();
+        assert_eq!(parsed.system.unwrap().cpu_arch, "x86_64");
+
+        // A directory outside the data holds no streams
+        put(&mut data, 12, 0x10_0000);
+        let parsed = Minidump::parse(&data).unwrap();
+        assert!(parsed.system.is_none());
+        assert!(parsed.modules.is_empty());
+    }
+
+    #[test]
+    fn test_module_list_truncated_mid_module() {
+        let mut dump = DumpBuilder::new();
+        let name = dump.string("app");
+        let mut modules = 3u32.to_le_bytes().to_vec();
+        modules.extend(module(0x1000, 0x100, name, None));
+        modules.extend(&module(0x2000, 0x100, name, None)[..50]);
+        dump.stream(MODULE_LIST_STREAM, &modules);
+
+        let parsed = Minidump::parse(&dump.data).unwrap();
+        assert_eq!(parsed.modules.len(), 1);
+        assert_eq!(parsed.modules[0].debug_id, None);
+    }
+
+    #[test]
+    fn test_rejects_other_data() {
+        assert!(Minidump::parse(b"").is_none());
+        assert!(Minidump::parse(b"MDMP").is_none());
+        assert!(Minidump::parse(b"PK\x03\x04 not a minidump at all").is_none());
+    }
+}
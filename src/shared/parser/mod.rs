@@ -1,7 +1,19 @@
 mod envelope;
+mod minidump;
+mod multipart;
 mod sentry_auth;
+mod sentry_check_in;
+mod sentry_client_report;
 mod sentry_session;
+mod sentry_user_feedback;
 
-pub use envelope::{Envelope, EnvelopeHeader, EnvelopeItem, ItemHeader};
+pub use envelope::{Envelope, EnvelopeError, EnvelopeHeader, EnvelopeItem, ItemHeader};
+pub use minidump::{Minidump, MinidumpCrash, MinidumpModule, MinidumpSystemInfo};
+pub use multipart::{
+    MINIDUMP_FIELD, MultipartPart, minidump_envelope, multipart_boundary, parse_multipart,
+};
 pub use sentry_auth::{SentryAuth, SentryDsn};
-pub use sentry_session::SentrySession;
+pub use sentry_check_in::{SentryCheckIn, SentryMonitorConfig, SentryMonitorSchedule};
+pub use sentry_client_report::{DiscardedEvents, SentryClientReport};
+pub use sentry_session::{SentrySession, SentrySessionAggregates, SessionAggregateBucket};
+pub use sentry_user_feedback::{SentryUserReport, normalize_event_id};
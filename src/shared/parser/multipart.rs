@@ -0,0 +1,347 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use super::envelope::{Envelope, EnvelopeHeader, EnvelopeItem, ItemHeader};
+
+/// Form field holding the minidump in uploads from Breakpad, Crashpad and the native SDKs
+pub const MINIDUMP_FIELD: &str = "upload_file_minidump";
+
+/// One part of a `multipart/form-data` body
+#[derive(Debug, Clone)]
+pub struct MultipartPart {
+    pub name: String,
+    /// Set for file uploads
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+/// Boundary parameter of a `multipart/form-data` content type
+pub fn multipart_boundary(content_type: &str) -> Option<&str> {
+    let (mime, params) = content_type.split_once(';')?;
+    if !mime.trim().eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params
+        .split(';')
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+        .filter(|value| !value.is_empty())
+}
+
+/// Splits a `multipart/form-data` body into its parts. Returns `None` when the
+/// body does not start with the boundary or a part has no name.
+pub fn parse_multipart(body: &[u8], boundary: &str) -> Option<Vec<MultipartPart>> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let mut rest = body.strip_prefix(delimiter.as_slice())?;
+    let mut parts = Vec::new();
+
+    // Each part ends with CRLF followed by the next delimiter
+    let mut next_delimiter = b"\r\n".to_vec();
+    next_delimiter.extend_from_slice(&delimiter);
+
+    loop {
+        if rest.starts_with(b"--") {
+            return Some(parts);
+        }
+        rest = rest.strip_prefix(b"\r\n")?;
+
+        let headers_end = find(rest, b"\r\n\r\n")?;
+        let headers = std::str::from_utf8(&rest[..headers_end]).ok()?;
+        let content = &rest[headers_end + 4..];
+        let content_end = find(content, &next_delimiter)?;
+
+        let mut name = None;
+        let mut filename = None;
+        let mut content_type = None;
+        for line in headers.split("\r\n") {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            if key.trim().eq_ignore_ascii_case("content-disposition") {
+                name = disposition_param(value, "name");
+                filename = disposition_param(value, "filename");
+            } else if key.trim().eq_ignore_ascii_case("content-type") {
+                content_type = Some(value.trim().to_string());
+            }
+        }
+
+        parts.push(MultipartPart {
+            name: name?,
+            filename,
+            content_type,
+            data: content[..content_end].to_vec(),
+        });
+        rest = &content[content_end + next_delimiter.len()..];
+    }
+}
+
+/// Value of `key=value` or `key="value"` in a Content-Disposition header. A
+/// quoted value may contain `;` and `\"`.
+fn disposition_param(header: &str, key: &str) -> Option<String> {
+    // Skip the disposition type (`form-data`)
+    let mut rest = header.split_once(';')?.1;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ';' || c.is_whitespace());
+        if rest.is_empty() {
+            return None;
+        }
+        let name_end = rest.find(['=', ';']).unwrap_or(rest.len());
+        let name = rest[..name_end].trim();
+        rest = &rest[name_end..];
+        let Some(after_eq) = rest.strip_prefix('=') else {
+            continue;
+        };
+
+        let (value, after_value) = match after_eq.trim_start().strip_prefix('"') {
+            Some(quoted) => unquote(quoted)?,
+            None => {
+                let end = after_eq.find(';').unwrap_or(after_eq.len());
+                (after_eq[..end].trim().to_string(), &after_eq[end..])
+            }
+        };
+        if name.eq_ignore_ascii_case(key) {
+            return Some(value);
+        }
+        rest = after_value;
+    }
+}
+
+/// Reads a quoted string up to its closing quote, undoing `\` escapes.
+/// Returns the value and the text after the closing quote.
+fn unquote(quoted: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &quoted[i + 1..])),
+            '\\' => value.push(chars.next()?.1),
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Turns a minidump upload into an envelope: the event comes from the `sentry`
+/// field (JSON) and `sentry[key][subkey]` fields, other text fields become
+/// `extra`, the minidump and any other file become attachments. Returns `None`
+/// without a minidump.
+pub fn minidump_envelope(parts: Vec<MultipartPart>) -> Option<Envelope<'static>> {
+    if !parts
+        .iter()
+        .any(|p| p.name == MINIDUMP_FIELD && !p.data.is_empty())
+    {
+        return None;
+    }
+
+    let mut event = Map::new();
+    let mut attachments = Vec::new();
+    for part in parts {
+        if part.name == MINIDUMP_FIELD || part.filename.is_some() {
+            attachments.push(part);
+            continue;
+        }
+        let text = String::from_utf8_lossy(&part.data).into_owned();
+        if part.name == "sentry" {
+            if let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(&text) {
+                event.extend(fields);
+            }
+        } else if let Some(path) = bracket_path(&part.name) {
+            insert_path(&mut event, &path, Value::String(text));
+        } else {
+            insert_path(
+                &mut event,
+                &["extra", part.name.as_str()],
+                Value::String(text),
+            );
+        }
+    }
+
+    let event_id = event
+        .get("event_id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+    event.insert("event_id".to_string(), Value::String(event_id.clone()));
+
+    let mut items = vec![EnvelopeItem {
+        header: item_header("event", None, HashMap::new()),
+        payload: Cow::Owned(serde_json::to_vec(&event).ok()?),
+    }];
+    for part in attachments {
+        let (default_name, attachment_type) = if part.name == MINIDUMP_FIELD {
+            ("minidump.dmp", "event.minidump")
+        } else {
+            (part.name.as_str(), "event.attachment")
+        };
+        let extra = HashMap::from([
+            (
+                "filename".to_string(),
+                Value::String(part.filename.clone().unwrap_or(default_name.to_string())),
+            ),
+            (
+                "attachment_type".to_string(),
+                Value::String(attachment_type.to_string()),
+            ),
+        ]);
+        items.push(EnvelopeItem {
+            header: item_header("attachment", part.content_type, extra),
+            payload: Cow::Owned(part.data),
+        });
+    }
+
+    Some(Envelope {
+        header: EnvelopeHeader {
+            event_id: Some(event_id),
+            dsn: None,
+            sdk: None,
+            sent_at: None,
+            extra: HashMap::new(),
+        },
+        items,
+    })
+}
+
+fn item_header(
+    item_type: &str,
+    content_type: Option<String>,
+    extra: HashMap<String, Value>,
+) -> ItemHeader {
+    ItemHeader {
+        item_type: item_type.to_string(),
+        length: None,
+        content_type,
+        extra,
+    }
+}
+
+/// `sentry[tags][flavor]` as `["tags", "flavor"]`
+fn bracket_path(name: &str) -> Option<Vec<&str>> {
+    let rest = name.strip_prefix("sentry[")?.strip_suffix(']')?;
+    let path: Vec<&str> = rest.split("][").collect();
+    path.iter().all(|key| !key.is_empty()).then_some(path)
+}
+
+/// Sets a nested value, replacing anything in the way that is not an object
+fn insert_path(target: &mut Map<String, Value>, path: &[&str], value: Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut current = target;
+    for key in parents {
+        let entry = current
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        match entry {
+            Value::Object(map) => current = map,
+            _ => return,
+        }
+    }
+    current.insert(last.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(parts: &[&str]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for part in parts {
+            body.extend_from_slice(b"--XyZ\r\n");
+            body.extend_from_slice(part.as_bytes());
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(b"--XyZ--\r\n");
+        body
+    }
+
+    #[test]
+    fn test_boundary_from_content_type() {
+        assert_eq!(
+            multipart_boundary("multipart/form-data; boundary=XyZ"),
+            Some("XyZ")
+        );
+        assert_eq!(
+            multipart_boundary("Multipart/Form-Data; charset=utf-8; BOUNDARY=\"a b\""),
+            Some("a b")
+        );
+        assert_eq!(multipart_boundary("multipart/form-data; boundary="), None);
+        assert_eq!(multipart_boundary("multipart/form-data"), None);
+        assert_eq!(multipart_boundary("multipart/mixed; boundary=XyZ"), None);
+    }
+
+    #[test]
+    fn test_parts_end_at_crlf_and_boundary() {
+        let body = form(&[
+            "Content-Disposition: form-data; name=\"sentry[release]\"\r\n\r\n1.0",
+            // Line breaks and a boundary not preceded by CRLF stay in the data
+            "Content-Disposition: form-data; name=upload_file_minidump; filename=\"crash.dmp\"\r\nContent-Type: application/octet-stream\r\n\r\nMDMP\r\n\r\nx--XyZ\r\n",
+        ]);
+
+        let parts = parse_multipart(&body, "XyZ").unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name, "sentry[release]");
+        assert_eq!(parts[0].filename, None);
+        assert_eq!(parts[0].data, b"1.0");
+        assert_eq!(parts[1].name, MINIDUMP_FIELD);
+        assert_eq!(parts[1].filename.as_deref(), Some("crash.dmp"));
+        assert_eq!(
+            parts[1].content_type.as_deref(),
+            Some("application/octet-stream")
+        );
+        assert_eq!(parts[1].data, b"MDMP\r\n\r\nx--XyZ\r\n");
+    }
+
+    #[test]
+    fn test_missing_closing_boundary() {
+        let body = b"--XyZ\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nvalue";
+        assert!(parse_multipart(body, "XyZ").is_none());
+
+        // Parts with bare LF line breaks are not recognized
+        let body = b"--XyZ\nContent-Disposition: form-data; name=\"a\"\n\nvalue\n--XyZ--\n";
+        assert!(parse_multipart(body, "XyZ").is_none());
+    }
+
+    #[test]
+    fn test_body_must_start_with_the_boundary() {
+        let mut body = b"preamble\r\n".to_vec();
+        body.extend(form(&[
+            "Content-Disposition: form-data; name=\"a\"\r\n\r\n1",
+        ]));
+        assert!(parse_multipart(&body, "XyZ").is_none());
+        assert!(parse_multipart(&form(&[]), "XyZ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_part_without_name_is_rejected() {
+        let body = form(&["Content-Disposition: form-data; filename=\"a.txt\"\r\n\r\n1"]);
+        assert!(parse_multipart(&body, "XyZ").is_none());
+    }
+
+    #[test]
+    fn test_quoted_disposition_params() {
+        let header = r#" form-data; name="file"; filename="a;b \"c\".dmp""#;
+        assert_eq!(disposition_param(header, "name").as_deref(), Some("file"));
+        assert_eq!(
+            disposition_param(header, "filename").as_deref(),
+            Some("a;b \"c\".dmp")
+        );
+        assert_eq!(
+            disposition_param(" form-data; name=plain ; x=1", "name").as_deref(),
+            Some("plain")
+        );
+        assert_eq!(disposition_param(" form-data; name=\"open", "name"), None);
+    }
+}
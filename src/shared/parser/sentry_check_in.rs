@@ -0,0 +1,92 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::normalize_event_id;
+
+/// Payload of a `check_in` envelope item: one run of a cron job reporting
+/// that it started or finished
+#[derive(Debug, Clone, Deserialize)]
+pub struct SentryCheckIn {
+    /// Set by the SDK so the finishing check-in updates the in-progress one
+    #[serde(default)]
+    pub check_in_id: Option<String>,
+
+    pub monitor_slug: String,
+
+    /// in_progress, ok or error
+    pub status: String,
+
+    /// Run time in seconds, sent with the finishing check-in
+    #[serde(default)]
+    pub duration: Option<f64>,
+
+    #[serde(default)]
+    pub release: Option<String>,
+
+    #[serde(default)]
+    pub environment: Option<String>,
+
+    /// Creates or updates the monitor when present
+    #[serde(default)]
+    pub monitor_config: Option<SentryMonitorConfig>,
+}
+
+/// Schedule of a monitor as sent with its check-ins
+#[derive(Debug, Clone, Deserialize)]
+pub struct SentryMonitorConfig {
+    pub schedule: SentryMonitorSchedule,
+
+    /// Minutes a check-in may be late
+    #[serde(default)]
+    pub checkin_margin: Option<i32>,
+
+    /// Minutes a run may stay in progress
+    #[serde(default)]
+    pub max_runtime: Option<i32>,
+
+    /// IANA timezone of a crontab schedule
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SentryMonitorSchedule {
+    /// crontab or interval
+    #[serde(rename = "type")]
+    pub schedule_type: String,
+
+    /// Crontab expression (string) or number of units (number)
+    pub value: Value,
+
+    /// Interval unit: minute, hour, day, week, month, year
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+impl SentryCheckIn {
+    /// Returns None when the payload is not a check-in or has no monitor slug
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut check_in: Self = serde_json::from_slice(data).ok()?;
+        check_in.monitor_slug = check_in.monitor_slug.trim().to_string();
+        if check_in.monitor_slug.is_empty() {
+            return None;
+        }
+        check_in.check_in_id = check_in
+            .check_in_id
+            .as_deref()
+            .map(normalize_event_id)
+            .filter(|id| !id.is_empty());
+        Some(check_in)
+    }
+}
+
+impl SentryMonitorSchedule {
+    /// `value` as text, whether the SDK sent a string or a number
+    pub fn value_text(&self) -> Option<String> {
+        match &self.value {
+            Value::String(s) => Some(s.trim().to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
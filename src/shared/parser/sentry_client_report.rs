@@ -0,0 +1,46 @@
+use chrono::{DateTime, NaiveDateTime};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Payload of a `client_report` envelope item: events the SDK discarded
+/// locally since its last report
+#[derive(Debug, Clone, Deserialize)]
+pub struct SentryClientReport {
+    /// When the report was created (RFC 3339 string or seconds since epoch)
+    pub timestamp: Option<Value>,
+
+    #[serde(default)]
+    pub discarded_events: Vec<DiscardedEvents>,
+}
+
+/// Events of one category discarded for the same reason
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscardedEvents {
+    /// Why they were dropped: queue_overflow, ratelimit_backoff, network_error, sample_rate, ...
+    pub reason: String,
+
+    /// Data category: error, transaction, session, attachment, ...
+    pub category: String,
+
+    pub quantity: i32,
+}
+
+impl SentryClientReport {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        serde_json::from_slice(data).ok()
+    }
+
+    /// `timestamp` in UTC, or None when it is missing or invalid
+    pub fn created_at(&self) -> Option<NaiveDateTime> {
+        match self.timestamp.as_ref()? {
+            Value::String(s) => DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(|dt| dt.naive_utc()),
+            Value::Number(n) => {
+                let secs = n.as_f64()?;
+                DateTime::from_timestamp_millis((secs * 1000.0) as i64).map(|dt| dt.naive_utc())
+            }
+            _ => None,
+        }
+    }
+}
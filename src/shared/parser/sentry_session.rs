@@ -1,3 +1,4 @@
+use chrono::{DateTime, NaiveDateTime};
 use serde::Deserialize;
 
 /// Represents a Sentry session payload
@@ -24,6 +25,12 @@ pub struct SentrySession {
     #[serde(default = "default_status")]
     pub status: String,
 
+    /// Distinct id of the user (user id, email or install id)
+    pub did: Option<String>,
+
+    /// Session length in seconds, sent with the final update
+    pub duration: Option<f64>,
+
     /// Session attributes
     #[serde(default)]
     pub attrs: SessionAttrs,
@@ -46,4 +53,64 @@ impl SentrySession {
     pub fn parse(data: &[u8]) -> Option<Self> {
         serde_json::from_slice(data).ok()
     }
+
+    /// `started` in UTC, or None when it is not an RFC 3339 timestamp
+    pub fn started_at(&self) -> Option<NaiveDateTime> {
+        parse_timestamp(&self.started)
+    }
+
+    /// `timestamp` in UTC, falling back to `started` when missing or invalid
+    pub fn updated_at(&self) -> Option<NaiveDateTime> {
+        self.timestamp
+            .as_deref()
+            .and_then(parse_timestamp)
+            .or_else(|| self.started_at())
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.naive_utc())
+}
+
+/// Payload of a `sessions` envelope item: pre-aggregated session counts, sent by
+/// server-side and browser SDKs instead of one `session` item per session
+#[derive(Debug, Clone, Deserialize)]
+pub struct SentrySessionAggregates {
+    pub aggregates: Vec<SessionAggregateBucket>,
+
+    /// Release and environment shared by every bucket
+    #[serde(default)]
+    pub attrs: SessionAttrs,
+}
+
+/// Sessions that started in the same period (usually a minute), by outcome
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionAggregateBucket {
+    /// Start of the period (ISO 8601 timestamp)
+    pub started: String,
+
+    /// Distinct id when the SDK aggregates per user
+    pub did: Option<String>,
+
+    /// Sessions that ended normally
+    #[serde(default)]
+    pub exited: i32,
+
+    /// Sessions with errors that did not crash
+    #[serde(default)]
+    pub errored: i32,
+
+    #[serde(default)]
+    pub abnormal: i32,
+
+    #[serde(default)]
+    pub crashed: i32,
+}
+
+impl SentrySessionAggregates {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        serde_json::from_slice(data).ok()
+    }
 }
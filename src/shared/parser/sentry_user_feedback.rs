@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+/// Feedback a user left about an event. Parsed from `user_report` items and the
+/// user-feedback endpoint (same JSON), or from the contexts of `feedback` items.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SentryUserReport {
+    /// Event the feedback is about
+    pub event_id: Option<String>,
+
+    pub name: Option<String>,
+
+    pub email: Option<String>,
+
+    #[serde(default)]
+    pub comments: String,
+}
+
+/// Payload of a `feedback` item: an event whose `contexts.feedback` holds the form
+#[derive(Debug, Deserialize)]
+struct FeedbackEvent {
+    contexts: FeedbackContexts,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackContexts {
+    feedback: FeedbackContext,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedbackContext {
+    #[serde(default)]
+    message: String,
+    contact_email: Option<String>,
+    name: Option<String>,
+    associated_event_id: Option<String>,
+}
+
+impl SentryUserReport {
+    /// Parses a `user_report` item or user-feedback request body.
+    /// Returns None when the JSON is invalid or `comments` is blank.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        serde_json::from_slice::<Self>(data)
+            .ok()
+            .map(Self::normalized)
+            .filter(|r| !r.comments.is_empty())
+    }
+
+    /// Parses a `feedback` item. Returns None when it has no message.
+    pub fn parse_feedback(data: &[u8]) -> Option<Self> {
+        let feedback = serde_json::from_slice::<FeedbackEvent>(data)
+            .ok()?
+            .contexts
+            .feedback;
+        Some(
+            Self {
+                event_id: feedback.associated_event_id,
+                name: feedback.name,
+                email: feedback.contact_email,
+                comments: feedback.message,
+            }
+            .normalized(),
+        )
+        .filter(|r| !r.comments.is_empty())
+    }
+
+    /// Trims every field and drops empty optional ones
+    fn normalized(self) -> Self {
+        fn non_empty(value: Option<String>) -> Option<String> {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        }
+
+        Self {
+            event_id: non_empty(self.event_id).map(|id| normalize_event_id(&id)),
+            name: non_empty(self.name),
+            email: non_empty(self.email),
+            comments: self.comments.trim().to_string(),
+        }
+    }
+}
+
+/// Event id in the form SDKs send in event payloads: lowercase hex without dashes
+pub fn normalize_event_id(event_id: &str) -> String {
+    event_id.replace('-', "").to_lowercase()
+}
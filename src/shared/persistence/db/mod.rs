@@ -5,8 +5,12 @@ pub mod schema;
 
 pub use connection::{DbConnection, DbPool, establish_connection_pool, run_migrations};
 pub use repositories::{
-    AnalyticsRepository, ArchiveRepository, DeviceSpecsParams, NewReport, ProjectRepository,
-    QueueErrorRepository, QueueRepository, Repositories, SessionRepository,
-    UnwrapSessionEnvironmentRepository, UnwrapSessionReleaseRepository,
-    UnwrapSessionStatusRepository,
+    AnalyticsRepository, ArchiveRepository, AttachmentRepository, DeviceSpecsParams, IssueCursor,
+    IssueFilter, IssueRepository, IssueSort, MonitorRepository, NewAttachment, NewReport,
+    NewReportBreadcrumb, NewReportException, NewReportModule, NewReportThread, ProjectRepository,
+    QueueErrorRepository, QueueRepository, ReleaseHealthBucket, ReleaseHealthFilter,
+    ReleaseHealthRepository, ReleaseRepository, ReportTagRepository, Repositories,
+    SessionAggregateRepository, SessionRepository, TagDistribution, TagValueCount,
+    TransactionLatencyBucket, TransactionLatencyFilter, TransactionLatencyRepository,
+    UnwrapSessionEnvironmentRepository, UnwrapSessionStatusRepository, UserFeedbackRepository,
 };
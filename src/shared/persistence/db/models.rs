@@ -2,13 +2,18 @@ use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
 use super::schema::{
-    archive, bucket_rate_limit_dsn, bucket_rate_limit_global, bucket_rate_limit_subnet,
-    bucket_request_latency, issue, project, queue, queue_error, report, session, unwrap_app_build,
+    archive, attachment, attachment_blob, bucket_client_discard, bucket_rate_limit_dsn,
+    bucket_rate_limit_global, bucket_rate_limit_subnet, bucket_release_health,
+    bucket_request_latency, bucket_transaction_latency, issue, monitor, monitor_checkin, project,
+    queue, queue_error, release, report, report_breadcrumb, report_exception, report_module,
+    report_tag, report_thread, session, session_aggregate, transaction, unwrap_app_build,
     unwrap_app_name, unwrap_app_version, unwrap_brand, unwrap_chipset, unwrap_connection_type,
     unwrap_device_specs, unwrap_environment, unwrap_exception_message, unwrap_exception_type,
-    unwrap_locale_code, unwrap_manufacturer, unwrap_model, unwrap_orientation, unwrap_os_name,
-    unwrap_os_version, unwrap_platform, unwrap_session_environment, unwrap_session_release,
-    unwrap_session_status, unwrap_stacktrace, unwrap_timezone, unwrap_user,
+    unwrap_level, unwrap_locale_code, unwrap_manufacturer, unwrap_model, unwrap_orientation,
+    unwrap_os_name, unwrap_os_version, unwrap_platform, unwrap_sdk_name, unwrap_sdk_version,
+    unwrap_session_environment, unwrap_session_status, unwrap_stacktrace, unwrap_tag_key,
+    unwrap_tag_value, unwrap_timezone, unwrap_transaction_name, unwrap_transaction_op, unwrap_user,
+    user_feedback,
 };
 
 // ============================================
@@ -40,6 +45,7 @@ pub struct ArchiveModel {
     pub compressed_payload: Vec<u8>,
     pub original_size: Option<i32>,
     pub created_at: NaiveDateTime,
+    pub compression: String,
 }
 
 #[derive(Queryable, Selectable, Debug)]
@@ -48,11 +54,13 @@ pub struct QueueModel {
     pub id: i32,
     pub archive_hash: String,
     pub created_at: NaiveDateTime,
+    pub project_id: i32,
 }
 
 #[derive(Insertable, Debug)]
 #[diesel(table_name = queue)]
 pub struct NewQueueModel {
+    pub project_id: i32,
     pub archive_hash: String,
     pub created_at: NaiveDateTime,
 }
@@ -64,16 +72,40 @@ pub struct QueueErrorModel {
     pub archive_hash: String,
     pub error: String,
     pub created_at: NaiveDateTime,
+    pub project_id: i32,
 }
 
 #[derive(Insertable, Debug)]
 #[diesel(table_name = queue_error)]
 pub struct NewQueueErrorModel {
+    pub project_id: i32,
     pub archive_hash: String,
     pub error: String,
     pub created_at: NaiveDateTime,
 }
 
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = release)]
+pub struct ReleaseModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub version: String,
+    /// Empty when the release has no build
+    pub build: String,
+    pub first_seen: NaiveDateTime,
+    pub last_seen: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = release)]
+pub struct NewReleaseModel {
+    pub project_id: i32,
+    pub version: String,
+    pub build: String,
+    pub first_seen: NaiveDateTime,
+    pub last_seen: NaiveDateTime,
+}
+
 // ============================================
 // SESSION MODELS
 // ============================================
@@ -91,19 +123,6 @@ pub struct NewUnwrapSessionStatusModel {
     pub value: String,
 }
 
-#[derive(Queryable, Selectable, Debug, Clone)]
-#[diesel(table_name = unwrap_session_release)]
-pub struct UnwrapSessionReleaseModel {
-    pub id: i32,
-    pub value: String,
-}
-
-#[derive(Insertable, Debug)]
-#[diesel(table_name = unwrap_session_release)]
-pub struct NewUnwrapSessionReleaseModel {
-    pub value: String,
-}
-
 #[derive(Queryable, Selectable, Debug, Clone)]
 #[diesel(table_name = unwrap_session_environment)]
 pub struct UnwrapSessionEnvironmentModel {
@@ -124,12 +143,14 @@ pub struct SessionModel {
     pub project_id: i32,
     pub sid: String,
     pub init: i32,
-    pub started_at: String,
-    pub timestamp: String,
+    pub started_at: NaiveDateTime,
+    pub timestamp: NaiveDateTime,
     pub errors: i32,
     pub status_id: i32,
     pub release_id: Option<i32>,
     pub environment_id: Option<i32>,
+    pub distinct_id: Option<String>,
+    pub duration: Option<f64>,
 }
 
 #[derive(Insertable, Debug)]
@@ -138,12 +159,44 @@ pub struct NewSessionModel {
     pub project_id: i32,
     pub sid: String,
     pub init: i32,
-    pub started_at: String,
-    pub timestamp: String,
+    pub started_at: NaiveDateTime,
+    pub timestamp: NaiveDateTime,
     pub errors: i32,
     pub status_id: i32,
     pub release_id: Option<i32>,
     pub environment_id: Option<i32>,
+    pub distinct_id: Option<String>,
+    pub duration: Option<f64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = session_aggregate)]
+pub struct SessionAggregateModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub started_at: NaiveDateTime,
+    pub release_id: Option<i32>,
+    pub environment_id: Option<i32>,
+    pub distinct_id: Option<String>,
+    pub exited: i32,
+    pub errored: i32,
+    pub abnormal: i32,
+    pub crashed: i32,
+    pub received_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = session_aggregate)]
+pub struct NewSessionAggregateModel {
+    pub project_id: i32,
+    pub started_at: NaiveDateTime,
+    pub release_id: Option<i32>,
+    pub environment_id: Option<i32>,
+    pub distinct_id: Option<String>,
+    pub exited: i32,
+    pub errored: i32,
+    pub abnormal: i32,
+    pub crashed: i32,
 }
 
 // ============================================
@@ -371,6 +424,97 @@ pub struct NewUnwrapExceptionTypeModel {
     pub value: String,
 }
 
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = unwrap_level)]
+pub struct UnwrapLevelModel {
+    pub id: i32,
+    pub value: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = unwrap_level)]
+pub struct NewUnwrapLevelModel {
+    pub value: String,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = unwrap_transaction_name)]
+pub struct UnwrapTransactionNameModel {
+    pub id: i32,
+    pub value: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = unwrap_transaction_name)]
+pub struct NewUnwrapTransactionNameModel {
+    pub value: String,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = unwrap_transaction_op)]
+pub struct UnwrapTransactionOpModel {
+    pub id: i32,
+    pub value: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = unwrap_transaction_op)]
+pub struct NewUnwrapTransactionOpModel {
+    pub value: String,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = unwrap_sdk_name)]
+pub struct UnwrapSdkNameModel {
+    pub id: i32,
+    pub value: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = unwrap_sdk_name)]
+pub struct NewUnwrapSdkNameModel {
+    pub value: String,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = unwrap_sdk_version)]
+pub struct UnwrapSdkVersionModel {
+    pub id: i32,
+    pub value: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = unwrap_sdk_version)]
+pub struct NewUnwrapSdkVersionModel {
+    pub value: String,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = unwrap_tag_key)]
+pub struct UnwrapTagKeyModel {
+    pub id: i32,
+    pub value: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = unwrap_tag_key)]
+pub struct NewUnwrapTagKeyModel {
+    pub value: String,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = unwrap_tag_value)]
+pub struct UnwrapTagValueModel {
+    pub id: i32,
+    pub value: String,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = unwrap_tag_value)]
+pub struct NewUnwrapTagValueModel {
+    pub value: String,
+}
+
 #[derive(Queryable, Selectable, Debug, Clone)]
 #[diesel(table_name = unwrap_device_specs)]
 pub struct UnwrapDeviceSpecsModel {
@@ -442,17 +586,27 @@ pub struct IssueModel {
     pub first_seen: NaiveDateTime,
     pub last_seen: NaiveDateTime,
     pub event_count: i32,
+    pub status: String,
+    pub resolved_in_release: Option<String>,
+    pub resolved_at: Option<NaiveDateTime>,
+    pub ignore_until: Option<NaiveDateTime>,
+    pub ignore_until_event_count: Option<i32>,
+    pub regressed_at: Option<NaiveDateTime>,
+    pub grouping_strategy: Option<String>,
+    pub project_id: i32,
 }
 
 #[derive(Insertable, Debug)]
 #[diesel(table_name = issue)]
 pub struct NewIssueModel {
+    pub project_id: i32,
     pub fingerprint_hash: String,
     pub exception_type_id: Option<i32>,
     pub title: Option<String>,
     pub first_seen: NaiveDateTime,
     pub last_seen: NaiveDateTime,
     pub event_count: i32,
+    pub grouping_strategy: Option<String>,
 }
 
 // ============================================
@@ -497,6 +651,11 @@ pub struct ReportModel {
     pub stacktrace_id: Option<i32>,
     pub issue_id: Option<i32>,
     pub session_id: Option<i32>,
+
+    pub level_id: Option<i32>,
+    pub sdk_name_id: Option<i32>,
+    pub sdk_version_id: Option<i32>,
+    pub release_id: Option<i32>,
 }
 
 #[derive(Insertable, Debug)]
@@ -536,6 +695,321 @@ pub struct NewReportModel {
     pub stacktrace_id: Option<i32>,
     pub issue_id: Option<i32>,
     pub session_id: Option<i32>,
+
+    pub level_id: Option<i32>,
+    pub sdk_name_id: Option<i32>,
+    pub sdk_version_id: Option<i32>,
+    pub release_id: Option<i32>,
+}
+
+// ============================================
+// REPORT EXCEPTION MODEL
+// ============================================
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = report_exception)]
+pub struct ReportExceptionModel {
+    pub id: i32,
+    pub report_id: i32,
+    pub position: i32,
+    pub exception_type_id: Option<i32>,
+    pub exception_message_id: Option<i32>,
+    pub mechanism_type: Option<String>,
+    pub handled: Option<bool>,
+    pub stacktrace_id: Option<i32>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = report_exception)]
+pub struct NewReportExceptionModel {
+    pub report_id: i32,
+    pub position: i32,
+    pub exception_type_id: Option<i32>,
+    pub exception_message_id: Option<i32>,
+    pub mechanism_type: Option<String>,
+    pub handled: Option<bool>,
+    pub stacktrace_id: Option<i32>,
+}
+
+// ============================================
+// REPORT THREAD MODEL
+// ============================================
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = report_thread)]
+pub struct ReportThreadModel {
+    pub id: i32,
+    pub report_id: i32,
+    pub position: i32,
+    pub thread_id: Option<String>,
+    pub name: Option<String>,
+    pub crashed: bool,
+    pub current: bool,
+    pub stacktrace_id: Option<i32>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = report_thread)]
+pub struct NewReportThreadModel {
+    pub report_id: i32,
+    pub position: i32,
+    pub thread_id: Option<String>,
+    pub name: Option<String>,
+    pub crashed: bool,
+    pub current: bool,
+    pub stacktrace_id: Option<i32>,
+}
+
+// ============================================
+// REPORT TAG MODEL
+// ============================================
+
+#[derive(Queryable, Selectable, Insertable, Debug, Clone)]
+#[diesel(table_name = report_tag)]
+pub struct ReportTagModel {
+    pub report_id: i32,
+    pub tag_key_id: i32,
+    pub tag_value_id: i32,
+}
+
+// ============================================
+// REPORT BREADCRUMB MODEL
+// ============================================
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = report_breadcrumb)]
+pub struct ReportBreadcrumbModel {
+    pub id: i32,
+    pub report_id: i32,
+    pub position: i32,
+    pub timestamp: Option<NaiveDateTime>,
+    pub breadcrumb_type: Option<String>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub url: Option<String>,
+    pub data: Option<serde_json::Value>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = report_breadcrumb)]
+pub struct NewReportBreadcrumbModel {
+    pub report_id: i32,
+    pub position: i32,
+    pub timestamp: Option<NaiveDateTime>,
+    pub breadcrumb_type: Option<String>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub url: Option<String>,
+    pub data: Option<serde_json::Value>,
+}
+
+// ============================================
+// REPORT MODULE MODEL
+// ============================================
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = report_module)]
+pub struct ReportModuleModel {
+    pub id: i32,
+    pub report_id: i32,
+    pub position: i32,
+    pub code_file: String,
+    pub code_id: Option<String>,
+    pub debug_file: Option<String>,
+    pub debug_id: Option<String>,
+    pub image_addr: String,
+    pub image_size: i64,
+    pub version: Option<String>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = report_module)]
+pub struct NewReportModuleModel {
+    pub report_id: i32,
+    pub position: i32,
+    pub code_file: String,
+    pub code_id: Option<String>,
+    pub debug_file: Option<String>,
+    pub debug_id: Option<String>,
+    pub image_addr: String,
+    pub image_size: i64,
+    pub version: Option<String>,
+}
+
+// ============================================
+// MONITOR MODELS
+// ============================================
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = monitor)]
+pub struct MonitorModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub slug: String,
+    pub schedule_type: String,
+    pub schedule: String,
+    pub schedule_unit: Option<String>,
+    pub checkin_margin: i32,
+    pub max_runtime: i32,
+    pub timezone: String,
+    pub status: String,
+    pub last_checkin_at: Option<NaiveDateTime>,
+    pub next_checkin_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = monitor)]
+pub struct NewMonitorModel {
+    pub project_id: i32,
+    pub slug: String,
+    pub schedule_type: String,
+    pub schedule: String,
+    pub schedule_unit: Option<String>,
+    pub checkin_margin: i32,
+    pub max_runtime: i32,
+    pub timezone: String,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = monitor_checkin)]
+pub struct MonitorCheckinModel {
+    pub id: i32,
+    pub monitor_id: i32,
+    pub check_in_id: String,
+    pub status: String,
+    pub duration_ms: Option<f64>,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    pub expected_at: Option<NaiveDateTime>,
+    pub received_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = monitor_checkin)]
+pub struct NewMonitorCheckinModel {
+    pub monitor_id: i32,
+    pub check_in_id: String,
+    pub status: String,
+    pub duration_ms: Option<f64>,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    pub expected_at: Option<NaiveDateTime>,
+    pub received_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+// ============================================
+// TRANSACTION MODEL
+// ============================================
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = transaction)]
+pub struct TransactionModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub event_id: String,
+    pub archive_hash: String,
+    pub name_id: i32,
+    pub op_id: Option<i32>,
+    pub status: Option<String>,
+    pub trace_id: Option<String>,
+    pub start_timestamp: NaiveDateTime,
+    pub timestamp: NaiveDateTime,
+    pub duration_ms: f64,
+    pub release_id: Option<i32>,
+    pub environment_id: Option<i32>,
+    pub received_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = transaction)]
+pub struct NewTransactionModel {
+    pub project_id: i32,
+    pub event_id: String,
+    pub archive_hash: String,
+    pub name_id: i32,
+    pub op_id: Option<i32>,
+    pub status: Option<String>,
+    pub trace_id: Option<String>,
+    pub start_timestamp: NaiveDateTime,
+    pub timestamp: NaiveDateTime,
+    pub duration_ms: f64,
+    pub release_id: Option<i32>,
+    pub environment_id: Option<i32>,
+}
+
+// ============================================
+// USER FEEDBACK MODELS
+// ============================================
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = user_feedback)]
+pub struct UserFeedbackModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub event_id: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub comments: String,
+    pub report_id: Option<i32>,
+    pub issue_id: Option<i32>,
+    pub received_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = user_feedback)]
+pub struct NewUserFeedbackModel {
+    pub project_id: i32,
+    pub event_id: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub comments: String,
+    pub report_id: Option<i32>,
+    pub issue_id: Option<i32>,
+}
+
+// ============================================
+// ATTACHMENT MODELS
+// ============================================
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = attachment_blob)]
+pub struct NewAttachmentBlobModel {
+    pub hash: String,
+    pub project_id: i32,
+    pub compressed_payload: Vec<u8>,
+    pub original_size: i32,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = attachment)]
+pub struct AttachmentModel {
+    pub id: i32,
+    pub report_id: i32,
+    pub position: i32,
+    pub project_id: i32,
+    pub hash: String,
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub attachment_type: String,
+    pub size: i32,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = attachment)]
+pub struct NewAttachmentModel {
+    pub report_id: i32,
+    pub position: i32,
+    pub project_id: i32,
+    pub hash: String,
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub attachment_type: String,
+    pub size: i32,
 }
 
 // ============================================
@@ -615,3 +1089,63 @@ pub struct NewBucketRequestLatencyModel {
     pub min_ms: Option<i32>,
     pub max_ms: Option<i32>,
 }
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = bucket_client_discard)]
+pub struct BucketClientDiscardModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub bucket_start: NaiveDateTime,
+    pub reason: String,
+    pub category: String,
+    pub quantity: i32,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = bucket_client_discard)]
+pub struct NewBucketClientDiscardModel {
+    pub project_id: i32,
+    pub bucket_start: NaiveDateTime,
+    pub reason: String,
+    pub category: String,
+    pub quantity: i32,
+}
+
+/// Hourly session rollup, written by the release health job with raw SQL
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = bucket_release_health)]
+pub struct BucketReleaseHealthModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub bucket_start: NaiveDateTime,
+    pub release_id: Option<i32>,
+    pub environment_id: Option<i32>,
+    pub total_sessions: i32,
+    pub crashed: i32,
+    pub abnormal: i32,
+    pub errored: i32,
+    pub total_users: i32,
+    pub crashed_users: i32,
+    pub crash_free_session_rate: f64,
+    pub crash_free_user_rate: Option<f64>,
+    pub computed_at: NaiveDateTime,
+}
+
+/// Hourly transaction duration percentiles, written by the transaction rollup job with raw SQL
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = bucket_transaction_latency)]
+pub struct BucketTransactionLatencyModel {
+    pub id: i32,
+    pub project_id: i32,
+    pub bucket_start: NaiveDateTime,
+    pub name_id: i32,
+    pub release_id: Option<i32>,
+    pub environment_id: Option<i32>,
+    pub transaction_count: i32,
+    pub avg_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub computed_at: NaiveDateTime,
+}
@@ -1,14 +1,15 @@
-use super::DbPool;
+use super::{DbConnection, DbPool};
 use chrono::{NaiveDateTime, Timelike, Utc};
 use diesel::prelude::*;
 
 use crate::shared::persistence::db::models::{
-    NewBucketRateLimitDsnModel, NewBucketRateLimitGlobalModel, NewBucketRateLimitSubnetModel,
-    NewBucketRequestLatencyModel,
+    NewBucketClientDiscardModel, NewBucketRateLimitDsnModel, NewBucketRateLimitGlobalModel,
+    NewBucketRateLimitSubnetModel, NewBucketRequestLatencyModel,
 };
 use crate::shared::persistence::db::schema::{
-    bucket_rate_limit_dsn, bucket_rate_limit_global, bucket_rate_limit_subnet,
-    bucket_request_latency,
+    bucket_client_discard, bucket_rate_limit_dsn, bucket_rate_limit_global,
+    bucket_rate_limit_subnet, bucket_request_latency, client_report_receipt,
+    session_aggregate_receipt,
 };
 
 #[derive(Clone)]
@@ -22,8 +23,12 @@ impl AnalyticsRepository {
     }
 
     fn bucket_start() -> NaiveDateTime {
-        let now = Utc::now().naive_utc();
-        now.with_second(0).unwrap().with_nanosecond(0).unwrap()
+        Self::minute_start(Utc::now().naive_utc())
+    }
+
+    /// Start of the minute bucket holding `at`
+    pub fn minute_start(at: NaiveDateTime) -> NaiveDateTime {
+        at.with_second(0).unwrap().with_nanosecond(0).unwrap()
     }
 
     pub fn record_rate_limit_global(&self) -> Result<(), diesel::result::Error> {
@@ -157,6 +162,53 @@ impl AnalyticsRepository {
         Ok(())
     }
 
+    /// Adds SDK discard counts to their minute buckets. Rows may share a bucket,
+    /// so they are upserted one at a time.
+    pub fn record_client_discards(
+        &self,
+        conn: &mut DbConnection,
+        rows: &[NewBucketClientDiscardModel],
+    ) -> Result<usize, diesel::result::Error> {
+        let mut total = 0;
+        for row in rows {
+            total += diesel::insert_into(bucket_client_discard::table)
+                .values(row)
+                .on_conflict((
+                    bucket_client_discard::project_id,
+                    bucket_client_discard::reason,
+                    bucket_client_discard::category,
+                    bucket_client_discard::bucket_start,
+                ))
+                .do_update()
+                .set(
+                    bucket_client_discard::quantity
+                        .eq(bucket_client_discard::quantity + row.quantity),
+                )
+                .execute(conn)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Records that the client reports of the body `hash` were counted.
+    /// Returns false when they already were, for a retried envelope.
+    pub fn record_client_report_receipt(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        hash: &str,
+    ) -> Result<bool, diesel::result::Error> {
+        let inserted = diesel::insert_into(client_report_receipt::table)
+            .values((
+                client_report_receipt::project_id.eq(project_id),
+                client_report_receipt::hash.eq(hash),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+
+        Ok(inserted > 0)
+    }
+
     pub fn cleanup_old_buckets(&self, retention_days: i64) -> Result<usize, diesel::result::Error> {
         let mut conn = self
             .pool
@@ -188,6 +240,22 @@ impl AnalyticsRepository {
         )
         .execute(&mut conn)?;
 
+        total += diesel::delete(
+            bucket_client_discard::table.filter(bucket_client_discard::bucket_start.lt(cutoff)),
+        )
+        .execute(&mut conn)?;
+
+        total += diesel::delete(
+            client_report_receipt::table.filter(client_report_receipt::received_at.lt(cutoff)),
+        )
+        .execute(&mut conn)?;
+
+        total += diesel::delete(
+            session_aggregate_receipt::table
+                .filter(session_aggregate_receipt::received_at.lt(cutoff)),
+        )
+        .execute(&mut conn)?;
+
         Ok(total)
     }
 
@@ -21,11 +21,12 @@ impl ArchiveRepository {
             compressed_payload: arch.compressed_payload.clone(),
             original_size: arch.original_size,
             created_at: arch.created_at.naive_utc(),
+            compression: arch.compression.clone(),
         };
 
         diesel::insert_into(archive::table)
             .values(&model)
-            .on_conflict(archive::hash)
+            .on_conflict((archive::project_id, archive::hash))
             .do_nothing()
             .execute(conn)
             .map_err(|e| DomainError::Database(e.to_string()))?;
@@ -36,9 +37,11 @@ impl ArchiveRepository {
     pub fn find_by_hash(
         &self,
         conn: &mut DbConnection,
+        project_id: i32,
         hash: &str,
     ) -> Result<Option<Archive>, DomainError> {
         let result = archive::table
+            .filter(archive::project_id.eq(project_id))
             .filter(archive::hash.eq(hash))
             .first::<ArchiveModel>(conn)
             .optional()
@@ -48,13 +51,20 @@ impl ArchiveRepository {
             hash: m.hash,
             project_id: m.project_id,
             compressed_payload: m.compressed_payload,
+            compression: m.compression,
             original_size: m.original_size,
             created_at: Utc.from_utc_datetime(&m.created_at),
         }))
     }
 
-    pub fn exists(&self, conn: &mut DbConnection, hash: &str) -> Result<bool, DomainError> {
+    pub fn exists(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        hash: &str,
+    ) -> Result<bool, DomainError> {
         let count: i64 = archive::table
+            .filter(archive::project_id.eq(project_id))
             .filter(archive::hash.eq(hash))
             .count()
             .get_result(conn)
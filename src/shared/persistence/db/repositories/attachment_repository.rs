@@ -0,0 +1,138 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::{
+    AttachmentModel, NewAttachmentBlobModel, NewAttachmentModel,
+};
+use crate::shared::persistence::db::schema::{attachment, attachment_blob};
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct AttachmentRepository {
+    pool: DbPool,
+}
+
+/// One attachment item of an event, compressed and hashed by the digest
+#[derive(Debug, Clone)]
+pub struct NewAttachment {
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub attachment_type: String,
+    /// SHA-256 of the raw content
+    pub hash: String,
+    pub size: i32,
+    pub compressed_payload: Vec<u8>,
+}
+
+impl AttachmentRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores the attachments of a report, keeping the order of `attachments`
+    /// as `position`. Content already stored for the project is reused.
+    pub fn create_for_report(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        report_id: i32,
+        attachments: Vec<NewAttachment>,
+    ) -> Result<usize, DomainError> {
+        if attachments.is_empty() {
+            return Ok(0);
+        }
+
+        let mut models = Vec::with_capacity(attachments.len());
+        for (position, a) in attachments.into_iter().enumerate() {
+            diesel::insert_into(attachment_blob::table)
+                .values(&NewAttachmentBlobModel {
+                    hash: a.hash.clone(),
+                    project_id,
+                    compressed_payload: a.compressed_payload,
+                    original_size: a.size,
+                })
+                .on_conflict((attachment_blob::project_id, attachment_blob::hash))
+                .do_nothing()
+                .execute(conn)
+                .map_err(|e| DomainError::Database(e.to_string()))?;
+
+            models.push(NewAttachmentModel {
+                report_id,
+                position: position as i32,
+                project_id,
+                hash: a.hash,
+                filename: a.filename,
+                content_type: a.content_type,
+                attachment_type: a.attachment_type,
+                size: a.size,
+            });
+        }
+
+        diesel::insert_into(attachment::table)
+            .values(&models)
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Attachments of a report, in envelope order
+    pub fn find_by_report(&self, report_id: i32) -> Result<Vec<AttachmentModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.find_by_report_with_conn(&mut conn, report_id)
+    }
+
+    pub fn find_by_report_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+    ) -> Result<Vec<AttachmentModel>, DomainError> {
+        attachment::table
+            .filter(attachment::report_id.eq(report_id))
+            .order(attachment::position.asc())
+            .select(AttachmentModel::as_select())
+            .load::<AttachmentModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// An attachment of a report with its compressed content
+    pub fn find_with_payload(
+        &self,
+        report_id: i32,
+        attachment_id: i32,
+    ) -> Result<Option<(AttachmentModel, Vec<u8>)>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.find_with_payload_with_conn(&mut conn, report_id, attachment_id)
+    }
+
+    pub fn find_with_payload_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+        attachment_id: i32,
+    ) -> Result<Option<(AttachmentModel, Vec<u8>)>, DomainError> {
+        let found = attachment::table
+            .filter(attachment::id.eq(attachment_id))
+            .filter(attachment::report_id.eq(report_id))
+            .select(AttachmentModel::as_select())
+            .first::<AttachmentModel>(conn)
+            .optional()
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        let Some(model) = found else {
+            return Ok(None);
+        };
+
+        let payload = attachment_blob::table
+            .filter(attachment_blob::project_id.eq(model.project_id))
+            .filter(attachment_blob::hash.eq(&model.hash))
+            .select(attachment_blob::compressed_payload)
+            .first::<Vec<u8>>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        Ok(Some((model, payload)))
+    }
+}
@@ -56,4 +56,17 @@ impl ExceptionMessageRepository {
             .optional()
             .map_err(|e| DomainError::Database(e.to_string()))
     }
+
+    pub fn find_by_id(
+        &self,
+        conn: &mut DbConnection,
+        id: i32,
+    ) -> Result<Option<UnwrapExceptionMessageModel>, DomainError> {
+        unwrap_exception_message::table
+            .filter(unwrap_exception_message::id.eq(id))
+            .select(UnwrapExceptionMessageModel::as_select())
+            .first::<UnwrapExceptionMessageModel>(conn)
+            .optional()
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
 }
@@ -1,10 +1,99 @@
 use super::{DbConnection, DbPool};
-use crate::shared::domain::DomainError;
+use crate::shared::domain::{DomainError, GroupingStrategy, IssueStatus, IssueStatusChange};
 use crate::shared::persistence::db::models::{IssueModel, NewIssueModel};
-use crate::shared::persistence::db::schema::issue;
-use chrono::Utc;
+use crate::shared::persistence::db::schema::{issue, report, report_tag};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::prelude::*;
 
+/// Sort order for issue listings. All orders are descending, ties broken by id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IssueSort {
+    #[default]
+    LastSeen,
+    FirstSeen,
+    EventCount,
+}
+
+impl IssueSort {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "last_seen" => Some(Self::LastSeen),
+            "first_seen" => Some(Self::FirstSeen),
+            "event_count" => Some(Self::EventCount),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LastSeen => "last_seen",
+            Self::FirstSeen => "first_seen",
+            Self::EventCount => "event_count",
+        }
+    }
+
+    /// Value of the sort column for an issue, as stored in a cursor
+    pub fn key_of(&self, issue: &IssueModel) -> i64 {
+        match self {
+            Self::LastSeen => issue.last_seen.and_utc().timestamp_micros(),
+            Self::FirstSeen => issue.first_seen.and_utc().timestamp_micros(),
+            Self::EventCount => issue.event_count as i64,
+        }
+    }
+}
+
+/// Keyset position of the last issue of a page: (sort column value, issue id),
+/// with the sort it was issued for since the value means something else in another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IssueCursor {
+    pub sort: IssueSort,
+    pub value: i64,
+    pub id: i32,
+}
+
+impl IssueCursor {
+    pub fn encode(&self) -> String {
+        BASE64_URL.encode(format!("{}:{}:{}", self.sort.as_str(), self.value, self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let raw = BASE64_URL.decode(cursor).ok()?;
+        let raw = String::from_utf8(raw).ok()?;
+        let mut parts = raw.splitn(3, ':');
+        Some(Self {
+            sort: IssueSort::parse(parts.next()?)?,
+            value: parts.next()?.parse().ok()?,
+            id: parts.next()?.parse().ok()?,
+        })
+    }
+}
+
+/// Filters for listing the issues of a project. Dimension filters are resolved ids;
+/// an issue matches when at least one of its reports matches every filter.
+#[derive(Debug, Clone, Default)]
+pub struct IssueFilter {
+    pub project_id: i32,
+    pub environment_id: Option<i32>,
+    pub platform_id: Option<i32>,
+    pub exception_type_id: Option<i32>,
+    pub level_id: Option<i32>,
+    pub sdk_name_id: Option<i32>,
+    pub sdk_version_id: Option<i32>,
+    pub status: Option<IssueStatus>,
+    pub app_version_id: Option<i32>,
+    pub app_build_id: Option<i32>,
+    /// Resolved (tag key id, tag value id)
+    pub tag: Option<(i32, i32)>,
+    /// Report timestamp lower bound (unix seconds, inclusive)
+    pub since: Option<i64>,
+    /// Report timestamp upper bound (unix seconds, exclusive)
+    pub until: Option<i64>,
+    pub sort: IssueSort,
+    pub cursor: Option<IssueCursor>,
+    pub limit: i64,
+}
+
 #[derive(Clone)]
 pub struct IssueRepository {
     pool: DbPool,
@@ -18,22 +107,26 @@ impl IssueRepository {
     pub fn get_or_create(
         &self,
         conn: &mut DbConnection,
+        project_id: i32,
         fingerprint_hash: &str,
         exception_type_id: Option<i32>,
         title: Option<String>,
+        grouping_strategy: GroupingStrategy,
     ) -> Result<i32, DomainError> {
         if let Some(existing) = issue::table
+            .filter(issue::project_id.eq(project_id))
             .filter(issue::fingerprint_hash.eq(fingerprint_hash))
             .select(IssueModel::as_select())
             .first::<IssueModel>(conn)
             .optional()
             .map_err(|e| DomainError::Database(e.to_string()))?
         {
+            // Incremented in SQL so concurrent digests do not lose events
             let now = Utc::now().naive_utc();
             diesel::update(issue::table.filter(issue::id.eq(existing.id)))
                 .set((
                     issue::last_seen.eq(now),
-                    issue::event_count.eq(existing.event_count + 1),
+                    issue::event_count.eq(issue::event_count + 1),
                 ))
                 .execute(conn)
                 .map_err(|e| DomainError::Database(e.to_string()))?;
@@ -43,12 +136,14 @@ impl IssueRepository {
 
         let now = Utc::now().naive_utc();
         let new_record = NewIssueModel {
+            project_id,
             fingerprint_hash: fingerprint_hash.to_string(),
             exception_type_id,
             title,
             first_seen: now,
             last_seen: now,
             event_count: 1,
+            grouping_strategy: Some(grouping_strategy.as_str().to_string()),
         };
 
         let id = diesel::insert_into(issue::table)
@@ -62,21 +157,24 @@ impl IssueRepository {
 
     pub fn find_by_fingerprint(
         &self,
+        project_id: i32,
         fingerprint_hash: &str,
     ) -> Result<Option<IssueModel>, DomainError> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
-        self.find_by_fingerprint_with_conn(&mut conn, fingerprint_hash)
+        self.find_by_fingerprint_with_conn(&mut conn, project_id, fingerprint_hash)
     }
 
     pub fn find_by_fingerprint_with_conn(
         &self,
         conn: &mut DbConnection,
+        project_id: i32,
         fingerprint_hash: &str,
     ) -> Result<Option<IssueModel>, DomainError> {
         issue::table
+            .filter(issue::project_id.eq(project_id))
             .filter(issue::fingerprint_hash.eq(fingerprint_hash))
             .select(IssueModel::as_select())
             .first::<IssueModel>(conn)
@@ -123,4 +221,232 @@ impl IssueRepository {
             .load::<IssueModel>(conn)
             .map_err(|e| DomainError::Database(e.to_string()))
     }
+
+    pub fn belongs_to_project_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        issue_id: i32,
+        project_id: i32,
+    ) -> Result<bool, DomainError> {
+        diesel::select(diesel::dsl::exists(
+            issue::table
+                .filter(issue::id.eq(issue_id))
+                .filter(issue::project_id.eq(project_id)),
+        ))
+        .get_result::<bool>(conn)
+        .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn set_status(
+        &self,
+        id: i32,
+        change: &IssueStatusChange,
+    ) -> Result<Option<IssueModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.set_status_with_conn(&mut conn, id, change)
+    }
+
+    /// Applies a status change and returns the updated issue, or `None` if it does not exist.
+    /// Fields belonging to other statuses are cleared.
+    pub fn set_status_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        id: i32,
+        change: &IssueStatusChange,
+    ) -> Result<Option<IssueModel>, DomainError> {
+        let existing = match self.find_by_id_with_conn(conn, id)? {
+            Some(existing) => existing,
+            None => return Ok(None),
+        };
+        let target = issue::table.filter(issue::id.eq(id));
+        let status = issue::status.eq(change.status().as_str());
+
+        let result = match change {
+            IssueStatusChange::Unresolve => diesel::update(target)
+                .set((
+                    status,
+                    issue::resolved_in_release.eq(None::<String>),
+                    issue::resolved_at.eq(None::<NaiveDateTime>),
+                    issue::ignore_until.eq(None::<NaiveDateTime>),
+                    issue::ignore_until_event_count.eq(None::<i32>),
+                ))
+                .returning(IssueModel::as_returning())
+                .get_result::<IssueModel>(conn),
+            IssueStatusChange::Resolve { in_release } => diesel::update(target)
+                .set((
+                    status,
+                    issue::resolved_in_release.eq(in_release.clone()),
+                    issue::resolved_at.eq(Some(Utc::now().naive_utc())),
+                    issue::ignore_until.eq(None::<NaiveDateTime>),
+                    issue::ignore_until_event_count.eq(None::<i32>),
+                ))
+                .returning(IssueModel::as_returning())
+                .get_result::<IssueModel>(conn),
+            IssueStatusChange::Ignore { until, count } => diesel::update(target)
+                .set((
+                    status,
+                    issue::resolved_in_release.eq(None::<String>),
+                    issue::resolved_at.eq(None::<NaiveDateTime>),
+                    issue::ignore_until.eq(*until),
+                    issue::ignore_until_event_count.eq(count.map(|c| existing.event_count + c)),
+                ))
+                .returning(IssueModel::as_returning())
+                .get_result::<IssueModel>(conn),
+        };
+
+        result
+            .map(Some)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Moves an issue back to unresolved, recording a regression when requested
+    pub fn reopen(
+        &self,
+        conn: &mut DbConnection,
+        id: i32,
+        regression: bool,
+    ) -> Result<(), DomainError> {
+        let target = issue::table.filter(issue::id.eq(id));
+        let clear = (
+            issue::status.eq(IssueStatus::Unresolved.as_str()),
+            issue::resolved_in_release.eq(None::<String>),
+            issue::resolved_at.eq(None::<NaiveDateTime>),
+            issue::ignore_until.eq(None::<NaiveDateTime>),
+            issue::ignore_until_event_count.eq(None::<i32>),
+        );
+
+        let result = if regression {
+            diesel::update(target)
+                .set((clear, issue::regressed_at.eq(Some(Utc::now().naive_utc()))))
+                .execute(conn)
+        } else {
+            diesel::update(target).set(clear).execute(conn)
+        };
+
+        result
+            .map(|_| ())
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn list_filtered(&self, filter: &IssueFilter) -> Result<Vec<IssueModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.list_filtered_with_conn(&mut conn, filter)
+    }
+
+    pub fn list_filtered_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        filter: &IssueFilter,
+    ) -> Result<Vec<IssueModel>, DomainError> {
+        let mut matching_reports = report::table
+            .filter(report::project_id.eq(filter.project_id))
+            .filter(report::issue_id.is_not_null())
+            .select(report::issue_id.assume_not_null())
+            .into_boxed();
+
+        if let Some(id) = filter.environment_id {
+            matching_reports = matching_reports.filter(report::environment_id.eq(id));
+        }
+        if let Some(id) = filter.platform_id {
+            matching_reports = matching_reports.filter(report::platform_id.eq(id));
+        }
+        if let Some(id) = filter.level_id {
+            matching_reports = matching_reports.filter(report::level_id.eq(id));
+        }
+        if let Some(id) = filter.sdk_name_id {
+            matching_reports = matching_reports.filter(report::sdk_name_id.eq(id));
+        }
+        if let Some(id) = filter.sdk_version_id {
+            matching_reports = matching_reports.filter(report::sdk_version_id.eq(id));
+        }
+        if let Some(id) = filter.app_version_id {
+            matching_reports = matching_reports.filter(report::app_version_id.eq(id));
+        }
+        if let Some(id) = filter.app_build_id {
+            matching_reports = matching_reports.filter(report::app_build_id.eq(id));
+        }
+        if let Some((key_id, value_id)) = filter.tag {
+            matching_reports = matching_reports.filter(
+                report::id.eq_any(
+                    report_tag::table
+                        .filter(report_tag::tag_key_id.eq(key_id))
+                        .filter(report_tag::tag_value_id.eq(value_id))
+                        .select(report_tag::report_id),
+                ),
+            );
+        }
+        if let Some(since) = filter.since {
+            matching_reports = matching_reports.filter(report::timestamp.ge(since));
+        }
+        if let Some(until) = filter.until {
+            matching_reports = matching_reports.filter(report::timestamp.lt(until));
+        }
+
+        let mut query = issue::table
+            .filter(issue::project_id.eq(filter.project_id))
+            .filter(issue::id.eq_any(matching_reports))
+            .into_boxed();
+
+        if let Some(id) = filter.exception_type_id {
+            query = query.filter(issue::exception_type_id.eq(id));
+        }
+        if let Some(status) = filter.status {
+            query = query.filter(issue::status.eq(status.as_str()));
+        }
+
+        query = match filter.sort {
+            IssueSort::LastSeen => {
+                if let Some(cursor) = &filter.cursor {
+                    let ts = Self::cursor_timestamp(cursor)?;
+                    query = query.filter(
+                        issue::last_seen
+                            .lt(ts)
+                            .or(issue::last_seen.eq(ts).and(issue::id.lt(cursor.id))),
+                    );
+                }
+                query.order((issue::last_seen.desc(), issue::id.desc()))
+            }
+            IssueSort::FirstSeen => {
+                if let Some(cursor) = &filter.cursor {
+                    let ts = Self::cursor_timestamp(cursor)?;
+                    query = query.filter(
+                        issue::first_seen
+                            .lt(ts)
+                            .or(issue::first_seen.eq(ts).and(issue::id.lt(cursor.id))),
+                    );
+                }
+                query.order((issue::first_seen.desc(), issue::id.desc()))
+            }
+            IssueSort::EventCount => {
+                if let Some(cursor) = &filter.cursor {
+                    let count = i32::try_from(cursor.value)
+                        .map_err(|_| DomainError::InvalidRequest("Invalid cursor".to_string()))?;
+                    query = query.filter(
+                        issue::event_count
+                            .lt(count)
+                            .or(issue::event_count.eq(count).and(issue::id.lt(cursor.id))),
+                    );
+                }
+                query.order((issue::event_count.desc(), issue::id.desc()))
+            }
+        };
+
+        query
+            .limit(filter.limit)
+            .select(IssueModel::as_select())
+            .load::<IssueModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    fn cursor_timestamp(cursor: &IssueCursor) -> Result<NaiveDateTime, DomainError> {
+        DateTime::from_timestamp_micros(cursor.value)
+            .map(|dt| dt.naive_utc())
+            .ok_or_else(|| DomainError::InvalidRequest("Invalid cursor".to_string()))
+    }
 }
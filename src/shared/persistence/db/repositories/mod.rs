@@ -1,26 +1,54 @@
 mod analytics_repository;
 mod archive_repository;
+mod attachment_repository;
 mod device_specs_repository;
 mod exception_message_repository;
 mod issue_repository;
+mod monitor_repository;
 mod project_repository;
 mod queue_repository;
+mod release_health_repository;
+mod release_repository;
+mod report_breadcrumb_repository;
+mod report_exception_repository;
+mod report_module_repository;
 mod report_repository;
+mod report_tag_repository;
+mod report_thread_repository;
 mod session_repository;
 mod stacktrace_repository;
+mod transaction_latency_repository;
+mod transaction_repository;
 mod unwrap_repository;
+mod user_feedback_repository;
 
 pub use analytics_repository::AnalyticsRepository;
 pub use archive_repository::ArchiveRepository;
+pub use attachment_repository::{AttachmentRepository, NewAttachment};
 pub use device_specs_repository::{DeviceSpecsParams, DeviceSpecsRepository};
 pub use exception_message_repository::ExceptionMessageRepository;
-pub use issue_repository::IssueRepository;
+pub use issue_repository::{IssueCursor, IssueFilter, IssueRepository, IssueSort};
+pub use monitor_repository::MonitorRepository;
 pub use project_repository::ProjectRepository;
 pub use queue_repository::{QueueErrorRepository, QueueRepository};
+pub use release_health_repository::{
+    ReleaseHealthBucket, ReleaseHealthFilter, ReleaseHealthRepository,
+};
+pub use release_repository::ReleaseRepository;
+pub use report_breadcrumb_repository::{NewReportBreadcrumb, ReportBreadcrumbRepository};
+pub use report_exception_repository::{NewReportException, ReportExceptionRepository};
+pub use report_module_repository::{NewReportModule, ReportModuleRepository};
 pub use report_repository::{NewReport, ReportRepository};
+pub use report_tag_repository::{ReportTagRepository, TagDistribution, TagValueCount};
+pub use report_thread_repository::{NewReportThread, ReportThreadRepository};
 pub use session_repository::*;
 pub use stacktrace_repository::StacktraceRepository;
+pub use transaction_latency_repository::{
+    TransactionLatencyBucket, TransactionLatencyFilter, TransactionLatencyRepository,
+};
+pub use transaction_repository::TransactionRepository;
 pub use unwrap_repository::*;
+pub use user_feedback_repository::UserFeedbackRepository;
 
 use super::{DbConnection, DbPool};
 
@@ -30,7 +58,15 @@ pub struct Repositories {
     pub queue: QueueRepository,
     pub queue_error: QueueErrorRepository,
     pub project: ProjectRepository,
+    pub release: ReleaseRepository,
+    pub release_health: ReleaseHealthRepository,
     pub report: ReportRepository,
+    pub report_exception: ReportExceptionRepository,
+    pub report_thread: ReportThreadRepository,
+    pub report_tag: ReportTagRepository,
+    pub report_breadcrumb: ReportBreadcrumbRepository,
+    pub report_module: ReportModuleRepository,
+    pub attachment: AttachmentRepository,
     pub platform: UnwrapPlatformRepository,
     pub environment: UnwrapEnvironmentRepository,
     pub os_name: UnwrapOsNameRepository,
@@ -49,14 +85,28 @@ pub struct Repositories {
     pub app_build: UnwrapAppBuildRepository,
     pub user: UnwrapUserRepository,
     pub exception_type: UnwrapExceptionTypeRepository,
+    pub level: UnwrapLevelRepository,
+    pub sdk_name: UnwrapSdkNameRepository,
+    pub sdk_version: UnwrapSdkVersionRepository,
+    pub transaction_name: UnwrapTransactionNameRepository,
+    pub transaction_op: UnwrapTransactionOpRepository,
+    pub tag_key: UnwrapTagKeyRepository,
+    pub tag_value: UnwrapTagValueRepository,
     pub exception_message: ExceptionMessageRepository,
     pub stacktrace: StacktraceRepository,
     pub issue: IssueRepository,
+    // Transaction repositories
+    pub transaction: TransactionRepository,
+    pub transaction_latency: TransactionLatencyRepository,
+    // User feedback
+    pub user_feedback: UserFeedbackRepository,
+    // Cron monitors
+    pub monitor: MonitorRepository,
     // Session repositories
     pub session: SessionRepository,
     pub session_status: UnwrapSessionStatusRepository,
-    pub session_release: UnwrapSessionReleaseRepository,
     pub session_environment: UnwrapSessionEnvironmentRepository,
+    pub session_aggregate: SessionAggregateRepository,
     // Analytics
     pub analytics: AnalyticsRepository,
 }
@@ -68,7 +118,15 @@ impl Repositories {
             queue: QueueRepository::new(),
             queue_error: QueueErrorRepository::new(),
             project: ProjectRepository::new(pool.clone()),
+            release: ReleaseRepository::new(pool.clone()),
+            release_health: ReleaseHealthRepository::new(pool.clone()),
             report: ReportRepository::new(pool.clone()),
+            report_exception: ReportExceptionRepository::new(pool.clone()),
+            report_thread: ReportThreadRepository::new(pool.clone()),
+            report_tag: ReportTagRepository::new(pool.clone()),
+            report_breadcrumb: ReportBreadcrumbRepository::new(pool.clone()),
+            report_module: ReportModuleRepository::new(pool.clone()),
+            attachment: AttachmentRepository::new(pool.clone()),
             platform: UnwrapPlatformRepository::new(pool.clone()),
             environment: UnwrapEnvironmentRepository::new(pool.clone()),
             os_name: UnwrapOsNameRepository::new(pool.clone()),
@@ -87,14 +145,28 @@ impl Repositories {
             app_build: UnwrapAppBuildRepository::new(pool.clone()),
             user: UnwrapUserRepository::new(pool.clone()),
             exception_type: UnwrapExceptionTypeRepository::new(pool.clone()),
+            level: UnwrapLevelRepository::new(pool.clone()),
+            sdk_name: UnwrapSdkNameRepository::new(pool.clone()),
+            sdk_version: UnwrapSdkVersionRepository::new(pool.clone()),
+            transaction_name: UnwrapTransactionNameRepository::new(pool.clone()),
+            transaction_op: UnwrapTransactionOpRepository::new(pool.clone()),
+            tag_key: UnwrapTagKeyRepository::new(pool.clone()),
+            tag_value: UnwrapTagValueRepository::new(pool.clone()),
             exception_message: ExceptionMessageRepository::new(pool.clone()),
             stacktrace: StacktraceRepository::new(pool.clone()),
             issue: IssueRepository::new(pool.clone()),
+            // Transaction repositories
+            transaction: TransactionRepository::new(pool.clone()),
+            transaction_latency: TransactionLatencyRepository::new(pool.clone()),
+            // User feedback
+            user_feedback: UserFeedbackRepository::new(pool.clone()),
+            // Cron monitors
+            monitor: MonitorRepository::new(pool.clone()),
             // Session repositories
             session: SessionRepository::new(pool.clone()),
             session_status: UnwrapSessionStatusRepository::new(pool.clone()),
-            session_release: UnwrapSessionReleaseRepository::new(pool.clone()),
             session_environment: UnwrapSessionEnvironmentRepository::new(pool.clone()),
+            session_aggregate: SessionAggregateRepository::new(pool.clone()),
             // Analytics
             analytics: AnalyticsRepository::new(pool),
         }
@@ -0,0 +1,263 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::{CheckInStatus, DomainError};
+use crate::shared::persistence::db::models::{
+    MonitorCheckinModel, MonitorModel, NewMonitorCheckinModel, NewMonitorModel,
+};
+use crate::shared::persistence::db::schema::{monitor, monitor_checkin};
+use chrono::{Duration, NaiveDateTime};
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct MonitorRepository {}
+
+impl MonitorRepository {
+    pub fn new(_pool: DbPool) -> Self {
+        Self {}
+    }
+
+    /// Creates the monitor or replaces its schedule with the one sent by the SDK
+    pub fn upsert(
+        &self,
+        conn: &mut DbConnection,
+        new_monitor: NewMonitorModel,
+    ) -> Result<MonitorModel, DomainError> {
+        diesel::insert_into(monitor::table)
+            .values(&new_monitor)
+            .on_conflict((monitor::project_id, monitor::slug))
+            .do_update()
+            .set((
+                monitor::schedule_type.eq(&new_monitor.schedule_type),
+                monitor::schedule.eq(&new_monitor.schedule),
+                monitor::schedule_unit.eq(&new_monitor.schedule_unit),
+                monitor::checkin_margin.eq(new_monitor.checkin_margin),
+                monitor::max_runtime.eq(new_monitor.max_runtime),
+                monitor::timezone.eq(&new_monitor.timezone),
+            ))
+            .returning(MonitorModel::as_returning())
+            .get_result(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn find_by_slug(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        slug: &str,
+    ) -> Result<Option<MonitorModel>, DomainError> {
+        monitor::table
+            .filter(monitor::project_id.eq(project_id))
+            .filter(monitor::slug.eq(slug))
+            .select(MonitorModel::as_select())
+            .first(conn)
+            .optional()
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn list_by_project(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+    ) -> Result<Vec<MonitorModel>, DomainError> {
+        monitor::table
+            .filter(monitor::project_id.eq(project_id))
+            .order(monitor::slug.asc())
+            .select(MonitorModel::as_select())
+            .load(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Stores a check-in sent by the SDK.
+    ///
+    /// A check-in whose id is already known finishes that run: it updates the
+    /// check-in while it is in progress and is ignored once it is finished
+    /// (e.g. timed out). A new check-in starts a run and moves the monitor's
+    /// next expected check-in to `next_checkin_at`. Finishing check-ins set the
+    /// monitor status.
+    pub fn record_check_in(
+        &self,
+        conn: &mut DbConnection,
+        new_check_in: NewMonitorCheckinModel,
+        next_checkin_at: Option<NaiveDateTime>,
+    ) -> Result<MonitorCheckinModel, DomainError> {
+        conn.transaction(|conn| {
+            let existing = monitor_checkin::table
+                .filter(monitor_checkin::monitor_id.eq(new_check_in.monitor_id))
+                .filter(monitor_checkin::check_in_id.eq(&new_check_in.check_in_id))
+                .select(MonitorCheckinModel::as_select())
+                .for_update()
+                .first(conn)
+                .optional()?;
+
+            let check_in = match existing {
+                Some(existing) if existing.status != CheckInStatus::InProgress.as_str() => {
+                    return Ok(existing);
+                }
+                Some(existing) => diesel::update(monitor_checkin::table.find(existing.id))
+                    .set((
+                        monitor_checkin::status.eq(&new_check_in.status),
+                        monitor_checkin::duration_ms
+                            .eq(new_check_in.duration_ms.or(existing.duration_ms)),
+                        monitor_checkin::updated_at.eq(new_check_in.updated_at),
+                    ))
+                    .returning(MonitorCheckinModel::as_returning())
+                    .get_result(conn)?,
+                None => {
+                    let inserted = diesel::insert_into(monitor_checkin::table)
+                        .values(&new_check_in)
+                        .returning(MonitorCheckinModel::as_returning())
+                        .get_result(conn)?;
+                    diesel::update(monitor::table.find(new_check_in.monitor_id))
+                        .set((
+                            monitor::last_checkin_at.eq(new_check_in.received_at),
+                            monitor::next_checkin_at.eq(next_checkin_at),
+                        ))
+                        .execute(conn)?;
+                    inserted
+                }
+            };
+
+            if check_in.status != CheckInStatus::InProgress.as_str() {
+                diesel::update(monitor::table.find(check_in.monitor_id))
+                    .set(monitor::status.eq(&check_in.status))
+                    .execute(conn)?;
+            }
+            Ok(check_in)
+        })
+        .map_err(|e: diesel::result::Error| DomainError::Database(e.to_string()))
+    }
+
+    /// Monitors whose next expected check-in is before `now`, margin not applied
+    pub fn find_overdue(
+        &self,
+        conn: &mut DbConnection,
+        now: NaiveDateTime,
+    ) -> Result<Vec<MonitorModel>, DomainError> {
+        monitor::table
+            .filter(monitor::next_checkin_at.lt(now))
+            .order(monitor::id.asc())
+            .select(MonitorModel::as_select())
+            .load(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Records a missed check-in for each expected run in `missed` and moves
+    /// the monitor to its next expected check-in. Does nothing when a check-in
+    /// arrived since `overdue` was read as the monitor's next check-in.
+    pub fn record_missed(
+        &self,
+        conn: &mut DbConnection,
+        monitor_id: i32,
+        overdue: NaiveDateTime,
+        missed: &[NaiveDateTime],
+        next_checkin_at: Option<NaiveDateTime>,
+        now: NaiveDateTime,
+    ) -> Result<usize, DomainError> {
+        let check_ins: Vec<NewMonitorCheckinModel> = missed
+            .iter()
+            .map(|expected_at| NewMonitorCheckinModel {
+                monitor_id,
+                check_in_id: uuid::Uuid::new_v4().simple().to_string(),
+                status: CheckInStatus::Missed.as_str().to_string(),
+                duration_ms: None,
+                release: None,
+                environment: None,
+                expected_at: Some(*expected_at),
+                received_at: now,
+                updated_at: now,
+            })
+            .collect();
+
+        conn.transaction(|conn| {
+            let moved = diesel::update(
+                monitor::table
+                    .find(monitor_id)
+                    .filter(monitor::next_checkin_at.eq(overdue)),
+            )
+            .set(monitor::next_checkin_at.eq(next_checkin_at))
+            .execute(conn)?;
+            if moved == 0 || check_ins.is_empty() {
+                return Ok(0);
+            }
+
+            let inserted = diesel::insert_into(monitor_checkin::table)
+                .values(&check_ins)
+                .execute(conn)?;
+            diesel::update(monitor::table.find(monitor_id))
+                .set(monitor::status.eq(CheckInStatus::Missed.as_str()))
+                .execute(conn)?;
+            Ok(inserted)
+        })
+        .map_err(|e: diesel::result::Error| DomainError::Database(e.to_string()))
+    }
+
+    /// Marks in-progress check-ins older than their monitor's max runtime as
+    /// timed out, along with their monitors. Returns how many timed out.
+    pub fn time_out_check_ins(
+        &self,
+        conn: &mut DbConnection,
+        now: NaiveDateTime,
+    ) -> Result<usize, DomainError> {
+        conn.transaction(|conn| {
+            let in_progress = monitor_checkin::table
+                .inner_join(monitor::table)
+                .filter(monitor_checkin::status.eq(CheckInStatus::InProgress.as_str()))
+                .select((
+                    monitor_checkin::id,
+                    monitor_checkin::monitor_id,
+                    monitor_checkin::received_at,
+                    monitor::max_runtime,
+                ))
+                .for_update()
+                .load::<(i32, i32, NaiveDateTime, i32)>(conn)?;
+
+            let (ids, monitor_ids): (Vec<i32>, Vec<i32>) = in_progress
+                .into_iter()
+                .filter(|(_, _, received_at, max_runtime)| {
+                    *received_at + Duration::minutes((*max_runtime).into()) < now
+                })
+                .map(|(id, monitor_id, _, _)| (id, monitor_id))
+                .unzip();
+            if ids.is_empty() {
+                return Ok(0);
+            }
+
+            let timed_out =
+                diesel::update(monitor_checkin::table.filter(monitor_checkin::id.eq_any(&ids)))
+                    .set((
+                        monitor_checkin::status.eq(CheckInStatus::Timeout.as_str()),
+                        monitor_checkin::updated_at.eq(now),
+                    ))
+                    .execute(conn)?;
+            diesel::update(monitor::table.filter(monitor::id.eq_any(&monitor_ids)))
+                .set(monitor::status.eq(CheckInStatus::Timeout.as_str()))
+                .execute(conn)?;
+            Ok(timed_out)
+        })
+        .map_err(|e: diesel::result::Error| DomainError::Database(e.to_string()))
+    }
+
+    /// Check-ins of a monitor, most recent first, optionally of one status
+    pub fn list_check_ins(
+        &self,
+        conn: &mut DbConnection,
+        monitor_id: i32,
+        status: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<MonitorCheckinModel>, DomainError> {
+        let mut query = monitor_checkin::table
+            .filter(monitor_checkin::monitor_id.eq(monitor_id))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(monitor_checkin::status.eq(status));
+        }
+        query
+            .order((
+                monitor_checkin::received_at.desc(),
+                monitor_checkin::id.desc(),
+            ))
+            .limit(limit)
+            .select(MonitorCheckinModel::as_select())
+            .load(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
@@ -18,6 +18,7 @@ impl QueueRepository {
 
     pub fn enqueue(&self, conn: &mut DbConnection, item: &QueueItem) -> Result<i32, DomainError> {
         let model = NewQueueModel {
+            project_id: item.project_id,
             archive_hash: item.archive_hash.clone(),
             created_at: item.created_at.naive_utc(),
         };
@@ -33,6 +34,7 @@ impl QueueRepository {
             Err(_) => {
                 // Conflict occurred, fetch the existing record
                 let existing = queue::table
+                    .filter(queue::project_id.eq(item.project_id))
                     .filter(queue::archive_hash.eq(&item.archive_hash))
                     .select(queue::id)
                     .first::<i32>(conn)
@@ -57,16 +59,26 @@ impl QueueRepository {
             .into_iter()
             .map(|m| QueueItem {
                 id: Some(m.id),
+                project_id: m.project_id,
                 archive_hash: m.archive_hash,
                 created_at: Utc.from_utc_datetime(&m.created_at),
             })
             .collect())
     }
 
-    pub fn remove(&self, conn: &mut DbConnection, archive_hash: &str) -> Result<(), DomainError> {
-        diesel::delete(queue::table.filter(queue::archive_hash.eq(archive_hash)))
-            .execute(conn)
-            .map_err(|e| DomainError::Database(e.to_string()))?;
+    pub fn remove(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        archive_hash: &str,
+    ) -> Result<(), DomainError> {
+        diesel::delete(
+            queue::table
+                .filter(queue::project_id.eq(project_id))
+                .filter(queue::archive_hash.eq(archive_hash)),
+        )
+        .execute(conn)
+        .map_err(|e| DomainError::Database(e.to_string()))?;
 
         Ok(())
     }
@@ -92,10 +104,12 @@ impl QueueErrorRepository {
     pub fn record_error(
         &self,
         conn: &mut DbConnection,
+        project_id: i32,
         archive_hash: &str,
         error: &str,
     ) -> Result<i32, DomainError> {
         let model = NewQueueErrorModel {
+            project_id,
             archive_hash: archive_hash.to_string(),
             error: error.to_string(),
             created_at: Utc::now().naive_utc(),
@@ -111,7 +125,9 @@ impl QueueErrorRepository {
             Err(_) => {
                 // Conflict occurred, update existing record and return its ID
                 let id = diesel::update(
-                    queue_error::table.filter(queue_error::archive_hash.eq(archive_hash)),
+                    queue_error::table
+                        .filter(queue_error::project_id.eq(project_id))
+                        .filter(queue_error::archive_hash.eq(archive_hash)),
                 )
                 .set((
                     queue_error::error.eq(error),
@@ -135,6 +151,7 @@ impl QueueErrorRepository {
             .into_iter()
             .map(|m| QueueError {
                 id: m.id,
+                project_id: m.project_id,
                 archive_hash: m.archive_hash,
                 error: m.error,
                 created_at: Utc.from_utc_datetime(&m.created_at),
@@ -142,10 +159,19 @@ impl QueueErrorRepository {
             .collect())
     }
 
-    pub fn remove(&self, conn: &mut DbConnection, archive_hash: &str) -> Result<(), DomainError> {
-        diesel::delete(queue_error::table.filter(queue_error::archive_hash.eq(archive_hash)))
-            .execute(conn)
-            .map_err(|e| DomainError::Database(e.to_string()))?;
+    pub fn remove(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        archive_hash: &str,
+    ) -> Result<(), DomainError> {
+        diesel::delete(
+            queue_error::table
+                .filter(queue_error::project_id.eq(project_id))
+                .filter(queue_error::archive_hash.eq(archive_hash)),
+        )
+        .execute(conn)
+        .map_err(|e| DomainError::Database(e.to_string()))?;
 
         Ok(())
     }
@@ -0,0 +1,217 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::BucketReleaseHealthModel;
+use crate::shared::persistence::db::schema::{
+    bucket_release_health, release, unwrap_session_environment,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Timestamp};
+use serde::Serialize;
+
+/// Advisory lock key serializing release health refreshes, so two workers or a worker
+/// and the CLI cannot interleave their delete and insert
+const REFRESH_LOCK_KEY: i64 = 0x6275_636b_6574_0001;
+
+/// Recomputes the hourly buckets of every project starting at `$1`, the start
+/// of an hour, so filtering on `started_at` selects whole buckets.
+///
+/// Individual sessions count as one session each, session aggregates as the
+/// sum of their outcome counts. Crashed and abnormal sessions are not counted
+/// as errored.
+const ROLLUP_SQL: &str = r#"
+INSERT INTO bucket_release_health (
+    project_id, bucket_start, release_id, environment_id,
+    total_sessions, crashed, abnormal, errored, total_users, crashed_users,
+    crash_free_session_rate, crash_free_user_rate, computed_at
+)
+SELECT
+    project_id, bucket_start, release_id, environment_id,
+    SUM(sessions),
+    SUM(crashed),
+    SUM(abnormal),
+    SUM(errored),
+    COUNT(DISTINCT distinct_id),
+    COUNT(DISTINCT distinct_id) FILTER (WHERE crashed > 0),
+    1.0 - SUM(crashed)::float8 / SUM(sessions),
+    CASE WHEN COUNT(DISTINCT distinct_id) > 0 THEN
+        1.0 - (COUNT(DISTINCT distinct_id) FILTER (WHERE crashed > 0))::float8
+            / COUNT(DISTINCT distinct_id)
+    END,
+    NOW() AT TIME ZONE 'UTC'
+FROM (
+    SELECT
+        s.project_id, s.release_id, s.environment_id, s.distinct_id,
+        1 AS sessions,
+        (st.value = 'crashed')::int AS crashed,
+        (st.value = 'abnormal')::int AS abnormal,
+        (st.value NOT IN ('crashed', 'abnormal')
+            AND (st.value = 'errored' OR s.errors > 0))::int AS errored,
+        date_trunc('hour', s.started_at) AS bucket_start
+    FROM session s
+    JOIN unwrap_session_status st ON st.id = s.status_id
+    WHERE s.started_at >= $1
+    UNION ALL
+    SELECT
+        a.project_id, a.release_id, a.environment_id, a.distinct_id,
+        a.exited + a.errored + a.abnormal + a.crashed,
+        a.crashed,
+        a.abnormal,
+        a.errored,
+        date_trunc('hour', a.started_at)
+    FROM session_aggregate a
+    WHERE a.started_at >= $1
+) sessions
+WHERE sessions > 0
+GROUP BY project_id, bucket_start, release_id, environment_id
+"#;
+
+#[derive(Clone)]
+pub struct ReleaseHealthRepository {
+    pool: DbPool,
+}
+
+/// Filters for reading rollup buckets of a project
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseHealthFilter {
+    pub project_id: i32,
+    pub release_id: Option<i32>,
+    pub environment: Option<String>,
+    /// Bucket start lower bound (inclusive)
+    pub since: Option<NaiveDateTime>,
+    /// Bucket start upper bound (exclusive)
+    pub until: Option<NaiveDateTime>,
+}
+
+/// One hourly rollup with its release (`version+build`) and environment resolved
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReleaseHealthBucket {
+    pub bucket_start: DateTime<Utc>,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    pub total_sessions: i32,
+    pub crashed: i32,
+    pub abnormal: i32,
+    pub errored: i32,
+    pub total_users: i32,
+    pub crashed_users: i32,
+    pub crash_free_session_rate: f64,
+    pub crash_free_user_rate: Option<f64>,
+}
+
+impl ReleaseHealthRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replaces every bucket starting at or after `since`, the start of an hour,
+    /// with fresh counts from the session and session_aggregate tables. Returns
+    /// the number of buckets written.
+    pub fn refresh_since(&self, since: NaiveDateTime) -> Result<usize, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.refresh_since_with_conn(&mut conn, since)
+    }
+
+    pub fn refresh_since_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        since: NaiveDateTime,
+    ) -> Result<usize, DomainError> {
+        conn.transaction(|conn| {
+            diesel::sql_query("SELECT pg_advisory_xact_lock($1)")
+                .bind::<BigInt, _>(REFRESH_LOCK_KEY)
+                .execute(conn)?;
+
+            diesel::delete(
+                bucket_release_health::table.filter(bucket_release_health::bucket_start.ge(since)),
+            )
+            .execute(conn)?;
+
+            diesel::sql_query(ROLLUP_SQL)
+                .bind::<Timestamp, _>(since)
+                .execute(conn)
+        })
+        .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn list_buckets(
+        &self,
+        filter: &ReleaseHealthFilter,
+    ) -> Result<Vec<ReleaseHealthBucket>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.list_buckets_with_conn(&mut conn, filter)
+    }
+
+    /// Buckets matching the filter, most recent first
+    pub fn list_buckets_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        filter: &ReleaseHealthFilter,
+    ) -> Result<Vec<ReleaseHealthBucket>, DomainError> {
+        let mut query = bucket_release_health::table
+            .left_join(release::table)
+            .left_join(unwrap_session_environment::table)
+            .filter(bucket_release_health::project_id.eq(filter.project_id))
+            .select((
+                BucketReleaseHealthModel::as_select(),
+                release::version.nullable(),
+                release::build.nullable(),
+                unwrap_session_environment::value.nullable(),
+            ))
+            .into_boxed();
+
+        if let Some(id) = filter.release_id {
+            query = query.filter(bucket_release_health::release_id.eq(id));
+        }
+        if let Some(environment) = &filter.environment {
+            query = query.filter(unwrap_session_environment::value.eq(environment.clone()));
+        }
+        if let Some(since) = filter.since {
+            query = query.filter(bucket_release_health::bucket_start.ge(since));
+        }
+        if let Some(until) = filter.until {
+            query = query.filter(bucket_release_health::bucket_start.lt(until));
+        }
+
+        let rows = query
+            .order((
+                bucket_release_health::bucket_start.desc(),
+                bucket_release_health::id,
+            ))
+            .load::<(
+                BucketReleaseHealthModel,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+            )>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(bucket, version, build, environment)| ReleaseHealthBucket {
+                    bucket_start: bucket.bucket_start.and_utc(),
+                    release: version.map(|v| match build.as_deref() {
+                        Some(b) if !b.is_empty() => format!("{}+{}", v, b),
+                        _ => v,
+                    }),
+                    environment,
+                    total_sessions: bucket.total_sessions,
+                    crashed: bucket.crashed,
+                    abnormal: bucket.abnormal,
+                    errored: bucket.errored,
+                    total_users: bucket.total_users,
+                    crashed_users: bucket.crashed_users,
+                    crash_free_session_rate: bucket.crash_free_session_rate,
+                    crash_free_user_rate: bucket.crash_free_user_rate,
+                },
+            )
+            .collect())
+    }
+}
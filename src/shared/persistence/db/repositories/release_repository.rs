@@ -0,0 +1,96 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::{DomainError, split_release};
+use crate::shared::persistence::db::models::{NewReleaseModel, ReleaseModel};
+use crate::shared::persistence::db::schema::release;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+
+#[derive(Clone)]
+pub struct ReleaseRepository {
+    pool: DbPool,
+}
+
+impl ReleaseRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the id of the project's release for a `package@version+build`
+    /// string, creating it on first sight and bumping `last_seen` otherwise.
+    /// Returns `None` for empty releases.
+    pub fn touch(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        release_str: &str,
+    ) -> Result<Option<i32>, DomainError> {
+        let (version, build) = split_release(release_str.trim());
+        if version.is_empty() {
+            return Ok(None);
+        }
+        let now = Utc::now().naive_utc();
+        let new_release = NewReleaseModel {
+            project_id,
+            version: version.to_string(),
+            build: build.unwrap_or("").to_string(),
+            first_seen: now,
+            last_seen: now,
+        };
+
+        diesel::insert_into(release::table)
+            .values(&new_release)
+            .on_conflict((release::project_id, release::version, release::build))
+            .do_update()
+            .set(release::last_seen.eq(excluded(release::last_seen)))
+            .returning(release::id)
+            .get_result::<i32>(conn)
+            .map(Some)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Looks up a release of the project by `package@version+build` string
+    pub fn find_by_name(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        release_str: &str,
+    ) -> Result<Option<ReleaseModel>, DomainError> {
+        let (version, build) = split_release(release_str.trim());
+        release::table
+            .filter(release::project_id.eq(project_id))
+            .filter(release::version.eq(version))
+            .filter(release::build.eq(build.unwrap_or("")))
+            .select(ReleaseModel::as_select())
+            .first::<ReleaseModel>(conn)
+            .optional()
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn find_by_id(
+        &self,
+        conn: &mut DbConnection,
+        id: i32,
+    ) -> Result<Option<ReleaseModel>, DomainError> {
+        release::table
+            .filter(release::id.eq(id))
+            .select(ReleaseModel::as_select())
+            .first::<ReleaseModel>(conn)
+            .optional()
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Releases of a project, most recently seen first
+    pub fn list_by_project(&self, project_id: i32) -> Result<Vec<ReleaseModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        release::table
+            .filter(release::project_id.eq(project_id))
+            .order((release::last_seen.desc(), release::id.desc()))
+            .select(ReleaseModel::as_select())
+            .load::<ReleaseModel>(&mut conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
@@ -0,0 +1,154 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::{
+    NewReportBreadcrumbModel, ReportBreadcrumbModel, ReportModel,
+};
+use crate::shared::persistence::db::schema::{report, report_breadcrumb};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct ReportBreadcrumbRepository {
+    pool: DbPool,
+}
+
+/// One breadcrumb, already capped, resolved before the report row exists
+#[derive(Debug, Clone, Default)]
+pub struct NewReportBreadcrumb {
+    pub timestamp: Option<NaiveDateTime>,
+    pub breadcrumb_type: Option<String>,
+    pub category: Option<String>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub url: Option<String>,
+    pub data: Option<serde_json::Value>,
+}
+
+impl ReportBreadcrumbRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores the breadcrumbs of a report, keeping the order of `breadcrumbs` as `position`
+    pub fn create_for_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+        breadcrumbs: Vec<NewReportBreadcrumb>,
+    ) -> Result<usize, DomainError> {
+        if breadcrumbs.is_empty() {
+            return Ok(0);
+        }
+
+        let models: Vec<NewReportBreadcrumbModel> = breadcrumbs
+            .into_iter()
+            .enumerate()
+            .map(|(position, b)| NewReportBreadcrumbModel {
+                report_id,
+                position: position as i32,
+                timestamp: b.timestamp,
+                breadcrumb_type: b.breadcrumb_type,
+                category: b.category,
+                level: b.level,
+                message: b.message,
+                url: b.url,
+                data: b.data,
+            })
+            .collect();
+
+        diesel::insert_into(report_breadcrumb::table)
+            .values(&models)
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Breadcrumbs of a report, oldest first
+    pub fn find_by_report(
+        &self,
+        report_id: i32,
+    ) -> Result<Vec<ReportBreadcrumbModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.find_by_report_with_conn(&mut conn, report_id)
+    }
+
+    pub fn find_by_report_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+    ) -> Result<Vec<ReportBreadcrumbModel>, DomainError> {
+        report_breadcrumb::table
+            .filter(report_breadcrumb::report_id.eq(report_id))
+            .order(report_breadcrumb::position.asc())
+            .select(ReportBreadcrumbModel::as_select())
+            .load::<ReportBreadcrumbModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Most recent reports of a project having a breadcrumb whose URL or
+    /// navigation target contains `needle` (case-insensitive)
+    pub fn search_reports_by_url(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        needle: &str,
+        limit: i64,
+    ) -> Result<Vec<ReportModel>, DomainError> {
+        let pattern = format!("%{}%", escape_like(needle));
+        let matching = report_breadcrumb::table
+            .filter(report_breadcrumb::url.ilike(pattern))
+            .select(report_breadcrumb::report_id);
+
+        report::table
+            .filter(report::project_id.eq(project_id))
+            .filter(report::id.eq_any(matching))
+            .order((report::timestamp.desc(), report::id.desc()))
+            .limit(limit)
+            .select(ReportModel::as_select())
+            .load::<ReportModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Breadcrumbs of a report whose URL contains `needle`, oldest first
+    pub fn find_matching_in_report(
+        &self,
+        report_id: i32,
+        needle: &str,
+    ) -> Result<Vec<ReportBreadcrumbModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.find_matching_in_report_with_conn(&mut conn, report_id, needle)
+    }
+
+    pub fn find_matching_in_report_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+        needle: &str,
+    ) -> Result<Vec<ReportBreadcrumbModel>, DomainError> {
+        let pattern = format!("%{}%", escape_like(needle));
+        report_breadcrumb::table
+            .filter(report_breadcrumb::report_id.eq(report_id))
+            .filter(report_breadcrumb::url.ilike(pattern))
+            .order(report_breadcrumb::position.asc())
+            .select(ReportBreadcrumbModel::as_select())
+            .load::<ReportBreadcrumbModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
+
+/// Escapes LIKE wildcards so the needle is matched literally
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,69 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::{NewReportExceptionModel, ReportExceptionModel};
+use crate::shared::persistence::db::schema::report_exception;
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct ReportExceptionRepository {}
+
+/// One value of an exception chain, resolved before the report row exists
+#[derive(Debug, Clone, Default)]
+pub struct NewReportException {
+    pub exception_type_id: Option<i32>,
+    pub exception_message_id: Option<i32>,
+    pub mechanism_type: Option<String>,
+    pub handled: Option<bool>,
+    pub stacktrace_id: Option<i32>,
+}
+
+impl ReportExceptionRepository {
+    pub fn new(_pool: DbPool) -> Self {
+        Self {}
+    }
+
+    /// Stores the chain of a report, keeping the order of `exceptions` as `position`
+    pub fn create_for_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+        exceptions: Vec<NewReportException>,
+    ) -> Result<usize, DomainError> {
+        if exceptions.is_empty() {
+            return Ok(0);
+        }
+
+        let models: Vec<NewReportExceptionModel> = exceptions
+            .into_iter()
+            .enumerate()
+            .map(|(position, e)| NewReportExceptionModel {
+                report_id,
+                position: position as i32,
+                exception_type_id: e.exception_type_id,
+                exception_message_id: e.exception_message_id,
+                mechanism_type: e.mechanism_type,
+                handled: e.handled,
+                stacktrace_id: e.stacktrace_id,
+            })
+            .collect();
+
+        diesel::insert_into(report_exception::table)
+            .values(&models)
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Exception chain of a report, in the order the SDK sent it
+    pub fn find_by_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+    ) -> Result<Vec<ReportExceptionModel>, DomainError> {
+        report_exception::table
+            .filter(report_exception::report_id.eq(report_id))
+            .order(report_exception::position.asc())
+            .select(ReportExceptionModel::as_select())
+            .load::<ReportExceptionModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
@@ -0,0 +1,74 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::{NewReportModuleModel, ReportModuleModel};
+use crate::shared::persistence::db::schema::report_module;
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct ReportModuleRepository {}
+
+/// One module of a native crash, read from its minidump
+#[derive(Debug, Clone, Default)]
+pub struct NewReportModule {
+    pub code_file: String,
+    pub code_id: Option<String>,
+    pub debug_file: Option<String>,
+    pub debug_id: Option<String>,
+    /// Load address, hex with `0x` prefix
+    pub image_addr: String,
+    pub image_size: i64,
+    pub version: Option<String>,
+}
+
+impl ReportModuleRepository {
+    pub fn new(_pool: DbPool) -> Self {
+        Self {}
+    }
+
+    /// Stores the modules of a report, keeping the order of `modules` as `position`
+    pub fn create_for_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+        modules: Vec<NewReportModule>,
+    ) -> Result<usize, DomainError> {
+        if modules.is_empty() {
+            return Ok(0);
+        }
+
+        let models: Vec<NewReportModuleModel> = modules
+            .into_iter()
+            .enumerate()
+            .map(|(position, m)| NewReportModuleModel {
+                report_id,
+                position: position as i32,
+                code_file: m.code_file,
+                code_id: m.code_id,
+                debug_file: m.debug_file,
+                debug_id: m.debug_id,
+                image_addr: m.image_addr,
+                image_size: m.image_size,
+                version: m.version,
+            })
+            .collect();
+
+        diesel::insert_into(report_module::table)
+            .values(&models)
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Modules of a report, in module list order
+    pub fn find_by_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+    ) -> Result<Vec<ReportModuleModel>, DomainError> {
+        report_module::table
+            .filter(report_module::report_id.eq(report_id))
+            .order(report_module::position.asc())
+            .select(ReportModuleModel::as_select())
+            .load::<ReportModuleModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
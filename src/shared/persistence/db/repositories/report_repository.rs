@@ -1,11 +1,18 @@
 use super::{DbConnection, DbPool};
 use chrono::Utc;
 use diesel::prelude::*;
+use diesel::sql_types::Text;
 
 use crate::shared::domain::DomainError;
+use crate::shared::parser::normalize_event_id;
 use crate::shared::persistence::db::models::{NewReportModel, ReportModel};
 use crate::shared::persistence::db::schema::report;
 
+// Event ids are stored as sent and matched in the form normalize_event_id
+// gives, through the idx_report_normalized_event_id expression
+define_sql_function!(fn lower(x: Text) -> Text);
+define_sql_function!(fn replace(x: Text, from: Text, to: Text) -> Text);
+
 #[derive(Clone)]
 pub struct ReportRepository {
     pool: DbPool,
@@ -39,6 +46,10 @@ pub struct NewReport {
     pub stacktrace_id: Option<i32>,
     pub issue_id: Option<i32>,
     pub session_id: Option<i32>,
+    pub level_id: Option<i32>,
+    pub sdk_name_id: Option<i32>,
+    pub sdk_version_id: Option<i32>,
+    pub release_id: Option<i32>,
 }
 
 impl ReportRepository {
@@ -52,6 +63,7 @@ impl ReportRepository {
         new_report: NewReport,
     ) -> Result<i32, DomainError> {
         let exists: i64 = report::table
+            .filter(report::project_id.eq(new_report.project_id))
             .filter(report::event_id.eq(&new_report.event_id))
             .count()
             .get_result(conn)
@@ -89,6 +101,10 @@ impl ReportRepository {
             stacktrace_id: new_report.stacktrace_id,
             issue_id: new_report.issue_id,
             session_id: new_report.session_id,
+            level_id: new_report.level_id,
+            sdk_name_id: new_report.sdk_name_id,
+            sdk_version_id: new_report.sdk_version_id,
+            release_id: new_report.release_id,
         };
 
         let id = diesel::insert_into(report::table)
@@ -100,16 +116,31 @@ impl ReportRepository {
         Ok(id)
     }
 
-    pub fn find_by_event_id(&self, event_id: &str) -> Result<Option<ReportModel>, DomainError> {
+    pub fn find_by_event_id(
+        &self,
+        project_id: i32,
+        event_id: &str,
+    ) -> Result<Option<ReportModel>, DomainError> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| DomainError::Database(e.to_string()))?;
+        self.find_in_project_with_conn(&mut conn, project_id, event_id)
+    }
 
+    /// Finds the report of an event, whether its id is given with or without
+    /// dashes and in any case.
+    pub fn find_in_project_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        event_id: &str,
+    ) -> Result<Option<ReportModel>, DomainError> {
         report::table
-            .filter(report::event_id.eq(event_id))
+            .filter(report::project_id.eq(project_id))
+            .filter(replace(lower(report::event_id), "-", "").eq(normalize_event_id(event_id)))
             .select(ReportModel::as_select())
-            .first::<ReportModel>(&mut conn)
+            .first::<ReportModel>(conn)
             .optional()
             .map_err(|e| DomainError::Database(e.to_string()))
     }
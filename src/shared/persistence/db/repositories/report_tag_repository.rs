@@ -0,0 +1,119 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::ReportTagModel;
+use crate::shared::persistence::db::schema::{
+    report, report_tag, unwrap_tag_key, unwrap_tag_value,
+};
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone)]
+pub struct ReportTagRepository {
+    pool: DbPool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagValueCount {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Values of one tag key over the events of an issue, most frequent first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagDistribution {
+    pub key: String,
+    /// Events of the issue carrying this key
+    pub total: i64,
+    pub top_values: Vec<TagValueCount>,
+}
+
+impl ReportTagRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Links a report to its tags, given as resolved (key id, value id) pairs
+    pub fn create_for_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+        tags: &[(i32, i32)],
+    ) -> Result<usize, DomainError> {
+        if tags.is_empty() {
+            return Ok(0);
+        }
+
+        let models: Vec<ReportTagModel> = tags
+            .iter()
+            .map(|&(tag_key_id, tag_value_id)| ReportTagModel {
+                report_id,
+                tag_key_id,
+                tag_value_id,
+            })
+            .collect();
+
+        diesel::insert_into(report_tag::table)
+            .values(&models)
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn top_values_for_issue(
+        &self,
+        issue_id: i32,
+        limit_per_key: usize,
+    ) -> Result<Vec<TagDistribution>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.top_values_for_issue_with_conn(&mut conn, issue_id, limit_per_key)
+    }
+
+    /// Every tag key seen on the events of an issue, sorted by key, with its
+    /// `limit_per_key` most frequent values
+    pub fn top_values_for_issue_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        issue_id: i32,
+        limit_per_key: usize,
+    ) -> Result<Vec<TagDistribution>, DomainError> {
+        let rows = report_tag::table
+            .inner_join(report::table)
+            .inner_join(unwrap_tag_key::table)
+            .inner_join(unwrap_tag_value::table)
+            .filter(report::issue_id.eq(issue_id))
+            .group_by((unwrap_tag_key::value, unwrap_tag_value::value))
+            .select((unwrap_tag_key::value, unwrap_tag_value::value, count_star()))
+            .order((
+                unwrap_tag_key::value.asc(),
+                count_star().desc(),
+                unwrap_tag_value::value.asc(),
+            ))
+            .load::<(String, String, i64)>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        let mut distributions: Vec<TagDistribution> = Vec::new();
+        for (key, value, count) in rows {
+            let distribution = match distributions.last_mut() {
+                Some(last) if last.key == key => last,
+                _ => {
+                    distributions.push(TagDistribution {
+                        key,
+                        total: 0,
+                        top_values: Vec::new(),
+                    });
+                    distributions.last_mut().expect("just pushed")
+                }
+            };
+            distribution.total += count;
+            if distribution.top_values.len() < limit_per_key {
+                distribution.top_values.push(TagValueCount { value, count });
+            }
+        }
+
+        Ok(distributions)
+    }
+}
@@ -0,0 +1,69 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::{NewReportThreadModel, ReportThreadModel};
+use crate::shared::persistence::db::schema::report_thread;
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct ReportThreadRepository {}
+
+/// One thread of the threads interface, resolved before the report row exists
+#[derive(Debug, Clone, Default)]
+pub struct NewReportThread {
+    pub thread_id: Option<String>,
+    pub name: Option<String>,
+    pub crashed: bool,
+    pub current: bool,
+    pub stacktrace_id: Option<i32>,
+}
+
+impl ReportThreadRepository {
+    pub fn new(_pool: DbPool) -> Self {
+        Self {}
+    }
+
+    /// Stores the threads of a report, keeping the order of `threads` as `position`
+    pub fn create_for_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+        threads: Vec<NewReportThread>,
+    ) -> Result<usize, DomainError> {
+        if threads.is_empty() {
+            return Ok(0);
+        }
+
+        let models: Vec<NewReportThreadModel> = threads
+            .into_iter()
+            .enumerate()
+            .map(|(position, t)| NewReportThreadModel {
+                report_id,
+                position: position as i32,
+                thread_id: t.thread_id,
+                name: t.name,
+                crashed: t.crashed,
+                current: t.current,
+                stacktrace_id: t.stacktrace_id,
+            })
+            .collect();
+
+        diesel::insert_into(report_thread::table)
+            .values(&models)
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Threads of a report, in the order the SDK sent them
+    pub fn find_by_report(
+        &self,
+        conn: &mut DbConnection,
+        report_id: i32,
+    ) -> Result<Vec<ReportThreadModel>, DomainError> {
+        report_thread::table
+            .filter(report_thread::report_id.eq(report_id))
+            .order(report_thread::position.asc())
+            .select(ReportThreadModel::as_select())
+            .load::<ReportThreadModel>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
@@ -1,5 +1,5 @@
 use super::{DbConnection, DbPool};
-use crate::shared::domain::DomainError;
+use crate::shared::domain::{DomainError, SessionStatus};
 use crate::shared::persistence::db::models::*;
 use crate::shared::persistence::db::schema::*;
 use diesel::prelude::*;
@@ -69,13 +69,6 @@ impl_session_unwrap_repository!(
     NewUnwrapSessionStatusModel
 );
 
-impl_session_unwrap_repository!(
-    UnwrapSessionReleaseRepository,
-    unwrap_session_release,
-    UnwrapSessionReleaseModel,
-    NewUnwrapSessionReleaseModel
-);
-
 impl_session_unwrap_repository!(
     UnwrapSessionEnvironmentRepository,
     unwrap_session_environment,
@@ -97,47 +90,86 @@ impl SessionRepository {
         Self { pool }
     }
 
-    /// Creates or updates a session. Uses INSERT OR REPLACE on (project_id, sid).
+    /// Creates a session or applies an update to it, keyed on (project_id, sid).
     /// Returns the session ID.
+    ///
+    /// Updates may arrive out of order, so they are merged rather than
+    /// overwriting the row: the status follows [`SessionStatus::merge`],
+    /// `errors` and `timestamp` only grow, `started_at` keeps the earliest value,
+    /// and `duration` comes from the latest update that has one. Release,
+    /// environment and distinct id keep the first value received.
+    ///
+    /// The first update inserts the row; later ones lock it before merging, so
+    /// concurrent updates of the same session (ingest and digest, or two
+    /// workers) are applied one after the other and none is lost.
     pub fn upsert(
         &self,
         conn: &mut DbConnection,
         new_session: NewSessionModel,
     ) -> Result<i32, DomainError> {
-        // Check if session already exists
-        if let Some(existing) = session::table
-            .filter(session::project_id.eq(new_session.project_id))
-            .filter(session::sid.eq(&new_session.sid))
-            .select(SessionModel::as_select())
-            .first::<SessionModel>(conn)
-            .optional()
-            .map_err(|e| DomainError::Database(e.to_string()))?
-        {
-            // Update existing session
+        conn.transaction(|conn| {
+            let inserted = diesel::insert_into(session::table)
+                .values(&new_session)
+                .on_conflict((session::project_id, session::sid))
+                .do_nothing()
+                .returning(session::id)
+                .get_result::<i32>(conn)
+                .optional()?;
+            if let Some(id) = inserted {
+                return Ok(id);
+            }
+
+            let existing = session::table
+                .filter(session::project_id.eq(new_session.project_id))
+                .filter(session::sid.eq(&new_session.sid))
+                .select(SessionModel::as_select())
+                .for_update()
+                .first::<SessionModel>(conn)?;
+
+            let statuses: Vec<(i32, String)> = unwrap_session_status::table
+                .filter(
+                    unwrap_session_status::id.eq_any([existing.status_id, new_session.status_id]),
+                )
+                .select((unwrap_session_status::id, unwrap_session_status::value))
+                .load(conn)?;
+            let status_of = |id: i32| {
+                statuses
+                    .iter()
+                    .find(|(status_id, _)| *status_id == id)
+                    .and_then(|(_, value)| SessionStatus::parse(value))
+                    .unwrap_or_default()
+            };
+            let current = status_of(existing.status_id);
+            let status_id = if current.merge(status_of(new_session.status_id)) == current {
+                existing.status_id
+            } else {
+                new_session.status_id
+            };
+
+            let duration = if new_session.timestamp >= existing.timestamp {
+                new_session.duration.or(existing.duration)
+            } else {
+                existing.duration.or(new_session.duration)
+            };
+
             diesel::update(session::table.filter(session::id.eq(existing.id)))
                 .set((
-                    session::init.eq(new_session.init),
-                    session::started_at.eq(&new_session.started_at),
-                    session::timestamp.eq(&new_session.timestamp),
-                    session::errors.eq(new_session.errors),
-                    session::status_id.eq(new_session.status_id),
-                    session::release_id.eq(new_session.release_id),
-                    session::environment_id.eq(new_session.environment_id),
+                    session::init.eq(existing.init.max(new_session.init)),
+                    session::started_at.eq(existing.started_at.min(new_session.started_at)),
+                    session::timestamp.eq(existing.timestamp.max(new_session.timestamp)),
+                    session::errors.eq(existing.errors.max(new_session.errors)),
+                    session::status_id.eq(status_id),
+                    session::release_id.eq(existing.release_id.or(new_session.release_id)),
+                    session::environment_id
+                        .eq(existing.environment_id.or(new_session.environment_id)),
+                    session::distinct_id.eq(existing.distinct_id.or(new_session.distinct_id)),
+                    session::duration.eq(duration),
                 ))
-                .execute(conn)
-                .map_err(|e| DomainError::Database(e.to_string()))?;
+                .execute(conn)?;
 
-            return Ok(existing.id);
-        }
-
-        // Insert new session
-        let id = diesel::insert_into(session::table)
-            .values(&new_session)
-            .returning(session::id)
-            .get_result::<i32>(conn)
-            .map_err(|e| DomainError::Database(e.to_string()))?;
-
-        Ok(id)
+            Ok(existing.id)
+        })
+        .map_err(|e: diesel::result::Error| DomainError::Database(e.to_string()))
     }
 
     pub fn find_by_sid(
@@ -209,3 +241,77 @@ impl SessionRepository {
             .map_err(|e| DomainError::Database(e.to_string()))
     }
 }
+
+// ============================================
+// SESSION AGGREGATE REPOSITORY
+// ============================================
+
+#[derive(Clone)]
+pub struct SessionAggregateRepository {
+    pool: DbPool,
+}
+
+impl SessionAggregateRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores the buckets of a `sessions` item. Returns the number of rows inserted.
+    pub fn create_many(
+        &self,
+        conn: &mut DbConnection,
+        aggregates: &[NewSessionAggregateModel],
+    ) -> Result<usize, DomainError> {
+        if aggregates.is_empty() {
+            return Ok(0);
+        }
+        diesel::insert_into(session_aggregate::table)
+            .values(aggregates)
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Records that the `sessions` items of the body `hash` were stored.
+    /// Returns false when they already were, for a retried envelope.
+    pub fn record_receipt(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        hash: &str,
+    ) -> Result<bool, DomainError> {
+        let inserted = diesel::insert_into(session_aggregate_receipt::table)
+            .values((
+                session_aggregate_receipt::project_id.eq(project_id),
+                session_aggregate_receipt::hash.eq(hash),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        Ok(inserted > 0)
+    }
+
+    pub fn list_by_project(
+        &self,
+        project_id: i32,
+    ) -> Result<Vec<SessionAggregateModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.list_by_project_with_conn(&mut conn, project_id)
+    }
+
+    pub fn list_by_project_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+    ) -> Result<Vec<SessionAggregateModel>, DomainError> {
+        session_aggregate::table
+            .filter(session_aggregate::project_id.eq(project_id))
+            .order(session_aggregate::started_at.asc())
+            .select(SessionAggregateModel::as_select())
+            .load(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
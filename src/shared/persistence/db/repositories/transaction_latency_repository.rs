@@ -0,0 +1,196 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::BucketTransactionLatencyModel;
+use crate::shared::persistence::db::schema::{
+    bucket_transaction_latency, release, unwrap_environment, unwrap_transaction_name,
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Timestamp};
+use serde::Serialize;
+
+/// Advisory lock key serializing transaction latency refreshes, so two workers or a worker
+/// and the CLI cannot interleave their delete and insert
+const REFRESH_LOCK_KEY: i64 = 0x6275_636b_6574_0002;
+
+/// Recomputes the hourly buckets of every project starting at `$1`.
+///
+/// Transactions are bucketed by the hour of `start_timestamp`; percentiles are
+/// interpolated (`percentile_cont`) over the durations of the bucket.
+const ROLLUP_SQL: &str = r#"
+INSERT INTO bucket_transaction_latency (
+    project_id, bucket_start, name_id, release_id, environment_id,
+    transaction_count, avg_ms, p50_ms, p95_ms, p99_ms, max_ms, computed_at
+)
+SELECT
+    project_id, date_trunc('hour', start_timestamp) AS bucket_start,
+    name_id, release_id, environment_id,
+    COUNT(*),
+    AVG(duration_ms),
+    percentile_cont(0.5) WITHIN GROUP (ORDER BY duration_ms),
+    percentile_cont(0.95) WITHIN GROUP (ORDER BY duration_ms),
+    percentile_cont(0.99) WITHIN GROUP (ORDER BY duration_ms),
+    MAX(duration_ms),
+    NOW() AT TIME ZONE 'UTC'
+FROM transaction
+WHERE start_timestamp >= $1
+GROUP BY project_id, bucket_start, name_id, release_id, environment_id
+"#;
+
+#[derive(Clone)]
+pub struct TransactionLatencyRepository {
+    pool: DbPool,
+}
+
+/// Filters for reading latency buckets of a project
+#[derive(Debug, Clone, Default)]
+pub struct TransactionLatencyFilter {
+    pub project_id: i32,
+    /// Exact transaction name
+    pub name: Option<String>,
+    pub release_id: Option<i32>,
+    pub environment: Option<String>,
+    /// Bucket start lower bound (inclusive)
+    pub since: Option<NaiveDateTime>,
+    /// Bucket start upper bound (exclusive)
+    pub until: Option<NaiveDateTime>,
+}
+
+/// One hourly rollup with its transaction name, release (`version+build`) and
+/// environment resolved
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransactionLatencyBucket {
+    pub bucket_start: DateTime<Utc>,
+    pub name: String,
+    pub release: Option<String>,
+    pub environment: Option<String>,
+    pub count: i32,
+    pub avg_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl TransactionLatencyRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Replaces every bucket starting at or after `since` with fresh
+    /// percentiles from the transaction table. Returns the number of buckets written.
+    pub fn refresh_since(&self, since: NaiveDateTime) -> Result<usize, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.refresh_since_with_conn(&mut conn, since)
+    }
+
+    pub fn refresh_since_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        since: NaiveDateTime,
+    ) -> Result<usize, DomainError> {
+        conn.transaction(|conn| {
+            diesel::sql_query("SELECT pg_advisory_xact_lock($1)")
+                .bind::<BigInt, _>(REFRESH_LOCK_KEY)
+                .execute(conn)?;
+
+            diesel::delete(
+                bucket_transaction_latency::table
+                    .filter(bucket_transaction_latency::bucket_start.ge(since)),
+            )
+            .execute(conn)?;
+
+            diesel::sql_query(ROLLUP_SQL)
+                .bind::<Timestamp, _>(since)
+                .execute(conn)
+        })
+        .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn list_buckets(
+        &self,
+        filter: &TransactionLatencyFilter,
+    ) -> Result<Vec<TransactionLatencyBucket>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.list_buckets_with_conn(&mut conn, filter)
+    }
+
+    /// Buckets matching the filter, most recent first, slowest p95 first within an hour
+    pub fn list_buckets_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        filter: &TransactionLatencyFilter,
+    ) -> Result<Vec<TransactionLatencyBucket>, DomainError> {
+        let mut query = bucket_transaction_latency::table
+            .inner_join(unwrap_transaction_name::table)
+            .left_join(release::table)
+            .left_join(unwrap_environment::table)
+            .filter(bucket_transaction_latency::project_id.eq(filter.project_id))
+            .select((
+                BucketTransactionLatencyModel::as_select(),
+                unwrap_transaction_name::value,
+                release::version.nullable(),
+                release::build.nullable(),
+                unwrap_environment::value.nullable(),
+            ))
+            .into_boxed();
+
+        if let Some(name) = &filter.name {
+            query = query.filter(unwrap_transaction_name::value.eq(name.clone()));
+        }
+        if let Some(id) = filter.release_id {
+            query = query.filter(bucket_transaction_latency::release_id.eq(id));
+        }
+        if let Some(environment) = &filter.environment {
+            query = query.filter(unwrap_environment::value.eq(environment.clone()));
+        }
+        if let Some(since) = filter.since {
+            query = query.filter(bucket_transaction_latency::bucket_start.ge(since));
+        }
+        if let Some(until) = filter.until {
+            query = query.filter(bucket_transaction_latency::bucket_start.lt(until));
+        }
+
+        let rows = query
+            .order((
+                bucket_transaction_latency::bucket_start.desc(),
+                bucket_transaction_latency::p95_ms.desc(),
+                bucket_transaction_latency::id,
+            ))
+            .load::<(
+                BucketTransactionLatencyModel,
+                String,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+            )>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(bucket, name, version, build, environment)| TransactionLatencyBucket {
+                    bucket_start: bucket.bucket_start.and_utc(),
+                    name,
+                    release: version.map(|v| match build.as_deref() {
+                        Some(b) if !b.is_empty() => format!("{}+{}", v, b),
+                        _ => v,
+                    }),
+                    environment,
+                    count: bucket.transaction_count,
+                    avg_ms: bucket.avg_ms,
+                    p50_ms: bucket.p50_ms,
+                    p95_ms: bucket.p95_ms,
+                    p99_ms: bucket.p99_ms,
+                    max_ms: bucket.max_ms,
+                },
+            )
+            .collect())
+    }
+}
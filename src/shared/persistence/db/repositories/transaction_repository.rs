@@ -0,0 +1,60 @@
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::{NewTransactionModel, TransactionModel};
+use crate::shared::persistence::db::schema::transaction;
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct TransactionRepository {
+    pool: DbPool,
+}
+
+impl TransactionRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a transaction. Returns None when the project already has one
+    /// with the same event_id (SDK retry or re-digest).
+    pub fn create(
+        &self,
+        conn: &mut DbConnection,
+        new_transaction: NewTransactionModel,
+    ) -> Result<Option<i32>, DomainError> {
+        diesel::insert_into(transaction::table)
+            .values(&new_transaction)
+            .on_conflict((transaction::project_id, transaction::event_id))
+            .do_nothing()
+            .returning(transaction::id)
+            .get_result::<i32>(conn)
+            .optional()
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn find_by_event_id(
+        &self,
+        project_id: i32,
+        event_id: &str,
+    ) -> Result<Option<TransactionModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.find_by_event_id_with_conn(&mut conn, project_id, event_id)
+    }
+
+    pub fn find_by_event_id_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        event_id: &str,
+    ) -> Result<Option<TransactionModel>, DomainError> {
+        transaction::table
+            .filter(transaction::project_id.eq(project_id))
+            .filter(transaction::event_id.eq(event_id))
+            .select(TransactionModel::as_select())
+            .first::<TransactionModel>(conn)
+            .optional()
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
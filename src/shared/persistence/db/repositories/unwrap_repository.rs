@@ -3,6 +3,7 @@ use crate::shared::domain::DomainError;
 use crate::shared::persistence::db::models::*;
 use crate::shared::persistence::db::schema::*;
 use diesel::prelude::*;
+use std::collections::HashMap;
 
 macro_rules! impl_unwrap_repository {
     ($repo_name:ident, $table:ident, $model:ident, $new_model:ident) => {
@@ -55,6 +56,23 @@ macro_rules! impl_unwrap_repository {
                     .map_err(|e| DomainError::Database(e.to_string()))
             }
 
+            /// Values of the given ids in one query, keyed by id
+            pub fn find_values_by_ids(
+                &self,
+                conn: &mut DbConnection,
+                ids: &[i32],
+            ) -> Result<HashMap<i32, String>, DomainError> {
+                if ids.is_empty() {
+                    return Ok(HashMap::new());
+                }
+                $table::table
+                    .filter($table::id.eq_any(ids))
+                    .select(($table::id, $table::value))
+                    .load::<(i32, String)>(conn)
+                    .map(|rows| rows.into_iter().collect())
+                    .map_err(|e| DomainError::Database(e.to_string()))
+            }
+
             pub fn find_by_value(
                 &self,
                 conn: &mut DbConnection,
@@ -173,3 +191,45 @@ impl_unwrap_repository!(
     UnwrapExceptionTypeModel,
     NewUnwrapExceptionTypeModel
 );
+impl_unwrap_repository!(
+    UnwrapTagKeyRepository,
+    unwrap_tag_key,
+    UnwrapTagKeyModel,
+    NewUnwrapTagKeyModel
+);
+impl_unwrap_repository!(
+    UnwrapTagValueRepository,
+    unwrap_tag_value,
+    UnwrapTagValueModel,
+    NewUnwrapTagValueModel
+);
+impl_unwrap_repository!(
+    UnwrapLevelRepository,
+    unwrap_level,
+    UnwrapLevelModel,
+    NewUnwrapLevelModel
+);
+impl_unwrap_repository!(
+    UnwrapTransactionNameRepository,
+    unwrap_transaction_name,
+    UnwrapTransactionNameModel,
+    NewUnwrapTransactionNameModel
+);
+impl_unwrap_repository!(
+    UnwrapTransactionOpRepository,
+    unwrap_transaction_op,
+    UnwrapTransactionOpModel,
+    NewUnwrapTransactionOpModel
+);
+impl_unwrap_repository!(
+    UnwrapSdkNameRepository,
+    unwrap_sdk_name,
+    UnwrapSdkNameModel,
+    NewUnwrapSdkNameModel
+);
+impl_unwrap_repository!(
+    UnwrapSdkVersionRepository,
+    unwrap_sdk_version,
+    UnwrapSdkVersionModel,
+    NewUnwrapSdkVersionModel
+);
@@ -0,0 +1,107 @@
+use super::report_repository::{lower, replace};
+use super::{DbConnection, DbPool};
+use crate::shared::domain::DomainError;
+use crate::shared::persistence::db::models::{NewUserFeedbackModel, UserFeedbackModel};
+use crate::shared::persistence::db::schema::{report, user_feedback};
+use chrono::Utc;
+use diesel::prelude::*;
+
+#[derive(Clone)]
+pub struct UserFeedbackRepository {
+    pool: DbPool,
+}
+
+impl UserFeedbackRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores feedback, linked to the report and issue of its event when the event
+    /// is already digested. New feedback on the same event replaces the old one.
+    pub fn upsert(
+        &self,
+        conn: &mut DbConnection,
+        mut new_feedback: NewUserFeedbackModel,
+    ) -> Result<i32, DomainError> {
+        if let Some(event_id) = &new_feedback.event_id {
+            let linked = report::table
+                .filter(report::project_id.eq(new_feedback.project_id))
+                // Events keep the id as sent; feedback ids are normalized
+                .filter(replace(lower(report::event_id), "-", "").eq(event_id))
+                .select((report::id, report::issue_id))
+                .first::<(i32, Option<i32>)>(conn)
+                .optional()
+                .map_err(|e| DomainError::Database(e.to_string()))?;
+            if let Some((report_id, issue_id)) = linked {
+                new_feedback.report_id = Some(report_id);
+                new_feedback.issue_id = issue_id;
+            }
+        }
+
+        diesel::insert_into(user_feedback::table)
+            .values(&new_feedback)
+            .on_conflict((user_feedback::project_id, user_feedback::event_id))
+            .do_update()
+            .set((
+                user_feedback::name.eq(&new_feedback.name),
+                user_feedback::email.eq(&new_feedback.email),
+                user_feedback::comments.eq(&new_feedback.comments),
+                user_feedback::report_id.eq(new_feedback.report_id),
+                user_feedback::issue_id.eq(new_feedback.issue_id),
+                user_feedback::received_at.eq(Utc::now().naive_utc()),
+            ))
+            .returning(user_feedback::id)
+            .get_result::<i32>(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    /// Links feedback that arrived before its event to the digested report
+    pub fn link_report(
+        &self,
+        conn: &mut DbConnection,
+        project_id: i32,
+        event_id: &str,
+        report_id: i32,
+        issue_id: Option<i32>,
+    ) -> Result<usize, DomainError> {
+        diesel::update(
+            user_feedback::table
+                .filter(user_feedback::project_id.eq(project_id))
+                .filter(user_feedback::event_id.eq(event_id)),
+        )
+        .set((
+            user_feedback::report_id.eq(report_id),
+            user_feedback::issue_id.eq(issue_id),
+        ))
+        .execute(conn)
+        .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
+    pub fn list_by_issue(
+        &self,
+        issue_id: i32,
+        limit: i64,
+    ) -> Result<Vec<UserFeedbackModel>, DomainError> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| DomainError::ConnectionPool(format!("Connection pool error: {}", e)))?;
+        self.list_by_issue_with_conn(&mut conn, issue_id, limit)
+    }
+
+    /// Feedback on the issue's events, most recent first
+    pub fn list_by_issue_with_conn(
+        &self,
+        conn: &mut DbConnection,
+        issue_id: i32,
+        limit: i64,
+    ) -> Result<Vec<UserFeedbackModel>, DomainError> {
+        user_feedback::table
+            .filter(user_feedback::issue_id.eq(issue_id))
+            .order((user_feedback::received_at.desc(), user_feedback::id.desc()))
+            .limit(limit)
+            .select(UserFeedbackModel::as_select())
+            .load(conn)
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+}
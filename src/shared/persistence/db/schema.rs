@@ -12,12 +12,13 @@ diesel::table! {
 }
 
 diesel::table! {
-    archive (hash) {
+    archive (project_id, hash) {
         hash -> Text,
         project_id -> Integer,
         compressed_payload -> Binary,
         original_size -> Nullable<Integer>,
         created_at -> Timestamp,
+        compression -> Text,
     }
 }
 
@@ -26,6 +27,7 @@ diesel::table! {
         id -> Integer,
         archive_hash -> Text,
         created_at -> Timestamp,
+        project_id -> Integer,
     }
 }
 
@@ -35,6 +37,18 @@ diesel::table! {
         archive_hash -> Text,
         error -> Text,
         created_at -> Timestamp,
+        project_id -> Integer,
+    }
+}
+
+diesel::table! {
+    release (id) {
+        id -> Integer,
+        project_id -> Integer,
+        version -> Text,
+        build -> Text,
+        first_seen -> Timestamp,
+        last_seen -> Timestamp,
     }
 }
 
@@ -81,19 +95,76 @@ diesel::table! {
     }
 }
 
-// ============================================
-// SESSION TABLES
-// ============================================
+diesel::table! {
+    bucket_client_discard (id) {
+        id -> Integer,
+        project_id -> Integer,
+        bucket_start -> Timestamp,
+        reason -> Text,
+        category -> Text,
+        quantity -> Integer,
+    }
+}
 
 diesel::table! {
-    unwrap_session_status (id) {
+    client_report_receipt (project_id, hash) {
+        project_id -> Integer,
+        hash -> Text,
+        received_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    session_aggregate_receipt (project_id, hash) {
+        project_id -> Integer,
+        hash -> Text,
+        received_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    bucket_release_health (id) {
         id -> Integer,
-        value -> Text,
+        project_id -> Integer,
+        bucket_start -> Timestamp,
+        release_id -> Nullable<Integer>,
+        environment_id -> Nullable<Integer>,
+        total_sessions -> Integer,
+        crashed -> Integer,
+        abnormal -> Integer,
+        errored -> Integer,
+        total_users -> Integer,
+        crashed_users -> Integer,
+        crash_free_session_rate -> Double,
+        crash_free_user_rate -> Nullable<Double>,
+        computed_at -> Timestamp,
     }
 }
 
 diesel::table! {
-    unwrap_session_release (id) {
+    bucket_transaction_latency (id) {
+        id -> Integer,
+        project_id -> Integer,
+        bucket_start -> Timestamp,
+        name_id -> Integer,
+        release_id -> Nullable<Integer>,
+        environment_id -> Nullable<Integer>,
+        transaction_count -> Integer,
+        avg_ms -> Double,
+        p50_ms -> Double,
+        p95_ms -> Double,
+        p99_ms -> Double,
+        max_ms -> Double,
+        computed_at -> Timestamp,
+    }
+}
+
+// ============================================
+// SESSION TABLES
+// ============================================
+
+diesel::table! {
+    unwrap_session_status (id) {
         id -> Integer,
         value -> Text,
     }
@@ -112,12 +183,30 @@ diesel::table! {
         project_id -> Integer,
         sid -> Text,
         init -> Integer,
-        started_at -> Text,
-        timestamp -> Text,
+        started_at -> Timestamp,
+        timestamp -> Timestamp,
         errors -> Integer,
         status_id -> Integer,
         release_id -> Nullable<Integer>,
         environment_id -> Nullable<Integer>,
+        distinct_id -> Nullable<Text>,
+        duration -> Nullable<Double>,
+    }
+}
+
+diesel::table! {
+    session_aggregate (id) {
+        id -> Integer,
+        project_id -> Integer,
+        started_at -> Timestamp,
+        release_id -> Nullable<Integer>,
+        environment_id -> Nullable<Integer>,
+        distinct_id -> Nullable<Text>,
+        exited -> Integer,
+        errored -> Integer,
+        abnormal -> Integer,
+        crashed -> Integer,
+        received_at -> Timestamp,
     }
 }
 
@@ -244,6 +333,55 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    unwrap_level (id) {
+        id -> Integer,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    unwrap_sdk_name (id) {
+        id -> Integer,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    unwrap_sdk_version (id) {
+        id -> Integer,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    unwrap_transaction_name (id) {
+        id -> Integer,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    unwrap_transaction_op (id) {
+        id -> Integer,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    unwrap_tag_key (id) {
+        id -> Integer,
+        value -> Text,
+    }
+}
+
+diesel::table! {
+    unwrap_tag_value (id) {
+        id -> Integer,
+        value -> Text,
+    }
+}
+
 diesel::table! {
     unwrap_device_specs (id) {
         id -> Integer,
@@ -287,6 +425,14 @@ diesel::table! {
         first_seen -> Timestamp,
         last_seen -> Timestamp,
         event_count -> Integer,
+        status -> Text,
+        resolved_in_release -> Nullable<Text>,
+        resolved_at -> Nullable<Timestamp>,
+        ignore_until -> Nullable<Timestamp>,
+        ignore_until_event_count -> Nullable<Integer>,
+        regressed_at -> Nullable<Timestamp>,
+        grouping_strategy -> Nullable<Text>,
+        project_id -> Integer,
     }
 }
 
@@ -331,6 +477,169 @@ diesel::table! {
         stacktrace_id -> Nullable<Integer>,
         issue_id -> Nullable<Integer>,
         session_id -> Nullable<Integer>,
+
+        level_id -> Nullable<Integer>,
+        sdk_name_id -> Nullable<Integer>,
+        sdk_version_id -> Nullable<Integer>,
+        release_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    report_exception (id) {
+        id -> Integer,
+        report_id -> Integer,
+        position -> Integer,
+        exception_type_id -> Nullable<Integer>,
+        exception_message_id -> Nullable<Integer>,
+        mechanism_type -> Nullable<Text>,
+        handled -> Nullable<Bool>,
+        stacktrace_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    report_thread (id) {
+        id -> Integer,
+        report_id -> Integer,
+        position -> Integer,
+        thread_id -> Nullable<Text>,
+        name -> Nullable<Text>,
+        crashed -> Bool,
+        current -> Bool,
+        stacktrace_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    report_breadcrumb (id) {
+        id -> Integer,
+        report_id -> Integer,
+        position -> Integer,
+        timestamp -> Nullable<Timestamp>,
+        breadcrumb_type -> Nullable<Text>,
+        category -> Nullable<Text>,
+        level -> Nullable<Text>,
+        message -> Nullable<Text>,
+        url -> Nullable<Text>,
+        data -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    report_tag (report_id, tag_key_id) {
+        report_id -> Integer,
+        tag_key_id -> Integer,
+        tag_value_id -> Integer,
+    }
+}
+
+// ============================================
+// TRANSACTION TABLE
+// ============================================
+
+diesel::table! {
+    transaction (id) {
+        id -> Integer,
+        project_id -> Integer,
+        event_id -> Text,
+        archive_hash -> Text,
+        name_id -> Integer,
+        op_id -> Nullable<Integer>,
+        status -> Nullable<Text>,
+        trace_id -> Nullable<Text>,
+        start_timestamp -> Timestamp,
+        timestamp -> Timestamp,
+        duration_ms -> Double,
+        release_id -> Nullable<Integer>,
+        environment_id -> Nullable<Integer>,
+        received_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_feedback (id) {
+        id -> Integer,
+        project_id -> Integer,
+        event_id -> Nullable<Text>,
+        name -> Nullable<Text>,
+        email -> Nullable<Text>,
+        comments -> Text,
+        report_id -> Nullable<Integer>,
+        issue_id -> Nullable<Integer>,
+        received_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    report_module (id) {
+        id -> Integer,
+        report_id -> Integer,
+        position -> Integer,
+        code_file -> Text,
+        code_id -> Nullable<Text>,
+        debug_file -> Nullable<Text>,
+        debug_id -> Nullable<Text>,
+        image_addr -> Text,
+        image_size -> BigInt,
+        version -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    attachment_blob (project_id, hash) {
+        hash -> Text,
+        project_id -> Integer,
+        compressed_payload -> Binary,
+        original_size -> Integer,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    attachment (id) {
+        id -> Integer,
+        report_id -> Integer,
+        position -> Integer,
+        project_id -> Integer,
+        hash -> Text,
+        filename -> Text,
+        content_type -> Nullable<Text>,
+        attachment_type -> Text,
+        size -> Integer,
+    }
+}
+
+diesel::table! {
+    monitor (id) {
+        id -> Integer,
+        project_id -> Integer,
+        slug -> Text,
+        schedule_type -> Text,
+        schedule -> Text,
+        schedule_unit -> Nullable<Text>,
+        checkin_margin -> Integer,
+        max_runtime -> Integer,
+        timezone -> Text,
+        status -> Text,
+        last_checkin_at -> Nullable<Timestamp>,
+        next_checkin_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    monitor_checkin (id) {
+        id -> Integer,
+        monitor_id -> Integer,
+        check_in_id -> Text,
+        status -> Text,
+        duration_ms -> Nullable<Double>,
+        release -> Nullable<Text>,
+        environment -> Nullable<Text>,
+        expected_at -> Nullable<Timestamp>,
+        received_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -338,9 +647,8 @@ diesel::table! {
 // JOINABLE RELATIONS
 // ============================================
 
-diesel::joinable!(queue -> archive (archive_hash));
-diesel::joinable!(queue_error -> archive (archive_hash));
-diesel::joinable!(report -> archive (archive_hash));
+// queue, queue_error and report reference archive by (project_id, archive_hash),
+// which joinable! cannot express
 diesel::joinable!(report -> project (project_id));
 diesel::joinable!(report -> unwrap_platform (platform_id));
 diesel::joinable!(report -> unwrap_environment (environment_id));
@@ -363,21 +671,64 @@ diesel::joinable!(report -> unwrap_exception_type (exception_type_id));
 diesel::joinable!(report -> unwrap_exception_message (exception_message_id));
 diesel::joinable!(report -> unwrap_stacktrace (stacktrace_id));
 diesel::joinable!(report -> issue (issue_id));
+diesel::joinable!(report -> unwrap_level (level_id));
+diesel::joinable!(report -> unwrap_sdk_name (sdk_name_id));
+diesel::joinable!(report -> unwrap_sdk_version (sdk_version_id));
+diesel::joinable!(report_exception -> report (report_id));
+diesel::joinable!(report_exception -> unwrap_exception_type (exception_type_id));
+diesel::joinable!(report_exception -> unwrap_exception_message (exception_message_id));
+diesel::joinable!(report_exception -> unwrap_stacktrace (stacktrace_id));
+diesel::joinable!(report_thread -> report (report_id));
+diesel::joinable!(report_thread -> unwrap_stacktrace (stacktrace_id));
+diesel::joinable!(report_tag -> report (report_id));
+diesel::joinable!(report_breadcrumb -> report (report_id));
+diesel::joinable!(report_module -> report (report_id));
+diesel::joinable!(report_tag -> unwrap_tag_key (tag_key_id));
+diesel::joinable!(report_tag -> unwrap_tag_value (tag_value_id));
 diesel::joinable!(issue -> unwrap_exception_type (exception_type_id));
+diesel::joinable!(issue -> project (project_id));
 diesel::joinable!(session -> project (project_id));
 diesel::joinable!(session -> unwrap_session_status (status_id));
-diesel::joinable!(session -> unwrap_session_release (release_id));
+diesel::joinable!(session -> release (release_id));
+diesel::joinable!(report -> release (release_id));
+diesel::joinable!(release -> project (project_id));
 diesel::joinable!(session -> unwrap_session_environment (environment_id));
+diesel::joinable!(session_aggregate -> project (project_id));
+diesel::joinable!(session_aggregate -> release (release_id));
+diesel::joinable!(session_aggregate -> unwrap_session_environment (environment_id));
 diesel::joinable!(report -> session (session_id));
+diesel::joinable!(bucket_release_health -> project (project_id));
+diesel::joinable!(bucket_release_health -> release (release_id));
+diesel::joinable!(bucket_release_health -> unwrap_session_environment (environment_id));
+diesel::joinable!(transaction -> project (project_id));
+diesel::joinable!(transaction -> unwrap_transaction_name (name_id));
+diesel::joinable!(transaction -> unwrap_transaction_op (op_id));
+diesel::joinable!(transaction -> release (release_id));
+diesel::joinable!(transaction -> unwrap_environment (environment_id));
+diesel::joinable!(user_feedback -> project (project_id));
+diesel::joinable!(user_feedback -> report (report_id));
+diesel::joinable!(user_feedback -> issue (issue_id));
+diesel::joinable!(attachment -> report (report_id));
+diesel::joinable!(attachment_blob -> project (project_id));
+diesel::joinable!(bucket_transaction_latency -> project (project_id));
+diesel::joinable!(bucket_transaction_latency -> unwrap_transaction_name (name_id));
+diesel::joinable!(bucket_transaction_latency -> release (release_id));
+diesel::joinable!(bucket_transaction_latency -> unwrap_environment (environment_id));
+diesel::joinable!(bucket_client_discard -> project (project_id));
+diesel::joinable!(client_report_receipt -> project (project_id));
+diesel::joinable!(session_aggregate_receipt -> project (project_id));
+diesel::joinable!(monitor -> project (project_id));
+diesel::joinable!(monitor_checkin -> monitor (monitor_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     project,
     archive,
     queue,
     queue_error,
+    release,
     session,
+    session_aggregate,
     unwrap_session_status,
-    unwrap_session_release,
     unwrap_session_environment,
     unwrap_platform,
     unwrap_environment,
@@ -396,13 +747,42 @@ diesel::allow_tables_to_appear_in_same_query!(
     unwrap_app_build,
     unwrap_user,
     unwrap_exception_type,
+    unwrap_level,
+    unwrap_sdk_name,
+    unwrap_sdk_version,
+    unwrap_transaction_name,
+    unwrap_transaction_op,
+    unwrap_tag_key,
+    unwrap_tag_value,
     unwrap_device_specs,
     unwrap_exception_message,
     unwrap_stacktrace,
     issue,
     report,
+    report_exception,
+    report_thread,
+    report_tag,
+    report_breadcrumb,
+    report_module,
+    transaction,
+    user_feedback,
+    attachment,
+    attachment_blob,
+    monitor,
+    monitor_checkin,
     bucket_rate_limit_global,
     bucket_rate_limit_dsn,
     bucket_rate_limit_subnet,
     bucket_request_latency,
+    bucket_release_health,
+    bucket_transaction_latency,
+    bucket_client_discard,
+    client_report_receipt,
+    session_aggregate_receipt,
+);
+
+// Tag distributions group by key and value together
+diesel::allow_columns_to_appear_in_same_group_by_clause!(
+    unwrap_tag_key::value,
+    unwrap_tag_value::value
 );
@@ -1,8 +1,13 @@
 pub mod db;
 
 pub use db::{
-    AnalyticsRepository, ArchiveRepository, DbConnection, DbPool, DeviceSpecsParams, NewReport,
-    ProjectRepository, QueueErrorRepository, QueueRepository, Repositories, SessionRepository,
-    UnwrapSessionEnvironmentRepository, UnwrapSessionReleaseRepository,
-    UnwrapSessionStatusRepository, establish_connection_pool, run_migrations,
+    AnalyticsRepository, ArchiveRepository, AttachmentRepository, DbConnection, DbPool,
+    DeviceSpecsParams, IssueCursor, IssueFilter, IssueRepository, IssueSort, MonitorRepository,
+    NewAttachment, NewReport, NewReportBreadcrumb, NewReportException, NewReportModule,
+    NewReportThread, ProjectRepository, QueueErrorRepository, QueueRepository, ReleaseHealthBucket,
+    ReleaseHealthFilter, ReleaseHealthRepository, ReleaseRepository, ReportTagRepository,
+    Repositories, SessionAggregateRepository, SessionRepository, TagDistribution, TagValueCount,
+    TransactionLatencyBucket, TransactionLatencyFilter, TransactionLatencyRepository,
+    UnwrapSessionEnvironmentRepository, UnwrapSessionStatusRepository, UserFeedbackRepository,
+    establish_connection_pool, run_migrations,
 };
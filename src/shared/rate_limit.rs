@@ -199,7 +199,10 @@ where
             if response.status() == StatusCode::TOO_MANY_REQUESTS {
                 match limit_type {
                     RateLimitType::Global => {
-                        warn!(subnet = ip.as_deref().map(|i| mask_ip(i)).as_deref().unwrap_or("unknown"), "Rate limit GLOBAL");
+                        warn!(
+                            subnet = ip.as_deref().map(mask_ip).as_deref().unwrap_or("unknown"),
+                            "Rate limit GLOBAL"
+                        );
                         collector.record_rate_limit_global();
                     }
                     RateLimitType::Ip => {
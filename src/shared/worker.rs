@@ -0,0 +1,80 @@
+use chrono::{NaiveDateTime, Utc};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::shared::domain::DomainError;
+
+/// A job that PeriodicWorker runs on a blocking thread every interval
+pub trait PeriodicJob: Clone + Send + 'static {
+    type Output: Send + 'static;
+
+    /// Names the worker in logs
+    const NAME: &'static str;
+
+    fn execute(&self, now: NaiveDateTime) -> Result<Self::Output, DomainError>;
+
+    /// Logs the outcome of a successful run
+    fn log_success(output: &Self::Output, elapsed: Duration);
+}
+
+/// Runs a job every `interval_secs` until its shutdown handle is set
+pub struct PeriodicWorker<J: PeriodicJob> {
+    job: J,
+    interval_secs: u64,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl<J: PeriodicJob> PeriodicWorker<J> {
+    pub fn new(job: J, interval_secs: u64) -> Self {
+        Self {
+            job,
+            interval_secs,
+            shutdown: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn shutdown_handle(&self) -> Arc<AtomicBool> {
+        self.shutdown.clone()
+    }
+
+    pub async fn run(&self) {
+        info!(
+            worker = J::NAME,
+            interval_secs = self.interval_secs,
+            "Starting periodic worker"
+        );
+
+        // Tick every second so shutdown is not held up by a long interval
+        let period = Duration::from_secs(self.interval_secs.max(1));
+        let mut ticker = interval(Duration::from_secs(1));
+        let mut last_run: Option<Instant> = None;
+
+        loop {
+            ticker.tick().await;
+
+            if self.shutdown.load(Ordering::SeqCst) {
+                info!(worker = J::NAME, "Periodic worker shutting down");
+                break;
+            }
+
+            if last_run.is_some_and(|t| t.elapsed() < period) {
+                continue;
+            }
+            last_run = Some(Instant::now());
+
+            let job = self.job.clone();
+            let start = Instant::now();
+            let result =
+                tokio::task::spawn_blocking(move || job.execute(Utc::now().naive_utc())).await;
+
+            match result {
+                Ok(Ok(output)) => J::log_success(&output, start.elapsed()),
+                Ok(Err(e)) => warn!(worker = J::NAME, error = %e, "Periodic job failed"),
+                Err(e) => warn!(worker = J::NAME, error = %e, "Periodic job task panicked"),
+            }
+        }
+    }
+}